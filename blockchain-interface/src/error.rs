@@ -45,6 +45,28 @@ pub enum BlockchainError {
     InvalidEpoch,
     #[error("Accounts diff not found")]
     AccountsDiffNotFound,
+    #[error("Archive mode is not enabled")]
+    ArchiveNotEnabled,
+    #[error("Accounts state is not archived for block {0}")]
+    StateNotArchived(u32),
+    #[error("Archived accounts state is corrupted for block {0}")]
+    ArchiveCorrupted(u32),
+    #[error("Transaction not found")]
+    TransactionNotFound,
+    #[error("Transaction is not part of a finalized batch yet")]
+    TransactionNotFinalized,
+    #[error("Failed to prove the transaction inclusion")]
+    CouldNotProveInclusion,
+    #[error("Block is not an election block")]
+    BlockIsNotElection,
+    #[error("Signal tally not found for block {0}")]
+    SignalTallyNotFound(u32),
+    #[error("Invalid equivocation proof: {0}")]
+    InvalidEquivocationProof(#[from] EquivocationProofError),
+    #[error("Proof for equivocation already included")]
+    EquivocationAlreadyIncluded(EquivocationLocator),
+    #[error("Equivocation proof is outside of its reporting window")]
+    EquivocationProofExpired,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,133 @@
+use nimiq_block::{Block, BlockInclusionProof, MacroBlock};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{
+    historic_transaction::{HistoricTransaction, RawTransactionHash},
+    history_proof::HistoryTreeProof,
+};
+use thiserror::Error;
+
+/// A self-contained proof that a transaction is part of the finalized chain. The proof can be
+/// verified offline by anyone who knows the hash of the macro block it is anchored at.
+///
+/// It consists of:
+///  - a history tree proof of the transaction against the history root of the proving block,
+///  - the header of the proving block, i.e. the election block that finalized the epoch of the
+///    transaction or the checkpoint block that finalized its batch,
+///  - the anchor block and the interlink chain of election blocks connecting it to the proving
+///    block. If the proving block is a checkpoint block, it is the anchor block itself.
+#[derive(Serialize, Deserialize)]
+pub struct TransactionInclusionProof {
+    /// The proof of the transaction in the history tree of the proving block.
+    pub history_proof: HistoryTreeProof,
+    /// The macro block whose history root the history proof is verified against.
+    pub block: Block,
+    /// The macro block the proof is anchored at.
+    pub anchor_block: MacroBlock,
+    /// The chain of election blocks connecting the anchor block to the proving block.
+    pub block_proof: BlockInclusionProof,
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum InclusionProofError {
+    #[error("Proof is not anchored at the trusted block")]
+    UntrustedAnchorBlock,
+    #[error("Transaction is not part of the proof")]
+    TransactionNotIncluded,
+    #[error("Invalid history tree proof")]
+    InvalidHistoryProof,
+    #[error("Proving block is not part of the chain of the anchor block")]
+    InvalidBlockProof,
+}
+
+impl TransactionInclusionProof {
+    /// Returns the proven historic transaction with the given hash, if it is part of the proof.
+    pub fn get_transaction(&self, tx_hash: &Blake2bHash) -> Option<&HistoricTransaction> {
+        let tx_hash = RawTransactionHash::from(tx_hash.clone());
+        self.history_proof
+            .history
+            .iter()
+            .find(|hist_tx| hist_tx.tx_hash() == tx_hash)
+    }
+
+    /// Verifies that the transaction with the given hash is part of the chain that leads to the
+    /// macro block with the given hash. The anchor block hash must be obtained from a trusted
+    /// source, e.g. the verifier's own node.
+    pub fn verify(
+        &self,
+        tx_hash: &Blake2bHash,
+        trusted_anchor_hash: &Blake2bHash,
+    ) -> Result<(), InclusionProofError> {
+        // The proof must be anchored at the trusted block.
+        if self.anchor_block.hash() != *trusted_anchor_hash {
+            return Err(InclusionProofError::UntrustedAnchorBlock);
+        }
+
+        // The transaction must be part of the history of the proving block's epoch.
+        let hist_tx = self
+            .get_transaction(tx_hash)
+            .ok_or(InclusionProofError::TransactionNotIncluded)?;
+        if hist_tx.block_number > self.block.block_number()
+            || Policy::epoch_at(hist_tx.block_number) != self.block.epoch_number()
+        {
+            return Err(InclusionProofError::TransactionNotIncluded);
+        }
+
+        // The history proof must verify against the history root of the proving block.
+        if self.history_proof.history.len() != self.history_proof.positions.len()
+            || self.history_proof.verify(self.block.history_root().clone()) != Some(true)
+        {
+            return Err(InclusionProofError::InvalidHistoryProof);
+        }
+
+        // The proving block must be part of the chain of the anchor block.
+        if !self.is_block_proven() {
+            return Err(InclusionProofError::InvalidBlockProof);
+        }
+
+        Ok(())
+    }
+
+    fn is_block_proven(&self) -> bool {
+        let block = match self.block {
+            Block::Macro(ref block) => block,
+            Block::Micro(_) => return false,
+        };
+        let block_hash = block.hash();
+
+        // The proving block is trivially proven if it is the anchor block itself. Otherwise, both
+        // blocks need to be election blocks, since only those are linked via the interlink chain.
+        if block_hash == self.anchor_block.hash() {
+            return true;
+        }
+        if !block.is_election() || !self.anchor_block.is_election() {
+            return false;
+        }
+
+        // The proving block is either directly referenced by the anchor block or connected to it
+        // via the interlink chain.
+        if block_hash == self.anchor_block.header.parent_election_hash
+            || self
+                .anchor_block
+                .header
+                .interlink
+                .as_ref()
+                .is_some_and(|interlink| interlink.contains(&block_hash))
+        {
+            return true;
+        }
+
+        // Blocks after the anchor block can't be proven.
+        if block.block_number() > self.anchor_block.block_number() {
+            return false;
+        }
+
+        // If no hops are needed, the anchor block must have referenced the block directly.
+        let hops = BlockInclusionProof::get_interlink_hops(
+            block.block_number(),
+            self.anchor_block.block_number(),
+        );
+        !hops.is_empty() && self.block_proof.is_block_proven(&self.anchor_block, block)
+    }
+}
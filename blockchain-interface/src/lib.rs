@@ -5,8 +5,10 @@ pub use error::{
     BlockchainError, BlockchainEvent, ChunksPushError, ChunksPushResult, Direction, ForkEvent,
     PushError, PushResult,
 };
+pub use inclusion_proof::{InclusionProofError, TransactionInclusionProof};
 
 mod abstract_blockchain;
 mod chain_info;
 mod chain_ordering;
 mod error;
+mod inclusion_proof;
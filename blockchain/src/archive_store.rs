@@ -0,0 +1,480 @@
+use std::{collections::BTreeMap, convert::TryInto};
+
+use nimiq_account::Accounts;
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy, TransactionProxy, WriteTransactionProxy,
+};
+use nimiq_primitives::{key_nibbles::KeyNibbles, trie::trie_diff::TrieDiff};
+
+/// A struct that contains the databases of the archive. The archive keeps the state of the
+/// accounts trie at every macro block, so that historic account and staking contract queries can
+/// be answered for any archived macro block.
+///
+/// Instead of storing a full snapshot of the trie at every macro block, the archive stores diff
+/// chains: A full snapshot of the trie at the first macro block of a segment, followed by the
+/// values of all trie keys that changed in each subsequent batch of that segment. A new segment
+/// starts whenever the archive can't be continued with a diff, e.g. after a gap in which the
+/// accounts trie was incomplete. The value of a key at a given macro block is thus the most recent value that was stored
+/// for it in the diff chain of the segment, or its value in the snapshot of the segment.
+///
+/// The keys of the value database are constructed as follows: Every nibble of the trie key is
+/// stored as a separate byte (incremented by one), followed by a zero byte and the big-endian byte
+/// representation of the macro block number. This way, all versions of a trie key are stored
+/// contiguously and sorted by block number, and the trie keys themselves keep their ordering.
+/// The keys of the snapshot database are the big-endian macro block number of the snapshot,
+/// followed by the value database key of the trie key at that block.
+#[derive(Debug)]
+pub struct ArchiveStore {
+    /// Database handle.
+    db: DatabaseProxy,
+    /// A database of all trie values set by a diff, indexed by their trie key and the macro block
+    /// number at which the value was set.
+    value_table: TableProxy,
+    /// A database of the full snapshots of the trie, indexed by the macro block number of the
+    /// snapshot and the trie key.
+    snapshot_table: TableProxy,
+    /// A database of the archived segments, mapping the block number of the first macro block of
+    /// a segment to the block number of its last macro block.
+    segment_table: TableProxy,
+    /// A database containing the first and the last archived macro block numbers.
+    meta_table: TableProxy,
+}
+
+impl ArchiveStore {
+    const VALUE_DB_NAME: &'static str = "ArchiveValues";
+    const SNAPSHOT_DB_NAME: &'static str = "ArchiveSnapshots";
+    const SEGMENT_DB_NAME: &'static str = "ArchiveSegments";
+    const META_DB_NAME: &'static str = "ArchiveMeta";
+
+    const FIRST_BLOCK_KEY: &'static str = "first";
+    const LAST_BLOCK_KEY: &'static str = "last";
+
+    /// The number of trie items that are read at once when taking a snapshot.
+    const SNAPSHOT_CHUNK_SIZE: usize = 10_000;
+
+    /// Creates a new ArchiveStore.
+    pub fn new(db: DatabaseProxy) -> Self {
+        let value_table = db.open_table(Self::VALUE_DB_NAME.to_string());
+        let snapshot_table = db.open_table(Self::SNAPSHOT_DB_NAME.to_string());
+        let segment_table = db.open_table(Self::SEGMENT_DB_NAME.to_string());
+        let meta_table = db.open_table(Self::META_DB_NAME.to_string());
+
+        ArchiveStore {
+            db,
+            value_table,
+            snapshot_table,
+            segment_table,
+            meta_table,
+        }
+    }
+
+    /// Returns the block number of the first archived macro block, if any.
+    pub fn get_first_block_number(&self, txn_option: Option<&TransactionProxy>) -> Option<u32> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        txn.get(&self.meta_table, Self::FIRST_BLOCK_KEY)
+    }
+
+    /// Returns the block number of the last archived macro block, if any.
+    pub fn get_last_block_number(&self, txn_option: Option<&TransactionProxy>) -> Option<u32> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        txn.get(&self.meta_table, Self::LAST_BLOCK_KEY)
+    }
+
+    /// Returns the first and the last macro block number of the segment that contains the given
+    /// block number, if any.
+    pub fn get_segment(&self, block_number: u32, txn: &TransactionProxy) -> Option<(u32, u32)> {
+        let mut cursor = txn.cursor(&self.segment_table);
+
+        // Place the cursor after the segment that starts at or before the given block and go back
+        // by one entry. If the seek reaches the end of the database, moving back yields the last
+        // entry.
+        let upper_bound = match block_number.checked_add(1) {
+            Some(next_block_number) => next_block_number.to_be_bytes().to_vec(),
+            None => vec![u8::MAX; 5],
+        };
+        cursor.seek_range_key::<Vec<u8>, u32>(&upper_bound);
+        let (first_block_number, last_block_number) = cursor.prev::<Vec<u8>, u32>()?;
+        let first_block_number = u32::from_be_bytes(first_block_number.try_into().ok()?);
+
+        (block_number <= last_block_number).then_some((first_block_number, last_block_number))
+    }
+
+    /// Stores a full snapshot of the given accounts trie as the state at the given macro block
+    /// and starts a new segment with it. The accounts trie must be complete.
+    pub fn put_snapshot(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        block_number: u32,
+        accounts: &Accounts,
+    ) {
+        let mut start = Some(KeyNibbles::ROOT);
+        while let Some(start_key) = start {
+            let chunk = accounts.get_chunk(start_key, Self::SNAPSHOT_CHUNK_SIZE, Some(txn));
+            self.put_snapshot_items(
+                txn,
+                block_number,
+                chunk.items.into_iter().map(|item| (item.key, item.value)),
+            );
+            start = chunk.end_key;
+        }
+
+        self.start_segment(txn, block_number);
+    }
+
+    /// Stores the given trie items in the snapshot at the given macro block.
+    fn put_snapshot_items(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        block_number: u32,
+        items: impl IntoIterator<Item = (KeyNibbles, Vec<u8>)>,
+    ) {
+        for (key, value) in items {
+            txn.put(
+                &self.snapshot_table,
+                &to_snapshot_key(block_number, &key),
+                &value,
+            );
+        }
+    }
+
+    /// Starts a new segment at the given macro block.
+    fn start_segment(&self, txn: &mut WriteTransactionProxy, block_number: u32) {
+        txn.put(
+            &self.segment_table,
+            &block_number.to_be_bytes().to_vec(),
+            &block_number,
+        );
+
+        if self.get_first_block_number(Some(txn)).is_none() {
+            txn.put(&self.meta_table, Self::FIRST_BLOCK_KEY, &block_number);
+        }
+        txn.put(&self.meta_table, Self::LAST_BLOCK_KEY, &block_number);
+    }
+
+    /// Stores the changes of a batch as the state at the given macro block, extending the last
+    /// segment. The diff must contain the new values of all trie keys that changed since the last
+    /// archived macro block, which must be the previous macro block.
+    pub fn put_diff(&self, txn: &mut WriteTransactionProxy, block_number: u32, diff: &TrieDiff) {
+        let first_block_number = match self
+            .get_last_block_number(Some(txn))
+            .and_then(|last_block_number| self.get_segment(last_block_number, txn))
+        {
+            Some((first_block_number, _)) => first_block_number,
+            None => {
+                error!(block_number, "Archive has no segment to extend with a diff");
+                return;
+            }
+        };
+
+        for (key, value) in &diff.0 {
+            txn.put(
+                &self.value_table,
+                &to_archive_key(key, block_number),
+                &to_archive_value(value.as_deref()),
+            );
+        }
+
+        txn.put(
+            &self.segment_table,
+            &first_block_number.to_be_bytes().to_vec(),
+            &block_number,
+        );
+        txn.put(&self.meta_table, Self::LAST_BLOCK_KEY, &block_number);
+    }
+
+    /// Gets the raw value of the given trie key at the given macro block. Returns None if the key
+    /// didn't exist at that block.
+    /// The caller must make sure that the block number is within an archived segment.
+    pub fn get(
+        &self,
+        key: &KeyNibbles,
+        block_number: u32,
+        txn: &TransactionProxy,
+    ) -> Option<Vec<u8>> {
+        let (snapshot_block_number, _) = self.get_segment(block_number, txn)?;
+        let mut cursor = txn.cursor(&self.value_table);
+
+        // Place the cursor on the first entry after the wanted block and go back by one entry. By
+        // definition that entry is the most recent version of some key at or before the wanted block.
+        // If the seek reaches the end of the database, the cursor is placed on a special key
+        // and moving back yields the last entry.
+        let upper_bound = match block_number.checked_add(1) {
+            Some(next_block_number) => to_archive_key(key, next_block_number),
+            None => next_archive_key(key),
+        };
+        cursor.seek_range_key::<Vec<u8>, Vec<u8>>(&upper_bound);
+
+        // Check that the entry actually belongs to the wanted trie key and was set within the
+        // segment. Otherwise, the value of the key is the one in the snapshot of the segment.
+        if let Some((archive_key, value)) = cursor.prev::<Vec<u8>, Vec<u8>>() {
+            match from_archive_key(&archive_key) {
+                Some((entry_key, entry_block_number))
+                    if entry_key == *key && entry_block_number > snapshot_block_number =>
+                {
+                    return from_archive_value(value);
+                }
+                _ => {}
+            }
+        }
+
+        txn.get(
+            &self.snapshot_table,
+            &to_snapshot_key(snapshot_block_number, key),
+        )
+    }
+
+    /// Returns the raw values of all trie keys within the given range (inclusive) at the given
+    /// macro block. Keys that didn't exist at that block are omitted.
+    /// The caller must make sure that the block number is within an archived segment.
+    pub fn iter(
+        &self,
+        start_key: &KeyNibbles,
+        end_key: &KeyNibbles,
+        block_number: u32,
+        txn: &TransactionProxy,
+    ) -> Vec<Vec<u8>> {
+        let (snapshot_block_number, _) = match self.get_segment(block_number, txn) {
+            Some(segment) => segment,
+            None => return vec![],
+        };
+
+        // The most recent version of each key at or before the wanted block.
+        let mut values: BTreeMap<KeyNibbles, Option<Vec<u8>>> = BTreeMap::new();
+
+        // Start with the values in the snapshot of the segment.
+        let mut cursor = txn.cursor(&self.snapshot_table);
+        let mut entry = cursor
+            .seek_range_key::<Vec<u8>, Vec<u8>>(&to_snapshot_key(snapshot_block_number, start_key));
+        while let Some((snapshot_key, value)) = entry {
+            match from_snapshot_key(&snapshot_key) {
+                Some((entry_block_number, key))
+                    if entry_block_number == snapshot_block_number && key <= *end_key =>
+                {
+                    values.insert(key, Some(value));
+                }
+                _ => break,
+            }
+            entry = cursor.next();
+        }
+
+        // Apply all changes within the segment up to the wanted block. All versions of a key are
+        // sorted by block number, so more recent changes take precedence.
+        let mut cursor = txn.cursor(&self.value_table);
+        let mut entry = cursor.seek_range_key::<Vec<u8>, Vec<u8>>(&to_archive_key(start_key, 0));
+        while let Some((archive_key, value)) = entry {
+            let (key, entry_block_number) = match from_archive_key(&archive_key) {
+                Some(key) => key,
+                None => break,
+            };
+            if key > *end_key {
+                break;
+            }
+
+            if entry_block_number > snapshot_block_number && entry_block_number <= block_number {
+                values.insert(key, from_archive_value(value));
+            }
+
+            entry = cursor.next();
+        }
+
+        values.into_values().flatten().collect()
+    }
+
+    /// Clears the archive.
+    pub fn clear(&self, txn: &mut WriteTransactionProxy) {
+        txn.clear_database(&self.value_table);
+        txn.clear_database(&self.snapshot_table);
+        txn.clear_database(&self.segment_table);
+        txn.clear_database(&self.meta_table);
+    }
+}
+
+/// Transforms a trie key and a macro block number into the corresponding database key.
+fn to_archive_key(key: &KeyNibbles, block_number: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(key.len() + 5);
+    for i in 0..key.len() {
+        bytes.push(key.get(i).unwrap() as u8 + 1);
+    }
+    bytes.push(0);
+    bytes.extend_from_slice(&block_number.to_be_bytes());
+    bytes
+}
+
+/// Returns the smallest database key that is greater than all database keys of the given trie key.
+fn next_archive_key(key: &KeyNibbles) -> Vec<u8> {
+    let mut bytes = to_archive_key(key, 0);
+    bytes.truncate(key.len());
+    bytes.push(1);
+    bytes
+}
+
+/// Transforms a database key into the corresponding trie key and macro block number. Returns None
+/// if it fails.
+fn from_archive_key(bytes: &[u8]) -> Option<(KeyNibbles, u32)> {
+    let (key, block_number) = bytes.split_at(bytes.len().checked_sub(4)?);
+    let (terminator, nibbles) = key.split_last()?;
+    if *terminator != 0 {
+        return None;
+    }
+
+    let mut hex = String::with_capacity(nibbles.len());
+    for nibble in nibbles {
+        hex.push(char::from_digit(nibble.checked_sub(1)? as u32, 16)?);
+    }
+
+    let key = hex.parse().ok()?;
+    let block_number = u32::from_be_bytes(block_number.try_into().ok()?);
+    Some((key, block_number))
+}
+
+/// Transforms a macro block number and a trie key into the corresponding snapshot database key.
+fn to_snapshot_key(block_number: u32, key: &KeyNibbles) -> Vec<u8> {
+    let mut bytes = block_number.to_be_bytes().to_vec();
+    bytes.extend(to_archive_key(key, block_number));
+    bytes
+}
+
+/// Transforms a snapshot database key into the corresponding macro block number and trie key.
+/// Returns None if it fails.
+fn from_snapshot_key(bytes: &[u8]) -> Option<(u32, KeyNibbles)> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (block_number, archive_key) = bytes.split_at(4);
+    let block_number = u32::from_be_bytes(block_number.try_into().ok()?);
+    let (key, _) = from_archive_key(archive_key)?;
+    Some((block_number, key))
+}
+
+/// Transforms an optional trie value into the corresponding database value. The first byte
+/// indicates whether the key exists at all.
+fn to_archive_value(value: Option<&[u8]>) -> Vec<u8> {
+    match value {
+        Some(value) => {
+            let mut bytes = Vec::with_capacity(value.len() + 1);
+            bytes.push(1);
+            bytes.extend_from_slice(value);
+            bytes
+        }
+        None => vec![0],
+    }
+}
+
+/// Transforms a database value into the corresponding optional trie value.
+fn from_archive_value(mut bytes: Vec<u8>) -> Option<Vec<u8>> {
+    if bytes.first() == Some(&1) {
+        bytes.remove(0);
+        Some(bytes)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_database::volatile::VolatileDatabase;
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn archive_keys_work() {
+        let key: KeyNibbles = "cfb98637bcae43c13323eaa1731ced2b716962fd".parse().unwrap();
+        let archive_key = to_archive_key(&key, 42);
+        assert_eq!(from_archive_key(&archive_key), Some((key.clone(), 42)));
+        assert_eq!(
+            from_archive_key(&to_archive_key(&KeyNibbles::ROOT, 7)),
+            Some((KeyNibbles::ROOT, 7))
+        );
+
+        // All versions of a key are sorted by block number and before any longer key.
+        let longer_key: KeyNibbles = "cfb98637bcae43c13323eaa1731ced2b716962fd0".parse().unwrap();
+        assert!(to_archive_key(&key, 1) < to_archive_key(&key, 2));
+        assert!(to_archive_key(&key, u32::MAX) < next_archive_key(&key));
+        assert!(next_archive_key(&key) <= to_archive_key(&longer_key, 0));
+    }
+
+    #[test]
+    fn it_returns_the_value_at_a_macro_block() {
+        let env = VolatileDatabase::new(20).unwrap();
+        let store = ArchiveStore::new(env.clone());
+
+        let key_1: KeyNibbles = "413f22b3e".parse().unwrap();
+        let key_2: KeyNibbles = "413f22b3f".parse().unwrap();
+        let key_3: KeyNibbles = "413f22b40".parse().unwrap();
+
+        // The first segment covers the macro blocks 32 and 64.
+        let mut txn = env.write_transaction();
+        store.put_snapshot_items(
+            &mut txn,
+            32,
+            [(key_1.clone(), vec![1]), (key_2.clone(), vec![2])],
+        );
+        store.start_segment(&mut txn, 32);
+
+        let mut diff = TrieDiff::default();
+        diff.0.insert(key_1.clone(), Some(vec![3]));
+        diff.0.insert(key_2.clone(), None);
+        diff.0.insert(key_3.clone(), Some(vec![4]));
+        store.put_diff(&mut txn, 64, &diff);
+
+        // After a gap, the second segment covers the macro blocks 128 and 160.
+        store.put_snapshot_items(&mut txn, 128, [(key_1.clone(), vec![5])]);
+        store.start_segment(&mut txn, 128);
+
+        let mut diff = TrieDiff::default();
+        diff.0.insert(key_3.clone(), Some(vec![6]));
+        store.put_diff(&mut txn, 160, &diff);
+        txn.commit();
+
+        let txn = env.read_transaction();
+        assert_eq!(store.get_first_block_number(Some(&txn)), Some(32));
+        assert_eq!(store.get_last_block_number(Some(&txn)), Some(160));
+        assert_eq!(store.get_segment(0, &txn), None);
+        assert_eq!(store.get_segment(64, &txn), Some((32, 64)));
+        assert_eq!(store.get_segment(96, &txn), None);
+        assert_eq!(store.get_segment(128, &txn), Some((128, 160)));
+        assert_eq!(store.get_segment(192, &txn), None);
+
+        assert_eq!(store.get(&key_1, 0, &txn), None);
+        assert_eq!(store.get(&key_1, 32, &txn), Some(vec![1]));
+        assert_eq!(store.get(&key_1, 63, &txn), Some(vec![1]));
+        assert_eq!(store.get(&key_1, 64, &txn), Some(vec![3]));
+        assert_eq!(store.get(&key_2, 32, &txn), Some(vec![2]));
+        assert_eq!(store.get(&key_2, 64, &txn), None);
+        assert_eq!(store.get(&key_3, 32, &txn), None);
+        assert_eq!(store.get(&key_3, 64, &txn), Some(vec![4]));
+        assert_eq!(store.get(&key_3, 96, &txn), None);
+        assert_eq!(store.get(&key_1, 128, &txn), Some(vec![5]));
+        assert_eq!(store.get(&key_1, 160, &txn), Some(vec![5]));
+        assert_eq!(store.get(&key_3, 128, &txn), None);
+        assert_eq!(store.get(&key_3, 160, &txn), Some(vec![6]));
+
+        assert_eq!(store.iter(&key_1, &key_3, 32, &txn), vec![vec![1], vec![2]]);
+        assert_eq!(store.iter(&key_1, &key_3, 64, &txn), vec![vec![3], vec![4]]);
+        assert_eq!(store.iter(&key_2, &key_2, 64, &txn), Vec::<Vec<u8>>::new());
+        assert_eq!(store.iter(&key_1, &key_3, 128, &txn), vec![vec![5]]);
+        assert_eq!(
+            store.iter(&key_1, &key_3, 160, &txn),
+            vec![vec![5], vec![6]]
+        );
+        assert_eq!(store.iter(&key_2, &key_3, 160, &txn), vec![vec![6]]);
+    }
+}
@@ -13,6 +13,8 @@ use nimiq_serde::Deserialize;
 use nimiq_transaction::historic_transaction::HistoricTransaction;
 use nimiq_trie::WriteTransactionProxy;
 
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::BlockProcessingStage;
 use crate::Blockchain;
 
 /// Subset of the accounts in the accounts tree
@@ -35,6 +37,9 @@ impl Blockchain {
         txn: &mut WriteTransactionProxy,
         block_logger: &mut BlockLogger,
     ) -> Result<u64, PushError> {
+        let _span =
+            log::debug_span!("commit_accounts", block_number = block.block_number()).entered();
+
         // Get the accounts from the state.
         let accounts = &self.state.accounts;
         let block_state = BlockState::new(block.block_number(), block.timestamp());
@@ -46,17 +51,29 @@ impl Blockchain {
                 let inherents = self.create_macro_block_inherents(macro_block);
 
                 // Commit block to AccountsTree.
-                if accounts.is_complete(Some(txn)) {
-                    accounts.commit(txn, &[], &inherents, &block_state, block_logger)?;
-                } else if let Some(diff) = diff {
-                    accounts.commit_incomplete(txn, diff)?;
-                } else {
-                    return Err(PushError::MissingAccountsTrieDiff);
+                {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::CommitAccounts);
+                    if accounts.is_complete(Some(txn)) {
+                        accounts.commit(txn, &[], &inherents, &block_state, block_logger)?;
+                    } else if let Some(diff) = diff {
+                        accounts.commit_incomplete(txn, diff)?;
+                    } else {
+                        return Err(PushError::MissingAccountsTrieDiff);
+                    }
                 }
 
                 // Macro blocks are final and receipts for the previous batch are no longer necessary
                 // as rebranching across this block is not possible.
-                self.chain_store.clear_revert_infos(txn.raw());
+                {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::ClearRevertInfos);
+                    self.chain_store.clear_revert_infos(txn.raw());
+                }
 
                 // Store the transactions and the inherents into the History tree.
                 let hist_txs = HistoricTransaction::from(
@@ -67,11 +84,16 @@ impl Blockchain {
                     inherents,
                     vec![],
                 );
-                let total_tx_size = self
-                    .history_store
-                    .add_to_history(txn.raw(), macro_block.epoch_number(), &hist_txs)
-                    .expect("Failed to store history")
-                    .1;
+                let total_tx_size = {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::UpdateHistory);
+                    self.history_store
+                        .add_to_history(txn.raw(), macro_block.epoch_number(), &hist_txs)
+                        .expect("Failed to store history")
+                        .1
+                };
 
                 Ok(total_tx_size)
             }
@@ -93,20 +115,26 @@ impl Blockchain {
                 );
 
                 // Commit block to AccountsTree and create the receipts.
-                let revert_info: RevertInfo = if accounts.is_complete(Some(txn)) {
-                    accounts
-                        .commit(
-                            txn,
-                            &body.get_raw_transactions(),
-                            &inherents,
-                            &block_state,
-                            block_logger,
-                        )?
-                        .into()
-                } else if let Some(diff) = diff {
-                    accounts.commit_incomplete(txn, diff)?.into()
-                } else {
-                    return Err(PushError::MissingAccountsTrieDiff);
+                let revert_info: RevertInfo = {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::CommitAccounts);
+                    if accounts.is_complete(Some(txn)) {
+                        accounts
+                            .commit(
+                                txn,
+                                &body.get_raw_transactions(),
+                                &inherents,
+                                &block_state,
+                                block_logger,
+                            )?
+                            .into()
+                    } else if let Some(diff) = diff {
+                        accounts.commit_incomplete(txn, diff)?.into()
+                    } else {
+                        return Err(PushError::MissingAccountsTrieDiff);
+                    }
                 };
 
                 // Check that the transaction results match the ones in the block.
@@ -130,11 +158,17 @@ impl Blockchain {
                 }
 
                 // Store revert info.
-                self.chain_store.put_revert_info(
-                    txn.raw(),
-                    micro_block.header.block_number,
-                    &revert_info,
-                );
+                {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::WriteRevertInfo);
+                    self.chain_store.put_revert_info(
+                        txn.raw(),
+                        micro_block.header.block_number,
+                        &revert_info,
+                    );
+                }
 
                 // Store the transactions and the inherents into the History tree.
                 let hist_txs = HistoricTransaction::from(
@@ -148,11 +182,16 @@ impl Blockchain {
                         .map(|proof| proof.locator())
                         .collect(),
                 );
-                let total_tx_size = self
-                    .history_store
-                    .add_to_history(txn.raw(), micro_block.epoch_number(), &hist_txs)
-                    .expect("Failed to store history")
-                    .1;
+                let total_tx_size = {
+                    #[cfg(feature = "metrics")]
+                    let _timer = self
+                        .metrics
+                        .time_block_processing(block, BlockProcessingStage::UpdateHistory);
+                    self.history_store
+                        .add_to_history(txn.raw(), micro_block.epoch_number(), &hist_txs)
+                        .expect("Failed to store history")
+                        .1
+                };
 
                 Ok(total_tx_size)
             }
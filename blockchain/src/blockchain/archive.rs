@@ -0,0 +1,289 @@
+use std::{cell::Cell, collections::BTreeMap};
+
+use nimiq_account::{
+    Account, DataStoreIterOps, DataStoreReadOps, Staker, StakingContract, Validator,
+};
+use nimiq_block::Block;
+use nimiq_blockchain_interface::BlockchainError;
+use nimiq_database::{traits::Database, TransactionProxy, WriteTransactionProxy};
+use nimiq_keys::Address;
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy, trie::trie_diff::TrieDiff};
+use nimiq_serde::Deserialize;
+
+use crate::{archive_store::ArchiveStore, Blockchain};
+
+/// A read-only data store over the archived state of a contract at a given macro block.
+/// It allows to run the regular contract queries (e.g. of the staking contract) against
+/// historic state.
+/// Values that fail to deserialize are treated as missing and mark the data store as corrupted,
+/// which is reported by `finish`.
+pub struct ArchiveDataStoreRead<'store, 'txn, 'env> {
+    store: &'store ArchiveStore,
+    txn: &'txn TransactionProxy<'env>,
+    prefix: KeyNibbles,
+    block_number: u32,
+    corrupted: Cell<bool>,
+}
+
+impl<'store, 'txn, 'env> ArchiveDataStoreRead<'store, 'txn, 'env> {
+    fn deserialize<T: Deserialize>(&self, value: &[u8]) -> Option<T> {
+        match T::deserialize_from_vec(value) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                error!(
+                    block_number = self.block_number,
+                    %error,
+                    "Failed to deserialize archived value"
+                );
+                self.corrupted.set(true);
+                None
+            }
+        }
+    }
+
+    /// Returns the result of a query run against this data store, or an error if any of the
+    /// values read by the query was corrupted.
+    fn finish<T>(self, result: T) -> Result<T, BlockchainError> {
+        if self.corrupted.get() {
+            return Err(BlockchainError::ArchiveCorrupted(self.block_number));
+        }
+        Ok(result)
+    }
+}
+
+impl<'store, 'txn, 'env> DataStoreReadOps for ArchiveDataStoreRead<'store, 'txn, 'env> {
+    fn get<T: Deserialize>(&self, key: &KeyNibbles) -> Option<T> {
+        self.store
+            .get(&(&self.prefix + key), self.block_number, self.txn)
+            .and_then(|value| self.deserialize(&value))
+    }
+}
+
+impl<'store, 'txn, 'env> DataStoreIterOps for ArchiveDataStoreRead<'store, 'txn, 'env> {
+    type Iter<T: Deserialize> = std::vec::IntoIter<T>;
+
+    fn iter<T: Deserialize>(&self, start_key: &KeyNibbles, end_key: &KeyNibbles) -> Self::Iter<T> {
+        self.store
+            .iter(
+                &(&self.prefix + start_key),
+                &(&self.prefix + end_key),
+                self.block_number,
+                self.txn,
+            )
+            .into_iter()
+            .filter_map(|value| self.deserialize(&value))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Implements methods to store and query the archived accounts state. The archive is only
+/// maintained if the blockchain was created with `BlockchainConfig::archive` enabled.
+impl Blockchain {
+    /// Returns true if this blockchain keeps historic accounts state.
+    pub fn is_archive(&self) -> bool {
+        self.archive_store.is_some()
+    }
+
+    /// Returns the range of macro block numbers for which the accounts state is archived.
+    /// The archive might have gaps within this range, e.g. for blocks that were pushed while the
+    /// accounts trie was incomplete.
+    pub fn archived_range(&self) -> Option<(u32, u32)> {
+        let archive_store = self.archive_store.as_ref()?;
+        let txn = self.read_transaction();
+        Some((
+            archive_store.get_first_block_number(Some(&txn))?,
+            archive_store.get_last_block_number(Some(&txn))?,
+        ))
+    }
+
+    /// Archives the accounts state at the given macro block. The given diff must contain all trie
+    /// changes since the previous macro block. If no diff is given or the archive doesn't contain
+    /// the previous macro block, a full snapshot of the accounts trie is taken instead, starting a
+    /// new segment of the archive.
+    pub(crate) fn archive_macro_block(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        block_number: u32,
+        diff: Option<TrieDiff>,
+    ) {
+        let archive_store = match self.archive_store {
+            Some(ref archive_store) => archive_store,
+            None => return,
+        };
+
+        let is_continuous = archive_store.get_last_block_number(Some(txn))
+            == block_number.checked_sub(Policy::blocks_per_batch());
+
+        match diff {
+            Some(diff) if is_continuous => archive_store.put_diff(txn, block_number, &diff),
+            _ => {
+                info!(
+                    block_number,
+                    "Taking a snapshot of the accounts trie for the archive"
+                );
+                archive_store.put_snapshot(txn, block_number, &self.state.accounts);
+            }
+        }
+    }
+
+    /// Collects the accounts trie diffs of all blocks in the batch of the given macro block into a
+    /// single diff. The diff of the macro block itself must be given, since it might not be stored
+    /// yet. Returns None if any of the diffs is not available.
+    pub(crate) fn get_batch_accounts_diff(
+        &self,
+        block: &Block,
+        block_diff: &TrieDiff,
+        txn: &TransactionProxy,
+    ) -> Option<TrieDiff> {
+        let mut changes: BTreeMap<KeyNibbles, Option<Vec<u8>>> = block_diff.0.clone();
+
+        // Walk backwards through the batch. More recent changes take precedence.
+        let first_block_number =
+            Policy::first_block_of_batch(Policy::batch_at(block.block_number()))?;
+        let mut parent_hash = block.parent_hash().clone();
+        for _ in first_block_number..block.block_number() {
+            let diff = self
+                .chain_store
+                .get_accounts_diff(&parent_hash, Some(txn))
+                .ok()?;
+            for (key, value) in diff.0 {
+                changes.entry(key).or_insert(value);
+            }
+
+            parent_hash = self
+                .chain_store
+                .get_block(&parent_hash, false, Some(txn))
+                .ok()?
+                .parent_hash()
+                .clone();
+        }
+
+        Some(TrieDiff(changes))
+    }
+
+    /// Checks that the accounts state at the given block number is archived.
+    fn check_archived(
+        &self,
+        block_number: u32,
+        txn: &TransactionProxy,
+    ) -> Result<(), BlockchainError> {
+        let archive_store = self
+            .archive_store
+            .as_ref()
+            .ok_or(BlockchainError::ArchiveNotEnabled)?;
+
+        if !Policy::is_macro_block_at(block_number) {
+            return Err(BlockchainError::BlockIsNotMacro);
+        }
+
+        archive_store
+            .get_segment(block_number, txn)
+            .map(|_| ())
+            .ok_or(BlockchainError::StateNotArchived(block_number))
+    }
+
+    /// Returns the account at the given address as it was at the given macro block.
+    pub fn get_account_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Account, BlockchainError> {
+        let txn = self.read_transaction();
+        self.check_archived(block_number, &txn)?;
+
+        let archive_store = self.archive_store.as_ref().unwrap();
+        archive_store
+            .get(&KeyNibbles::from(address), block_number, &txn)
+            .map(|value| Account::deserialize_from_vec(&value))
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(|error| {
+                error!(block_number, %error, "Failed to deserialize archived account");
+                BlockchainError::ArchiveCorrupted(block_number)
+            })
+    }
+
+    /// Returns the staking contract as it was at the given macro block.
+    pub fn get_staking_contract_at(
+        &self,
+        block_number: u32,
+    ) -> Result<StakingContract, BlockchainError> {
+        match self.get_account_at(&Policy::STAKING_CONTRACT_ADDRESS, block_number)? {
+            Account::Staking(staking_contract) => Ok(staking_contract),
+            _ => Err(BlockchainError::ArchiveCorrupted(block_number)),
+        }
+    }
+
+    /// Returns all validators of the staking contract as they were at the given macro block.
+    /// IMPORTANT: This is a very expensive operation, iterating over all archived validators.
+    pub fn get_validators_at(&self, block_number: u32) -> Result<Vec<Validator>, BlockchainError> {
+        let staking_contract = self.get_staking_contract_at(block_number)?;
+        let txn = self.read_transaction();
+        let data_store =
+            self.archive_data_store(&Policy::STAKING_CONTRACT_ADDRESS, block_number, &txn);
+        let result = staking_contract.get_validators(&data_store);
+        data_store.finish(result)
+    }
+
+    /// Returns the validator with the given address as it was at the given macro block.
+    pub fn get_validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Validator>, BlockchainError> {
+        let staking_contract = self.get_staking_contract_at(block_number)?;
+        let txn = self.read_transaction();
+        let data_store =
+            self.archive_data_store(&Policy::STAKING_CONTRACT_ADDRESS, block_number, &txn);
+        let result = staking_contract.get_validator(&data_store, address);
+        data_store.finish(result)
+    }
+
+    /// Returns all stakers delegating to the given validator as they were at the given macro block.
+    /// IMPORTANT: This is a very expensive operation, iterating over all archived stakers.
+    pub fn get_stakers_for_validator_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Vec<Staker>, BlockchainError> {
+        let staking_contract = self.get_staking_contract_at(block_number)?;
+        let txn = self.read_transaction();
+        let data_store =
+            self.archive_data_store(&Policy::STAKING_CONTRACT_ADDRESS, block_number, &txn);
+        let result = staking_contract.get_stakers_for_validator(&data_store, address);
+        data_store.finish(result)
+    }
+
+    /// Returns the staker with the given address as it was at the given macro block.
+    pub fn get_staker_at(
+        &self,
+        address: &Address,
+        block_number: u32,
+    ) -> Result<Option<Staker>, BlockchainError> {
+        let staking_contract = self.get_staking_contract_at(block_number)?;
+        let txn = self.read_transaction();
+        let data_store =
+            self.archive_data_store(&Policy::STAKING_CONTRACT_ADDRESS, block_number, &txn);
+        let result = staking_contract.get_staker(&data_store, address);
+        data_store.finish(result)
+    }
+
+    fn archive_data_store<'store, 'txn, 'env>(
+        &'store self,
+        prefix: &Address,
+        block_number: u32,
+        txn: &'txn TransactionProxy<'env>,
+    ) -> ArchiveDataStoreRead<'store, 'txn, 'env> {
+        ArchiveDataStoreRead {
+            store: self
+                .archive_store
+                .as_ref()
+                .expect("Archive must be enabled"),
+            txn,
+            prefix: KeyNibbles::from(prefix),
+            block_number,
+            corrupted: Cell::new(false),
+        }
+    }
+}
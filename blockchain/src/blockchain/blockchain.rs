@@ -18,8 +18,12 @@ use tokio::sync::broadcast::{channel as broadcast, Sender as BroadcastSender};
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::{
-    blockchain_state::BlockchainState, chain_store::ChainStore, history::HistoryStore,
-    interface::HistoryInterface, reward::genesis_parameters,
+    archive_store::ArchiveStore,
+    blockchain_state::BlockchainState,
+    chain_store::ChainStore,
+    history::{HistoryStore, HistorySyncStore},
+    interface::HistoryInterface,
+    reward::genesis_parameters,
 };
 
 const BROADCAST_MAX_CAPACITY: usize = 256;
@@ -45,6 +49,11 @@ pub struct Blockchain {
     pub chain_store: ChainStore,
     /// The history store is a database containing all of the history trees and transactions.
     pub history_store: Box<dyn HistoryInterface + Sync + Send>,
+    /// The history sync store is a database containing the progress of an ongoing history sync.
+    pub history_sync_store: HistorySyncStore,
+    /// The archive store is a database containing the historic accounts state at every macro
+    /// block. It is only present if archive mode is enabled.
+    pub(crate) archive_store: Option<ArchiveStore>,
     /// The current state of the blockchain.
     pub state: BlockchainState,
     /// A reference to a "function" to test whether a given transaction is known and valid.
@@ -69,6 +78,12 @@ pub struct BlockchainConfig {
     /// Maximum number of epochs (other than the current one) that the ChainStore will store fully.
     /// Epochs older than this number will be pruned.
     pub max_epochs_stored: u32,
+    /// Flag indicating if the accounts state at every macro block should be archived
+    pub archive: bool,
+    /// Number of epochs (other than the current one) for which the full history is retained.
+    /// The history of older epochs is pruned in the background. If set to None, the full history
+    /// is kept. This only has an effect if `keep_history` is set.
+    pub history_retention_epochs: Option<u32>,
 }
 
 impl Default for BlockchainConfig {
@@ -76,6 +91,8 @@ impl Default for BlockchainConfig {
         Self {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
+            archive: false,
+            history_retention_epochs: None,
         }
     }
 }
@@ -265,6 +282,9 @@ impl Blockchain {
         let (tx_fork, _rx_fork) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_log, _rx_log) = broadcast(BROADCAST_MAX_CAPACITY);
 
+        let history_sync_store = HistorySyncStore::new(env.clone());
+        let archive_store = config.archive.then(|| ArchiveStore::new(env.clone()));
+
         Ok(Blockchain {
             env,
             config,
@@ -275,6 +295,8 @@ impl Blockchain {
             log_notifier: tx_log,
             chain_store,
             history_store: Box::new(history_store) as Box<dyn HistoryInterface + Sync + Send>,
+            history_sync_store,
+            archive_store,
             state: BlockchainState {
                 accounts,
                 main_chain,
@@ -323,6 +345,12 @@ impl Blockchain {
         let mut txn = env.write_transaction();
         accounts.init(&mut (&mut txn).into(), genesis_accounts);
 
+        // Archive the genesis state.
+        let archive_store = config.archive.then(|| ArchiveStore::new(env.clone()));
+        if let Some(ref archive_store) = archive_store {
+            archive_store.put_snapshot(&mut txn, genesis_block_number, &accounts);
+        }
+
         // Store genesis block.
         chain_store.put_chain_info(&mut txn, &head_hash, &main_chain, true);
         chain_store.set_head(&mut txn, &head_hash);
@@ -332,6 +360,8 @@ impl Blockchain {
         let (tx_fork, _rx_fork) = broadcast(BROADCAST_MAX_CAPACITY);
         let (tx_log, _rx_log) = broadcast(BROADCAST_MAX_CAPACITY);
 
+        let history_sync_store = HistorySyncStore::new(env.clone());
+
         Ok(Blockchain {
             env,
             config,
@@ -342,6 +372,8 @@ impl Blockchain {
             log_notifier: tx_log,
             chain_store,
             history_store: Box::new(history_store) as Box<dyn HistoryInterface + Sync + Send>,
+            history_sync_store,
+            archive_store,
             state: BlockchainState {
                 accounts,
                 macro_info: main_chain.clone(),
@@ -0,0 +1,88 @@
+use std::cmp;
+
+use nimiq_database::traits::{Database, WriteTransaction};
+use nimiq_primitives::policy::Policy;
+use parking_lot::RwLock;
+
+use crate::Blockchain;
+
+/// Implements methods to prune the history of old epochs. This is only done if the blockchain keeps
+/// the history and a history retention is configured via `BlockchainConfig::history_retention_epochs`.
+/// The history tree roots of the pruned epochs are kept, so only the historic transactions (and
+/// thus the ability to prove them) of the pruned epochs are lost.
+impl Blockchain {
+    /// Returns the configured number of epochs (other than the current one) for which the history
+    /// is retained. Returns None if the full history is kept.
+    pub fn history_retention_epochs(&self) -> Option<u32> {
+        if !self.config.keep_history {
+            return None;
+        }
+        self.config
+            .history_retention_epochs
+            .map(|epochs| cmp::max(epochs, Policy::MIN_EPOCHS_STORED))
+    }
+
+    /// Returns the first epoch whose history has not been pruned yet.
+    pub fn first_retained_history_epoch(&self) -> u32 {
+        self.chain_store
+            .get_history_pruned_epoch(None)
+            .map_or(1, |epoch_number| epoch_number + 1)
+    }
+
+    /// Returns the first epoch whose history must be retained according to the configured
+    /// retention. Returns None if the full history is kept.
+    fn history_retention_boundary(&self) -> Option<u32> {
+        let retention_epochs = self.history_retention_epochs()?;
+
+        // The epoch of the last election block is the most recently completed epoch.
+        let last_epoch = Policy::epoch_at(self.state.election_head.block_number());
+        Some((last_epoch + 1).saturating_sub(retention_epochs))
+    }
+
+    /// Returns the oldest epoch whose history is beyond the configured retention and must be
+    /// pruned. Returns None if there is nothing to prune.
+    fn next_history_epoch_to_prune(&self) -> Option<u32> {
+        let boundary = self.history_retention_boundary()?;
+        let epoch_number = self.first_retained_history_epoch();
+        (epoch_number < boundary).then_some(epoch_number)
+    }
+
+    /// Prunes the history of the oldest epoch that is beyond the configured retention. Each call
+    /// prunes at most one epoch in its own database transaction, such that pruning can be done in
+    /// small batches in the background. The history tree root of the pruned epoch is kept.
+    /// The blockchain lock is not held while waiting for the database transaction, since it might
+    /// be held by a block push at that time.
+    /// Returns the number of the pruned epoch or None if there is nothing to prune.
+    pub fn prune_history_step(this: &RwLock<Self>) -> Option<u32> {
+        let env = {
+            let this = this.read();
+            this.next_history_epoch_to_prune()?;
+            this.env.clone()
+        };
+
+        let mut txn = env.write_transaction();
+        let this = this.read();
+
+        // The chain might have changed while waiting for the transaction.
+        let Some(epoch_number) = this.next_history_epoch_to_prune() else {
+            txn.abort();
+            return None;
+        };
+
+        if this
+            .history_store
+            .prune_history(&mut txn, epoch_number)
+            .is_none()
+        {
+            warn!(epoch_number, "Failed to prune the history of epoch");
+            txn.abort();
+            return None;
+        }
+        this.chain_store
+            .set_history_pruned_epoch(&mut txn, epoch_number);
+        txn.commit();
+
+        debug!(epoch_number, "Pruned the history of epoch");
+        Some(epoch_number)
+    }
+}
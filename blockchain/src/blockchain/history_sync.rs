@@ -18,6 +18,7 @@ use nimiq_transaction::{
     inherent::Inherent,
     Transaction,
 };
+use nimiq_trie::WriteTransactionProxy as TrieWriteTransactionProxy;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 
 use crate::Blockchain;
@@ -299,41 +300,75 @@ impl Blockchain {
             }
         }
 
-        // Update the accounts tree, one block at a time.
-        for i in 0..block_numbers.len() {
-            // Extract the transactions from the block
-            let txns: Vec<Transaction> = block_transactions[i]
-                .iter()
-                .map(|txn| txn.get_raw_transaction().clone())
-                .collect();
-
-            // Commit block to AccountsTree and create the receipts.
-            let block_state = BlockState::new(block_numbers[i], block_timestamps[i]);
-            let receipts = this.state.accounts.commit_batch(
-                &mut (&mut txn).into(),
-                &txns,
-                &block_inherents[i],
-                &block_state,
-                &mut BlockLogger::empty(),
-            );
+        // The accounts changes of this push continue the archive only if they start at the state
+        // of the previous macro block, i.e. if no history of the current batch was known already.
+        let last_macro_block = Policy::last_macro_block(this.block_number());
+        let mut archive_continuous = history[..first_new_hist_tx]
+            .last()
+            .map_or(true, |hist_tx| hist_tx.block_number <= last_macro_block);
+
+        // Update the accounts tree, one block at a time. If the archive is enabled, the changes
+        // are recorded and every macro block is archived with the changes of its batch.
+        let archive = this.is_archive() && this.state.accounts.is_complete(Some(&txn));
+        let commit_result = {
+            let mut txn: TrieWriteTransactionProxy = (&mut txn).into();
+            if archive {
+                txn.start_recording();
+            }
 
-            // Check if the receipts contain an error.
-            if let Err(e) = receipts {
-                warn!(
-                    %block,
-                    reason = "commit of block failed",
-                    block_no = block_numbers[i],
-                    num_transactions = block_transactions[i].len(),
-                    num_inherents = block_inherents[i].len(),
-                    error = &e as &dyn Error,
-                    "Rejecting block",
+            let mut commit_result = Ok(());
+            for i in 0..block_numbers.len() {
+                // Extract the transactions from the block
+                let txns: Vec<Transaction> = block_transactions[i]
+                    .iter()
+                    .map(|txn| txn.get_raw_transaction().clone())
+                    .collect();
+
+                // Commit block to AccountsTree and create the receipts.
+                let block_state = BlockState::new(block_numbers[i], block_timestamps[i]);
+                let receipts = this.state.accounts.commit_batch(
+                    &mut txn,
+                    &txns,
+                    &block_inherents[i],
+                    &block_state,
+                    &mut BlockLogger::empty(),
                 );
 
-                txn.abort();
-                #[cfg(feature = "metrics")]
-                this.metrics.note_invalid_block();
-                return Err(PushError::AccountsError(e));
+                // Check if the receipts contain an error.
+                if let Err(e) = receipts {
+                    warn!(
+                        %block,
+                        reason = "commit of block failed",
+                        block_no = block_numbers[i],
+                        num_transactions = block_transactions[i].len(),
+                        num_inherents = block_inherents[i].len(),
+                        error = &e as &dyn Error,
+                        "Rejecting block",
+                    );
+                    commit_result = Err(e);
+                    break;
+                }
+
+                if archive && Policy::is_macro_block_at(block_numbers[i]) {
+                    this.state.accounts.finalize_batch(&mut txn);
+                    let diff = txn.stop_recording().into_forward_diff();
+                    this.archive_macro_block(
+                        txn.raw(),
+                        block_numbers[i],
+                        archive_continuous.then_some(diff),
+                    );
+                    archive_continuous = true;
+                    txn.start_recording();
+                }
             }
+            commit_result
+        };
+
+        if let Err(e) = commit_result {
+            txn.abort();
+            #[cfg(feature = "metrics")]
+            this.metrics.note_invalid_block();
+            return Err(PushError::AccountsError(e));
         }
         this.state.accounts.finalize_batch(&mut (&mut txn).into());
 
@@ -400,6 +435,20 @@ impl Blockchain {
             return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
         }
 
+        // Tally the signals of the newly elected validators, like it is done when pushing blocks.
+        if block.is_election() {
+            if let Some(tally) = this.tally_signals(&block, &txn) {
+                #[cfg(feature = "metrics")]
+                this.metrics.note_signal_tally(&tally);
+                this.chain_store.put_signal_tally(&mut txn, &tally);
+            }
+        }
+
+        // The history of this block is now part of the chain, so any persisted history sync
+        // progress up to this block is obsolete.
+        this.history_sync_store
+            .remove_chunks_up_to(&mut txn, block.block_number());
+
         // Give up database transactions and push lock before creating notifications.
         txn.commit();
 
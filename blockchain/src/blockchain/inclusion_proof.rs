@@ -0,0 +1,96 @@
+use nimiq_block::BlockInclusionProof;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, TransactionInclusionProof};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+
+use crate::Blockchain;
+
+/// Implements methods to create self-contained transaction inclusion proofs.
+impl Blockchain {
+    /// Creates an inclusion proof for the transaction with the given hash that can be verified
+    /// offline with `TransactionInclusionProof::verify`.
+    /// Transactions of finalized epochs are proven against the election block of their epoch and
+    /// anchored at the current election head. Transactions of a finalized batch in the current
+    /// epoch are proven against (and anchored at) the current checkpoint block. Transactions of
+    /// the current batch can't be proven yet.
+    pub fn prove_transaction_inclusion(
+        &self,
+        tx_hash: &Blake2bHash,
+    ) -> Result<TransactionInclusionProof, BlockchainError> {
+        let txn = self.read_transaction();
+
+        // Due to the history store implementation, there might be multiple historic transactions
+        // for this hash. We just pick any of them.
+        let hist_tx = self
+            .history_store
+            .get_hist_tx_by_hash(tx_hash, Some(&txn))
+            .pop()
+            .ok_or(BlockchainError::TransactionNotFound)?;
+        let block_number = hist_tx.block_number;
+
+        let election_head = self.election_head();
+        let macro_head = self.macro_head();
+
+        let (proving_block_number, mut anchor_block, verifier_state) =
+            if block_number <= election_head.block_number() {
+                (
+                    Policy::election_block_after(block_number),
+                    election_head,
+                    None,
+                )
+            } else if block_number <= macro_head.block_number() {
+                // The history tree of the current epoch already contains transactions after the
+                // checkpoint block, so we need to prove against its state at the checkpoint block.
+                let chain_info =
+                    self.chain_store
+                        .get_chain_info(&macro_head.hash(), false, Some(&txn))?;
+                (
+                    macro_head.block_number(),
+                    macro_head,
+                    Some(chain_info.history_tree_len as usize),
+                )
+            } else {
+                return Err(BlockchainError::TransactionNotFinalized);
+            };
+
+        let block = self
+            .chain_store
+            .get_block_at(proving_block_number, false, Some(&txn))?;
+
+        let history_proof = self
+            .history_store
+            .prove(
+                Policy::epoch_at(proving_block_number),
+                vec![tx_hash],
+                verifier_state,
+                Some(&txn),
+            )
+            .ok_or(BlockchainError::CouldNotProveInclusion)?;
+
+        // Collect the interlink chain connecting the anchor block to the proving block.
+        let mut proof = vec![];
+        if proving_block_number < anchor_block.block_number() {
+            for hop in BlockInclusionProof::get_interlink_hops(
+                proving_block_number,
+                anchor_block.block_number(),
+            ) {
+                let mut hop_block = self
+                    .chain_store
+                    .get_block_at(hop, false, Some(&txn))?
+                    .unwrap_macro();
+                hop_block.body = None;
+                proof.push(hop_block);
+            }
+        }
+
+        // Only the header of the anchor block is needed to verify the proof.
+        anchor_block.body = None;
+
+        Ok(TransactionInclusionProof {
+            history_proof,
+            block,
+            anchor_block,
+            block_proof: BlockInclusionProof { proof },
+        })
+    }
+}
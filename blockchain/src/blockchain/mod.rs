@@ -1,11 +1,17 @@
 mod abstract_blockchain;
 pub mod accounts;
+pub mod archive;
 #[allow(clippy::module_inception)]
 pub mod blockchain;
+pub mod history_pruning;
 pub mod history_sync;
+pub mod inclusion_proof;
 pub mod inherents;
 pub mod push;
 pub(super) mod rebranch_utils;
+pub mod reward_attribution;
+pub mod reward_estimation;
+pub mod signaling;
 pub mod slots;
 pub mod verify;
 pub mod wrappers;
@@ -23,6 +23,8 @@ use nimiq_trie::WriteTransactionProxy as TrieWriteTransactionProxy;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 use tokio::sync::broadcast::Sender as BroadcastSender;
 
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::BlockProcessingStage;
 use crate::Blockchain;
 
 fn send_vec(log_notifier: &BroadcastSender<BlockLog>, logs: Vec<BlockLog>) {
@@ -95,6 +97,11 @@ impl Blockchain {
 
         // Detect forks in non-skip micro blocks.
         if block.is_micro() && !block.is_skip() {
+            #[cfg(feature = "metrics")]
+            let _timer = this
+                .metrics
+                .time_block_processing(&block, BlockProcessingStage::DetectForks);
+
             let validator = this
                 .get_proposer(
                     block.block_number(),
@@ -268,6 +275,13 @@ impl Blockchain {
         diff: Option<TrieDiff>,
         chunks: Vec<TrieChunkWithStart>,
     ) -> Result<(PushResult, Result<ChunksPushResult, ChunksPushError>), PushError> {
+        let _span = log::debug_span!(
+            "push_block",
+            block_number = block.block_number(),
+            trusted = trust,
+        )
+        .entered();
+
         #[cfg(not(feature = "metrics"))]
         {
             Self::do_push(this, block, trust, diff, chunks)
@@ -580,6 +594,21 @@ impl Blockchain {
                 let recorded_diff = txn.stop_recording().into_forward_diff();
                 self.chain_store
                     .put_accounts_diff(txn.raw(), &block.hash(), &recorded_diff);
+
+                // Archive the accounts state at every macro block.
+                if block.is_macro() && self.is_archive() {
+                    let batch_diff = self.get_batch_accounts_diff(block, &recorded_diff, txn.raw());
+                    self.archive_macro_block(txn.raw(), block.block_number(), batch_diff);
+                }
+
+                // Tally the signals of the newly elected validators.
+                if block.is_election() {
+                    if let Some(tally) = self.tally_signals(block, txn.raw()) {
+                        #[cfg(feature = "metrics")]
+                        self.metrics.note_signal_tally(&tally);
+                        self.chain_store.put_signal_tally(txn.raw(), &tally);
+                    }
+                }
             }
         }
 
@@ -0,0 +1,118 @@
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_transaction::historic_transaction::HistoricTransactionData;
+
+use crate::Blockchain;
+
+/// The share of a validator's reward for a batch that is attributed to one of its stakers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakerReward {
+    /// The batch the reward was earned in.
+    pub batch_number: u32,
+    /// The macro block that paid out the reward.
+    pub block_number: u32,
+    /// The validator the stake was delegated to.
+    pub validator_address: Address,
+    /// The total reward paid to the validator for the batch.
+    pub validator_reward: Coin,
+    /// The total stake of the validator at the election block of the epoch.
+    pub validator_stake: Coin,
+    /// The active balance of the staker at the election block of the epoch.
+    pub active_balance: Coin,
+    /// The staker's pro-rata share of the validator reward.
+    pub value: Coin,
+}
+
+/// Implements methods to attribute validator rewards to their stakers. Rewards are only paid out
+/// to the validators' reward addresses, so the stakers' shares are not part of the chain state.
+impl Blockchain {
+    /// Computes the rewards earned by the given staker in the batches of the given epochs.
+    ///
+    /// The slots of an epoch are assigned according to the stake distribution at the preceding
+    /// election block. Thus, the staker's share of each batch reward is its active balance at that
+    /// election block relative to the validator's total stake at that time. Shares are rounded down.
+    ///
+    /// This requires the accounts state at the election blocks to be archived, i.e. the blockchain
+    /// must run in archive mode. Batches whose reward hasn't been paid out yet are skipped.
+    pub fn get_staker_rewards(
+        &self,
+        staker_address: &Address,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> Result<Vec<StakerReward>, BlockchainError> {
+        // The genesis epoch has no rewards.
+        if from_epoch == 0 || from_epoch > to_epoch {
+            return Err(BlockchainError::InvalidEpoch);
+        }
+
+        let macro_head = self.macro_head().block_number();
+        let txn = self.read_transaction();
+        let mut rewards = vec![];
+
+        for epoch in from_epoch..=to_epoch {
+            let election_block =
+                Policy::election_block_of(epoch - 1).ok_or(BlockchainError::InvalidEpoch)?;
+            if election_block >= macro_head {
+                break;
+            }
+
+            let staker = match self.get_staker_at(staker_address, election_block)? {
+                Some(staker) if !staker.active_balance.is_zero() => staker,
+                _ => continue,
+            };
+            let validator_address = match staker.delegation {
+                Some(validator_address) => validator_address,
+                None => continue,
+            };
+            let validator = match self.get_validator_at(&validator_address, election_block)? {
+                Some(validator) if !validator.total_stake.is_zero() => validator,
+                _ => continue,
+            };
+
+            // The reward of a batch is paid out in the macro block of the following batch.
+            let first_batch = Policy::batch_at(election_block) + 1;
+            let last_batch = Policy::batch_at(
+                Policy::election_block_of(epoch).ok_or(BlockchainError::InvalidEpoch)?,
+            );
+            for batch_number in first_batch..=last_batch {
+                let block_number = Policy::macro_block_of(batch_number + 1)
+                    .ok_or(BlockchainError::InvalidEpoch)?;
+                if block_number > macro_head {
+                    break;
+                }
+
+                for hist_tx in self
+                    .history_store
+                    .get_block_transactions(block_number, Some(&txn))
+                {
+                    let event = match hist_tx.data {
+                        HistoricTransactionData::Reward(event)
+                            if event.validator_address == validator_address =>
+                        {
+                            event
+                        }
+                        _ => continue,
+                    };
+
+                    let value = u128::from(u64::from(event.value))
+                        * u128::from(u64::from(staker.active_balance))
+                        / u128::from(u64::from(validator.total_stake));
+
+                    rewards.push(StakerReward {
+                        batch_number,
+                        block_number,
+                        validator_address: validator_address.clone(),
+                        validator_reward: event.value,
+                        validator_stake: validator.total_stake,
+                        active_balance: staker.active_balance,
+                        // The active balance is part of the total stake, so the share can't exceed the reward.
+                        value: Coin::from_u64_unchecked(value as u64),
+                    });
+                }
+            }
+        }
+
+        Ok(rewards)
+    }
+}
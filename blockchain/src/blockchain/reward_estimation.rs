@@ -0,0 +1,77 @@
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_keys::Address;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+
+use crate::{
+    reward::batch_delay,
+    reward_calculator::{estimate_rewards, RewardEstimate, RewardParameters},
+    Blockchain,
+};
+
+/// Implements methods to project staking rewards from the current chain state.
+impl Blockchain {
+    /// Returns the average delay of the batches of the last epoch, in milliseconds.
+    pub fn average_batch_delay(&self) -> u64 {
+        let last_batch = Policy::batch_at(self.macro_head().block_number());
+        let first_batch = last_batch
+            .saturating_sub(Policy::batches_per_epoch())
+            .max(1);
+
+        let mut total_delay = 0;
+        let mut num_batches = 0;
+        let mut previous_timestamp = None;
+        for batch in first_batch - 1..=last_batch {
+            let block_number = match Policy::macro_block_of(batch) {
+                Some(block_number) => block_number,
+                None => break,
+            };
+            let timestamp = match self.get_block_at(block_number, false, None) {
+                Ok(block) => block.timestamp(),
+                Err(_) => {
+                    previous_timestamp = None;
+                    continue;
+                }
+            };
+            if let Some(previous_timestamp) = previous_timestamp {
+                total_delay += batch_delay(previous_timestamp, timestamp);
+                num_batches += 1;
+            }
+            previous_timestamp = Some(timestamp);
+        }
+
+        if num_batches == 0 {
+            0
+        } else {
+            total_delay / num_batches
+        }
+    }
+
+    /// Projects the rewards for the given stake if it is delegated to the given validator, based
+    /// on the current total stake and the observed batch delays. The stake is treated as
+    /// additional stake, i.e. it is added to the current total stake.
+    /// Returns None if the validator doesn't exist or the accounts tree is incomplete. Validators
+    /// that are inactive or jailed don't earn rewards, so the projected rewards are zero for them.
+    pub fn estimate_staking_rewards(
+        &self,
+        validator_address: &Address,
+        stake: Coin,
+    ) -> Option<RewardEstimate> {
+        let staking_contract = self.get_staking_contract_if_complete(None)?;
+        let data_store = self.get_staking_contract_store();
+        let txn = self.read_transaction();
+        let validator =
+            staking_contract.get_validator(&data_store.read(&txn), validator_address)?;
+
+        let parameters = RewardParameters {
+            genesis_supply: self.genesis_supply,
+            genesis_timestamp: self.genesis_timestamp,
+            timestamp: self.timestamp(),
+            total_stake: staking_contract.active_validators.values().copied().sum(),
+            average_batch_delay: self.average_batch_delay(),
+        };
+
+        let earns_rewards = validator.is_active() && !validator.is_jailed(self.block_number());
+        let stake = if earns_rewards { stake } else { Coin::ZERO };
+        Some(estimate_rewards(&parameters, stake))
+    }
+}
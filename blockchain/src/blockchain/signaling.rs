@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, io};
+
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_database::TransactionProxy;
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::{Deserialize, Serialize};
+
+use crate::Blockchain;
+
+/// The validators signaling the same `signal_data` at an election block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalCount {
+    /// The signaled data. Validators that don't signal anything are counted under None.
+    pub signal_data: Option<Blake2bHash>,
+    /// The number of elected validators signaling this data.
+    pub num_validators: u16,
+    /// The number of slots held by these validators in the elected epoch.
+    pub num_slots: u16,
+    /// The total stake of these validators at the election block.
+    pub stake: Coin,
+}
+
+/// The tally of the `signal_data` of the validators elected at an election block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalTally {
+    /// The election block the tally was taken at.
+    pub block_number: u32,
+    /// The signals of the elected validators, ordered by the number of slots (most first).
+    pub signals: Vec<SignalCount>,
+}
+
+impl SignalTally {
+    /// Returns the total number of slots of the elected validators.
+    pub fn total_slots(&self) -> u16 {
+        self.signals.iter().map(|signal| signal.num_slots).sum()
+    }
+
+    /// Returns the total stake of the elected validators.
+    pub fn total_stake(&self) -> Coin {
+        self.signals.iter().map(|signal| signal.stake).sum()
+    }
+}
+
+impl IntoDatabaseValue for SignalTally {
+    fn database_byte_size(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        Serialize::serialize(&self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for SignalTally {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+/// Implements methods to tally the `signal_data` of validators. Validators use the signal data to
+/// signal their readiness for chain upgrades. The signals are tallied at every election block,
+/// weighted both by the slots and by the stake of the elected validators.
+impl Blockchain {
+    /// Tallies the signals of the validators elected in the given election block. The block must
+    /// have been committed to the accounts tree already, such that the staking contract reflects
+    /// the state at the election block.
+    pub(crate) fn tally_signals(
+        &self,
+        block: &Block,
+        txn: &TransactionProxy,
+    ) -> Option<SignalTally> {
+        let validators = block.unwrap_macro_ref().get_validators()?;
+        let staking_contract = self.get_staking_contract_if_complete(Some(txn))?;
+        let data_store = self.get_staking_contract_store();
+        let data_store = data_store.read(txn);
+
+        let mut signals: BTreeMap<Option<Blake2bHash>, SignalCount> = BTreeMap::new();
+        for elected_validator in validators.iter() {
+            let validator = staking_contract.get_validator(&data_store, &elected_validator.address);
+            let signal_data = validator
+                .as_ref()
+                .and_then(|validator| validator.signal_data.clone());
+
+            let count = signals
+                .entry(signal_data.clone())
+                .or_insert_with(|| SignalCount {
+                    signal_data,
+                    num_validators: 0,
+                    num_slots: 0,
+                    stake: Coin::ZERO,
+                });
+            count.num_validators += 1;
+            count.num_slots += elected_validator.num_slots();
+            if let Some(validator) = validator {
+                count.stake += validator.total_stake;
+            }
+        }
+
+        let mut signals: Vec<_> = signals.into_values().collect();
+        signals.sort_by(|a, b| b.num_slots.cmp(&a.num_slots));
+
+        Some(SignalTally {
+            block_number: block.block_number(),
+            signals,
+        })
+    }
+
+    /// Returns the signal tally taken at the given election block, if it is known.
+    pub fn get_signal_tally(&self, block_number: u32) -> Result<SignalTally, BlockchainError> {
+        if !Policy::is_election_block_at(block_number) {
+            return Err(BlockchainError::BlockIsNotElection);
+        }
+        self.chain_store
+            .get_signal_tally(block_number, None)
+            .ok_or(BlockchainError::SignalTallyNotFound(block_number))
+    }
+
+    /// Returns the signal tallies taken at the election blocks of the given epochs. Tallies that
+    /// are not known, e.g. because the node synced its state after the election, are skipped.
+    pub fn get_signal_tallies(&self, from_epoch: u32, to_epoch: u32) -> Vec<SignalTally> {
+        // There are no tallies beyond the latest election block.
+        let to_epoch = to_epoch.min(Policy::epoch_at(self.election_head().block_number()));
+
+        let txn = self.read_transaction();
+        (from_epoch..=to_epoch)
+            .map_while(Policy::election_block_of)
+            .filter_map(|block_number| self.chain_store.get_signal_tally(block_number, Some(&txn)))
+            .collect()
+    }
+}
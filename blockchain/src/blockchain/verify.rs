@@ -1,6 +1,6 @@
 use nimiq_account::BlockLogger;
-use nimiq_block::{Block, BlockError, MacroBlock, MacroBody};
-use nimiq_blockchain_interface::{AbstractBlockchain, ChainInfo, PushError};
+use nimiq_block::{Block, BlockError, EquivocationProof, MacroBlock, MacroBody};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, ChainInfo, PushError};
 use nimiq_database::{
     traits::{ReadTransaction, WriteTransaction},
     TransactionProxy as DBTransaction, WriteTransactionProxy,
@@ -8,6 +8,8 @@ use nimiq_database::{
 use nimiq_hash::Hash;
 use nimiq_primitives::policy::Policy;
 
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::BlockProcessingStage;
 use crate::{BlockProducer, Blockchain};
 
 /// Implements methods to verify the validity of blocks.
@@ -21,6 +23,11 @@ impl Blockchain {
         block: &Block,
         trusted: bool,
     ) -> Result<(), PushError> {
+        #[cfg(feature = "metrics")]
+        let body_timer = self
+            .metrics
+            .time_block_processing(block, BlockProcessingStage::VerifyBody);
+
         // We expect full blocks (with body) here.
         block
             .body()
@@ -66,9 +73,17 @@ impl Blockchain {
             }
         }
 
+        #[cfg(feature = "metrics")]
+        drop(body_timer);
+
         // In trusted don't do slot related checks since they are mostly signature verifications
         // that can be slow.
         if !trusted {
+            #[cfg(feature = "metrics")]
+            let signatures_timer = self
+                .metrics
+                .time_block_processing(block, BlockProcessingStage::VerifySignatures);
+
             // Get the proposer for this block. The block's predecessor is not necessarily on the
             // main chain, thus the predecessor's VRF seed is used.
             let proposer = self
@@ -93,7 +108,14 @@ impl Blockchain {
             // Verify that the transactions in the block are valid.
             self.verify_transactions(block)?;
 
+            #[cfg(feature = "metrics")]
+            drop(signatures_timer);
+
             // Verify that the equivocation proofs are valid.
+            #[cfg(feature = "metrics")]
+            let _timer = self
+                .metrics
+                .time_block_processing(block, BlockProcessingStage::VerifyEquivocationProofs);
             self.verify_equivocation_proofs(block, txn)?;
         }
 
@@ -232,6 +254,35 @@ impl Blockchain {
         Ok(())
     }
 
+    /// Verifies an equivocation proof that is not part of a block yet, e.g. one that was received
+    /// from the network or submitted by an external observer. The proof must be includable in the
+    /// next block, i.e. it must be within its reporting window and must not have been included yet.
+    pub fn verify_equivocation_proof(
+        &self,
+        equivocation_proof: &EquivocationProof,
+    ) -> Result<(), BlockchainError> {
+        if !equivocation_proof.is_valid_at(self.block_number() + 1) {
+            return Err(BlockchainError::EquivocationProofExpired);
+        }
+
+        let txn = self.read_transaction();
+        if self
+            .history_store
+            .has_equivocation_proof(equivocation_proof.locator(), Some(&txn))
+        {
+            return Err(BlockchainError::EquivocationAlreadyIncluded(
+                equivocation_proof.locator(),
+            ));
+        }
+
+        let validators = self.get_validators_for_epoch(
+            Policy::epoch_at(equivocation_proof.block_number()),
+            Some(&txn),
+        )?;
+        equivocation_proof.verify(self.network_id, &validators)?;
+        Ok(())
+    }
+
     /// Verifies a block against the blockchain state BEFORE changes to the accounts tree and thus to the staking contract.
     /// Some fields in the staking contract are cleared using the FinalizeBatch and FinalizeEpoch Inherents in preparation for the next batch.
     /// Thus, we need to compare the respective fields in the block before clearing the staking contract.
@@ -1,16 +1,52 @@
+use std::time::Instant;
+
 use nimiq_block::{Block, BlockBody::Micro};
 use nimiq_blockchain_interface::{ChunksPushError, ChunksPushResult, PushError, PushResult};
 use nimiq_hash::Blake2bHash;
 use prometheus_client::{
     encoding::{EncodeLabelSet, EncodeLabelValue},
-    metrics::{counter::Counter, family::Family},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
     registry::Registry,
 };
 
-#[derive(Default)]
+use crate::SignalTally;
+
+type HistogramConstructor = fn() -> Histogram;
+
+fn duration_histogram() -> Histogram {
+    // 100µs up to ~3s.
+    Histogram::new(exponential_buckets(0.0001, 2.0, 15))
+}
+
+fn rebranch_depth_histogram() -> Histogram {
+    Histogram::new(exponential_buckets(1.0, 2.0, 8))
+}
+
 pub struct BlockchainMetrics {
     block_push_counts: Family<PushResultLabels, Counter>,
     transactions_counts: Family<TransactionProcessedLabels, Counter>,
+    signal_slots: Family<SignalLabels, Gauge>,
+    signal_stake: Family<SignalLabels, Gauge>,
+    block_processing_duration: Family<BlockProcessingLabels, Histogram, HistogramConstructor>,
+    rebranch_depth: Family<BlockTypeLabels, Histogram, HistogramConstructor>,
+}
+
+impl Default for BlockchainMetrics {
+    fn default() -> Self {
+        Self {
+            block_push_counts: Family::default(),
+            transactions_counts: Family::default(),
+            signal_slots: Family::default(),
+            signal_stake: Family::default(),
+            block_processing_duration: Family::new_with_constructor(duration_histogram),
+            rebranch_depth: Family::new_with_constructor(rebranch_depth_histogram),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -29,6 +65,79 @@ enum BlockPushResult {
     Invalid,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct BlockProcessingLabels {
+    block_type: BlockType,
+    stage: BlockProcessingStage,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct BlockTypeLabels {
+    block_type: BlockType,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum BlockType {
+    Micro,
+    Macro,
+    Skip,
+}
+
+impl From<&Block> for BlockType {
+    fn from(block: &Block) -> Self {
+        if block.is_skip() {
+            BlockType::Skip
+        } else if block.is_macro() {
+            BlockType::Macro
+        } else {
+            BlockType::Micro
+        }
+    }
+}
+
+/// The stages of processing a block that are timed.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum BlockProcessingStage {
+    /// Intrinsic checks of the block and its body, and checks against its predecessor.
+    VerifyBody,
+    /// Verification of the proposer, validator and transaction signatures.
+    VerifySignatures,
+    /// Verification of the equivocation proofs included in the block.
+    VerifyEquivocationProofs,
+    /// Detection of forks created by the proposer of the block.
+    DetectForks,
+    /// Commit of the block to the accounts trie.
+    CommitAccounts,
+    /// Addition of the transactions and inherents of the block to the history tree.
+    UpdateHistory,
+    /// Write of the information needed to revert the block.
+    WriteRevertInfo,
+    /// Removal of the revert information of the previous batch at a macro block.
+    ClearRevertInfos,
+}
+
+/// Times a stage of processing a block. The time is recorded when the timer is dropped, also if
+/// the stage fails.
+pub struct BlockProcessingTimer<'a> {
+    metrics: &'a BlockchainMetrics,
+    labels: BlockProcessingLabels,
+    start: Instant,
+}
+
+impl<'a> Drop for BlockProcessingTimer<'a> {
+    fn drop(&mut self) {
+        self.metrics
+            .block_processing_duration
+            .get_or_create(&self.labels)
+            .observe(self.start.elapsed().as_secs_f64());
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct SignalLabels {
+    signal_data: String,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 struct TransactionProcessedLabels {
     ty: TransactionProcessed,
@@ -53,6 +162,30 @@ impl BlockchainMetrics {
             "Count of transactions applied/reverted",
             self.transactions_counts.clone(),
         );
+
+        registry.register(
+            "signal_slots",
+            "Number of slots of the elected validators per signal data",
+            self.signal_slots.clone(),
+        );
+
+        registry.register(
+            "signal_stake",
+            "Stake of the elected validators per signal data",
+            self.signal_stake.clone(),
+        );
+
+        registry.register(
+            "block_processing_duration_seconds",
+            "Time spent in each stage of processing a block",
+            self.block_processing_duration.clone(),
+        );
+
+        registry.register(
+            "rebranch_depth",
+            "Number of blocks reverted by a rebranch",
+            self.rebranch_depth.clone(),
+        );
     }
 
     #[inline]
@@ -95,12 +228,39 @@ impl BlockchainMetrics {
             .inc_by(tx_count as u64);
     }
 
+    /// Starts timing the given stage of processing the block. The time is recorded when the
+    /// returned timer is dropped.
+    #[inline]
+    pub fn time_block_processing(
+        &self,
+        block: &Block,
+        stage: BlockProcessingStage,
+    ) -> BlockProcessingTimer<'_> {
+        BlockProcessingTimer {
+            metrics: self,
+            labels: BlockProcessingLabels {
+                block_type: block.into(),
+                stage,
+            },
+            start: Instant::now(),
+        }
+    }
+
     #[inline]
     pub fn note_rebranch(
         &self,
         reverted_blocks: &[(Blake2bHash, Block)],
         adopted_blocks: &[(Blake2bHash, Block)],
     ) {
+        // The depth is labeled with the type of the new head.
+        if let Some((_, head)) = adopted_blocks.last() {
+            self.rebranch_depth
+                .get_or_create(&BlockTypeLabels {
+                    block_type: head.into(),
+                })
+                .observe(reverted_blocks.len() as f64);
+        }
+
         for (_, micro_block) in reverted_blocks {
             if let Some(Micro(micro_body)) = micro_block.body() {
                 self.transactions_counts
@@ -121,4 +281,26 @@ impl BlockchainMetrics {
             }
         }
     }
+
+    #[inline]
+    pub fn note_signal_tally(&self, tally: &SignalTally) {
+        // Only the signals of the current validators are reported.
+        self.signal_slots.clear();
+        self.signal_stake.clear();
+
+        for signal in &tally.signals {
+            let labels = SignalLabels {
+                signal_data: signal
+                    .signal_data
+                    .as_ref()
+                    .map_or_else(|| "none".to_string(), |signal_data| signal_data.to_hex()),
+            };
+            self.signal_slots
+                .get_or_create(&labels)
+                .set(signal.num_slots as i64);
+            self.signal_stake
+                .get_or_create(&labels)
+                .set(u64::from(signal.stake) as i64);
+        }
+    }
 }
@@ -8,6 +8,8 @@ use nimiq_database::{
 use nimiq_hash::Blake2bHash;
 use nimiq_primitives::{policy::Policy, trie::trie_diff::TrieDiff};
 
+use crate::blockchain::signaling::SignalTally;
+
 #[derive(Debug)]
 pub struct ChainStore {
     /// Database handle.
@@ -22,6 +24,8 @@ pub struct ChainStore {
     revert_table: TableProxy,
     /// A database of accounts trie diffs for a block.
     accounts_diff_table: TableProxy,
+    /// A database of validator signal tallies indexed by the number of their election block.
+    signal_table: TableProxy,
 }
 
 impl ChainStore {
@@ -30,8 +34,10 @@ impl ChainStore {
     const HEIGHT_IDX_NAME: &'static str = "HeightIndex";
     const REVERT_DB_NAME: &'static str = "Receipts";
     const ACCOUNTS_DIFF_DB_NAME: &'static str = "AccountsDiff";
+    const SIGNAL_DB_NAME: &'static str = "SignalTally";
 
     const HEAD_KEY: &'static str = "head";
+    const HISTORY_PRUNED_KEY: &'static str = "history_pruned";
 
     pub fn new(db: DatabaseProxy) -> Self {
         let chain_table = db.open_table(Self::CHAIN_DB_NAME.to_string());
@@ -43,6 +49,8 @@ impl ChainStore {
         let revert_table =
             db.open_table_with_flags(Self::REVERT_DB_NAME.to_string(), TableFlags::UINT_KEYS);
         let accounts_diff_table = db.open_table(Self::ACCOUNTS_DIFF_DB_NAME.to_string());
+        let signal_table =
+            db.open_table_with_flags(Self::SIGNAL_DB_NAME.to_string(), TableFlags::UINT_KEYS);
         ChainStore {
             db,
             chain_table,
@@ -50,6 +58,7 @@ impl ChainStore {
             height_idx,
             revert_table,
             accounts_diff_table,
+            signal_table,
         }
     }
 
@@ -59,6 +68,7 @@ impl ChainStore {
         txn.clear_database(&self.height_idx);
         txn.clear_database(&self.revert_table);
         txn.clear_database(&self.accounts_diff_table);
+        txn.clear_database(&self.signal_table);
     }
 
     pub fn get_head(&self, txn_option: Option<&TransactionProxy>) -> Option<Blake2bHash> {
@@ -75,6 +85,25 @@ impl ChainStore {
         txn.put(&self.chain_table, ChainStore::HEAD_KEY, hash);
     }
 
+    /// Returns the number of the last epoch whose history was pruned, if any.
+    pub fn get_history_pruned_epoch(&self, txn_option: Option<&TransactionProxy>) -> Option<u32> {
+        match txn_option {
+            Some(txn) => txn.get(&self.chain_table, ChainStore::HISTORY_PRUNED_KEY),
+            None => self
+                .db
+                .read_transaction()
+                .get(&self.chain_table, ChainStore::HISTORY_PRUNED_KEY),
+        }
+    }
+
+    pub fn set_history_pruned_epoch(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) {
+        txn.put(
+            &self.chain_table,
+            ChainStore::HISTORY_PRUNED_KEY,
+            &epoch_number,
+        );
+    }
+
     pub fn get_chain_info(
         &self,
         hash: &Blake2bHash,
@@ -572,6 +601,24 @@ impl ChainStore {
         txn.put_reserve(&self.revert_table, &block_height, receipts);
     }
 
+    pub fn put_signal_tally(&self, txn: &mut WriteTransactionProxy, tally: &SignalTally) {
+        txn.put_reserve(&self.signal_table, &tally.block_number, tally);
+    }
+
+    pub fn get_signal_tally(
+        &self,
+        block_number: u32,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<SignalTally> {
+        match txn_option {
+            Some(txn) => txn.get(&self.signal_table, &block_number),
+            None => self
+                .db
+                .read_transaction()
+                .get(&self.signal_table, &block_number),
+        }
+    }
+
     pub fn get_revert_info(
         &self,
         block_height: u32,
@@ -55,6 +55,11 @@ pub struct HistoryStore {
     /// A database of all raw transaction (and reward inherent) hashes indexed by their sender and
     /// recipient addresses.
     address_table: TableProxy,
+    /// A database of the leaf hashes of all punishment and equivocation events indexed by the
+    /// address of the offending validator and ordered by block number.
+    punishment_table: TableProxy,
+    /// A database of the history tree roots of all pruned epochs indexed by their epoch number.
+    pruned_root_table: TableProxy,
 }
 
 impl HistoryStore {
@@ -63,6 +68,8 @@ impl HistoryStore {
     const TX_HASH_DB_NAME: &'static str = "LeafHashesByTxHash";
     const LAST_LEAF_DB_NAME: &'static str = "LastLeafIndexesByBlock";
     const ADDRESS_DB_NAME: &'static str = "TxHashesByAddress";
+    const PUNISHMENT_DB_NAME: &'static str = "PunishmentsByValidator";
+    const PRUNED_ROOT_DB_NAME: &'static str = "PrunedHistoryRoots";
 
     /// Creates a new HistoryStore.
     pub fn new(db: DatabaseProxy) -> Self {
@@ -78,6 +85,12 @@ impl HistoryStore {
             Self::ADDRESS_DB_NAME.to_string(),
             TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
         );
+        let punishment_table = db.open_table_with_flags(
+            Self::PUNISHMENT_DB_NAME.to_string(),
+            TableFlags::DUPLICATE_KEYS | TableFlags::DUP_FIXED_SIZE_VALUES,
+        );
+        let pruned_root_table =
+            db.open_table_with_flags(Self::PRUNED_ROOT_DB_NAME.to_string(), TableFlags::UINT_KEYS);
 
         HistoryStore {
             db,
@@ -86,6 +99,8 @@ impl HistoryStore {
             tx_hash_table,
             last_leaf_table,
             address_table,
+            punishment_table,
+            pruned_root_table,
         }
     }
 
@@ -172,7 +187,18 @@ impl HistoryStore {
                 }
                 HistoricTransactionData::Equivocation(_)
                 | HistoricTransactionData::Penalize(_)
-                | HistoricTransactionData::Jail(_) => {}
+                | HistoricTransactionData::Jail(_) => {
+                    let validator_address = Self::punished_validator(&hist_tx.data)
+                        .expect("Punishment events must have a validator");
+                    txn.remove_item(
+                        &self.punishment_table,
+                        validator_address,
+                        &OrderedHash {
+                            index: block_number,
+                            hash: leaf_hash,
+                        },
+                    );
+                }
             }
         }
 
@@ -319,8 +345,8 @@ impl HistoryStore {
                     },
                 );
             }
-            // Do not index equivocation or punishments events, since I do not see a use case
-            // for this at the time.
+            // Equivocation and punishment events are not added to the address database, but to
+            // the punishment database of the offending validator.
             HistoricTransactionData::Equivocation(_)
             | HistoricTransactionData::Penalize(_)
             | HistoricTransactionData::Jail(_) => {
@@ -332,6 +358,17 @@ impl HistoryStore {
                         hash: leaf_hash.clone(),
                     },
                 );
+
+                let validator_address = Self::punished_validator(&hist_tx.data)
+                    .expect("Punishment events must have a validator");
+                txn.put(
+                    &self.punishment_table,
+                    validator_address,
+                    &OrderedHash {
+                        index: hist_tx.block_number,
+                        hash: leaf_hash.clone(),
+                    },
+                );
             }
         }
         hist_tx.serialized_size()
@@ -410,6 +447,16 @@ impl HistoryStore {
         (start, end)
     }
 
+    /// Returns the address of the offending validator for equivocation and punishment events.
+    fn punished_validator(data: &HistoricTransactionData) -> Option<&Address> {
+        match data {
+            HistoricTransactionData::Equivocation(ev) => Some(ev.locator.validator_address()),
+            HistoricTransactionData::Penalize(ev) => Some(&ev.validator_address),
+            HistoricTransactionData::Jail(ev) => Some(&ev.validator_address),
+            HistoricTransactionData::Basic(_) | HistoricTransactionData::Reward(_) => None,
+        }
+    }
+
     /// Returns the index of the last transaction (or reward inherent) associated to the given address.
     fn get_last_tx_index_for_address(
         &self,
@@ -462,6 +509,8 @@ impl HistoryInterface for HistoryStore {
         txn.clear_database(&self.tx_hash_table);
         txn.clear_database(&self.last_leaf_table);
         txn.clear_database(&self.address_table);
+        txn.clear_database(&self.punishment_table);
+        txn.clear_database(&self.pruned_root_table);
     }
 
     /// Returns the length (i.e. the number of leaves) of the History Tree at a given block height.
@@ -539,6 +588,9 @@ impl HistoryInterface for HistoryStore {
         epoch_number: u32,
         hist_txs: &[HistoricTransaction],
     ) -> Option<(Blake2bHash, u64)> {
+        let _span =
+            log::debug_span!("add_to_history", epoch_number, num_txs = hist_txs.len()).entered();
+
         // Get the history tree.
         let mut tree = MerkleMountainRange::new(MMRStore::with_write_transaction(
             &self.hist_tree_table,
@@ -629,6 +681,16 @@ impl HistoryInterface for HistoryStore {
         Some(())
     }
 
+    fn prune_history(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) -> Option<()> {
+        // Keep the history tree root, such that it remains available after the tree is removed.
+        let root = self.get_history_tree_root(epoch_number, Some(txn))?;
+
+        self.remove_history(txn, epoch_number)?;
+        txn.put(&self.pruned_root_table, &epoch_number, &root);
+
+        Some(())
+    }
+
     /// Gets the history tree root for a given epoch.
     fn get_history_tree_root(
         &self,
@@ -651,6 +713,13 @@ impl HistoryInterface for HistoryStore {
             epoch_number,
         ));
 
+        // If the history of the epoch was pruned, return the root that was kept.
+        if tree.is_empty() {
+            if let Some(root) = txn.get(&self.pruned_root_table, &epoch_number) {
+                return Some(root);
+            }
+        }
+
         // Return the history root.
         tree.get_root().ok()
     }
@@ -934,10 +1003,20 @@ impl HistoryInterface for HistoryStore {
         max: u16,
         txn_option: Option<&TransactionProxy>,
     ) -> Vec<Blake2bHash> {
-        if max == 0 {
-            return vec![];
-        }
+        self.get_indexed_tx_hashes_by_address(address, max, None, txn_option)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, hash)| hash)
+            .collect()
+    }
 
+    fn get_indexed_tx_hashes_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<(u32, &Blake2bHash)>,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<Vec<(u32, Blake2bHash)>> {
         let read_txn: TransactionProxy;
         let txn = match txn_option {
             Some(txn) => txn,
@@ -948,27 +1027,89 @@ impl HistoryInterface for HistoryStore {
         };
 
         let mut tx_hashes = vec![];
-
-        // Seek to the first transaction hash at the given address. If there's none, stop here.
         let mut cursor = txn.cursor(&self.address_table);
 
-        if cursor.seek_key::<Address, OrderedHash>(address).is_none() {
-            return tx_hashes;
-        }
+        match start_at {
+            // Seek to the given transaction hash at the given address. The page starts with the
+            // transaction before it.
+            Some((index, hash)) => {
+                let start_at = OrderedHash {
+                    index,
+                    hash: hash.clone(),
+                };
+                if !cursor.seek_key_value(address, &start_at) {
+                    return None;
+                }
+            }
+            // Seek to the first transaction hash at the given address and then go to the last one,
+            // which starts the page. If there's none, stop here.
+            None => {
+                if max == 0 || cursor.seek_key::<Address, OrderedHash>(address).is_none() {
+                    return Some(tx_hashes);
+                }
 
-        // Then go to the last transaction hash at the given address and add it to the transaction
-        // hashes list.
-        tx_hashes.push(cursor.last_duplicate::<OrderedHash>().expect("This shouldn't panic since we already verified before that there is at least one transactions at this address!").hash);
+                let last = cursor.last_duplicate::<OrderedHash>().expect("This shouldn't panic since we already verified before that there is at least one transactions at this address!");
+                tx_hashes.push((last.index, last.hash));
+            }
+        }
 
         while tx_hashes.len() < max as usize {
             // Get previous transaction hash.
             match cursor.prev_duplicate::<Address, OrderedHash>() {
-                Some((_, v)) => tx_hashes.push(v.hash),
+                Some((_, v)) => tx_hashes.push((v.index, v.hash)),
                 None => break,
             };
         }
 
-        tx_hashes
+        Some(tx_hashes)
+    }
+
+    /// Returns the equivocation and punishment events of the given validator. It fetches the events
+    /// from most recent to least recent up to the maximum number given.
+    fn get_punishments_by_address(
+        &self,
+        validator_address: &Address,
+        max: u16,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<HistoricTransaction> {
+        let read_txn: TransactionProxy;
+        let txn = match txn_option {
+            Some(txn) => txn,
+            None => {
+                read_txn = self.db.read_transaction();
+                &read_txn
+            }
+        };
+
+        let mut punishments = vec![];
+
+        // Seek to the first event of the given validator. If there's none, stop here.
+        let mut cursor = txn.cursor(&self.punishment_table);
+
+        if max == 0
+            || cursor
+                .seek_key::<Address, OrderedHash>(validator_address)
+                .is_none()
+        {
+            return punishments;
+        }
+
+        // Then walk back from the most recent event.
+        let mut current = cursor.last_duplicate::<OrderedHash>();
+
+        while let Some(leaf) = current {
+            if let Some(hist_tx) = self.get_historic_tx(&leaf.hash, Some(txn)) {
+                punishments.push(hist_tx);
+            }
+            if punishments.len() >= max as usize {
+                break;
+            }
+            current = cursor
+                .prev_duplicate::<Address, OrderedHash>()
+                .map(|(_, v)| v);
+        }
+
+        punishments
     }
 
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
@@ -1711,6 +1852,101 @@ mod tests {
         );
 
         assert_eq!(query_4.len(), 0);
+
+        // Page through the transactions of an address.
+        let query_5 = history_store
+            .get_indexed_tx_hashes_by_address(&Address::burn_address(), 2, None, Some(&txn))
+            .unwrap();
+
+        assert_eq!(query_5.len(), 2);
+        assert_eq!(query_5[0].1, *hashes[6]);
+        assert_eq!(query_5[1].1, *hashes[5]);
+
+        let query_6 = history_store
+            .get_indexed_tx_hashes_by_address(
+                &Address::burn_address(),
+                2,
+                Some((query_5[1].0, &query_5[1].1)),
+                Some(&txn),
+            )
+            .unwrap();
+
+        assert_eq!(query_6.len(), 2);
+        assert_eq!(query_6[0].1, *hashes[3]);
+        assert_eq!(query_6[1].1, *hashes[1]);
+
+        let query_7 = history_store
+            .get_indexed_tx_hashes_by_address(
+                &Address::burn_address(),
+                2,
+                Some((query_6[1].0, &query_6[1].1)),
+                Some(&txn),
+            )
+            .unwrap();
+
+        assert_eq!(query_7.len(), 1);
+        assert_eq!(query_7[0].1, *hashes[0]);
+
+        let query_8 = history_store
+            .get_indexed_tx_hashes_by_address(
+                &Address::burn_address(),
+                2,
+                Some((query_7[0].0, &query_7[0].1)),
+                Some(&txn),
+            )
+            .unwrap();
+
+        assert_eq!(query_8.len(), 0);
+
+        // An unknown start is an error rather than an empty page.
+        let query_9 = history_store.get_indexed_tx_hashes_by_address(
+            &Address::burn_address(),
+            2,
+            Some((query_5[0].0, &*hashes[7])),
+            Some(&txn),
+        );
+
+        assert!(query_9.is_none());
+    }
+
+    #[test]
+    fn get_punishments_by_address_works() {
+        // Initialize History Store.
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 0, &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, 1, &hist_txs[3..]);
+
+        let validator_address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+
+        // Verify method works.
+        let query_1 = history_store.get_punishments_by_address(&validator_address, 99, Some(&txn));
+
+        assert_eq!(query_1.len(), 3);
+        for hist_tx in &hist_txs[8..] {
+            assert!(query_1.contains(hist_tx));
+        }
+
+        let query_2 = history_store.get_punishments_by_address(&validator_address, 2, Some(&txn));
+        assert_eq!(query_2.len(), 2);
+
+        let query_3 =
+            history_store.get_punishments_by_address(&Address::burn_address(), 99, Some(&txn));
+        assert_eq!(query_3.len(), 0);
+
+        // Removing the events also removes them from the index.
+        history_store.remove_partial_history(&mut txn, 1, 2);
+
+        let query_4 = history_store.get_punishments_by_address(&validator_address, 99, Some(&txn));
+        assert_eq!(query_4, vec![hist_txs[8].clone()]);
     }
 
     #[test]
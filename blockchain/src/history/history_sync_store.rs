@@ -0,0 +1,224 @@
+use std::convert::TryInto;
+
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteCursor, WriteTransaction},
+    DatabaseProxy, TableProxy, WriteTransactionProxy,
+};
+
+use crate::history::HistoryTreeChunk;
+
+/// A struct that persists the progress of history sync. It contains a database of the history
+/// chunks that were already downloaded and verified for a macro block, but whose batch set has not
+/// been pushed into the blockchain yet.
+/// This allows a node that is restarted in the middle of syncing a large epoch to resume from the
+/// chunks it already has instead of downloading the whole epoch again.
+/// Only the chunks themselves are persisted, not the partial history tree built from them. On
+/// resume, the stored chunks are verified again and their history is re-added, which rebuilds the
+/// partial history tree.
+///
+/// The keys are constructed as follows: The big-endian byte representation of the macro block
+/// number concatenated with the big-endian byte representation of the chunk index. This way, all
+/// chunks of a batch set are stored contiguously and in order.
+#[derive(Clone, Debug)]
+pub struct HistorySyncStore {
+    /// Database handle.
+    db: DatabaseProxy,
+    /// A database of all verified history chunks indexed by their macro block number and chunk index.
+    chunk_table: TableProxy,
+}
+
+impl HistorySyncStore {
+    const CHUNK_DB_NAME: &'static str = "HistorySyncChunks";
+
+    /// Creates a new HistorySyncStore.
+    pub fn new(db: DatabaseProxy) -> Self {
+        let chunk_table = db.open_table(Self::CHUNK_DB_NAME.to_string());
+
+        HistorySyncStore { db, chunk_table }
+    }
+
+    /// Stores verified history chunks, given as the block number of their macro block, their
+    /// chunk index and the chunk itself. The chunks are committed in a single transaction
+    /// immediately, so that they survive a restart of the node.
+    pub fn put_chunks<'a>(
+        &self,
+        chunks: impl IntoIterator<Item = (u32, u64, &'a HistoryTreeChunk)>,
+    ) {
+        let mut txn = self.db.write_transaction();
+        for (block_number, chunk_index, chunk) in chunks {
+            txn.put_reserve(
+                &self.chunk_table,
+                &index_to_key(block_number, chunk_index),
+                chunk,
+            );
+        }
+        txn.commit();
+    }
+
+    /// Returns all stored chunks for the macro block at the given block number, starting at
+    /// `first_chunk_index`. Only chunks with consecutive indices are returned, i.e. the returned
+    /// chunks cover a contiguous range of the history tree.
+    pub fn get_chunks(&self, block_number: u32, first_chunk_index: u64) -> Vec<HistoryTreeChunk> {
+        let txn = self.db.read_transaction();
+        let mut cursor = txn.cursor(&self.chunk_table);
+
+        let mut chunks = vec![];
+        let mut expected_index = first_chunk_index;
+        let mut entry = cursor.seek_range_key::<Vec<u8>, HistoryTreeChunk>(&index_to_key(
+            block_number,
+            first_chunk_index,
+        ));
+
+        while let Some((key, chunk)) = entry {
+            match key_to_index(key) {
+                Some((n, i)) if n == block_number && i == expected_index => {
+                    chunks.push(chunk);
+                    expected_index += 1;
+                }
+                _ => break,
+            }
+            entry = cursor.next();
+        }
+
+        chunks
+    }
+
+    /// Removes all stored chunks for the macro block at the given block number. The removal is
+    /// committed in its own transaction immediately.
+    pub fn remove_chunks(&self, block_number: u32) {
+        let mut txn = self.db.write_transaction();
+        {
+            let mut cursor = WriteTransaction::cursor(&txn, &self.chunk_table);
+            let mut pos =
+                cursor.seek_range_key::<Vec<u8>, HistoryTreeChunk>(&index_to_key(block_number, 0));
+
+            while let Some((key, _)) = pos {
+                match key_to_index(key) {
+                    Some((n, _)) if n == block_number => cursor.remove(),
+                    _ => break,
+                }
+                pos = cursor.next();
+            }
+        }
+        txn.commit();
+    }
+
+    /// Removes all stored chunks for macro blocks up to and including the given block number.
+    /// This is called whenever a macro block is pushed via history sync, since the progress for
+    /// this block (and any previous one) is no longer needed.
+    pub fn remove_chunks_up_to(&self, txn: &mut WriteTransactionProxy, block_number: u32) {
+        let mut cursor = WriteTransaction::cursor(txn, &self.chunk_table);
+        let mut pos: Option<(Vec<u8>, HistoryTreeChunk)> = cursor.first();
+
+        while let Some((key, _)) = pos {
+            match key_to_index(key) {
+                Some((n, _)) if n <= block_number => cursor.remove(),
+                _ => break,
+            }
+            pos = cursor.next();
+        }
+    }
+}
+
+/// Transforms a block number and a chunk index into the corresponding database key.
+fn index_to_key(block_number: u32, chunk_index: u64) -> Vec<u8> {
+    let mut bytes = block_number.to_be_bytes().to_vec();
+    bytes.extend_from_slice(&chunk_index.to_be_bytes());
+    bytes
+}
+
+/// Transforms a database key into the corresponding block number and chunk index. Returns None if
+/// it fails.
+fn key_to_index(key: Vec<u8>) -> Option<(u32, u64)> {
+    if key.len() != 12 {
+        return None;
+    }
+    let (block_number, index) = key.split_at(4);
+    let block_number = u32::from_be_bytes(block_number.try_into().ok()?);
+    let index = u64::from_be_bytes(index.try_into().ok()?);
+    Some((block_number, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_database::volatile::VolatileDatabase;
+    use nimiq_keys::Address;
+    use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+    use nimiq_test_log::test;
+    use nimiq_transaction::{
+        historic_transaction::{HistoricTransaction, HistoricTransactionData},
+        ExecutedTransaction, Transaction,
+    };
+
+    use super::*;
+    use crate::{history::HistoryStore, interface::HistoryInterface};
+
+    fn create_transaction(block: u32, value: u64) -> HistoricTransaction {
+        HistoricTransaction {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: block,
+            block_time: 0,
+            data: HistoricTransactionData::Basic(ExecutedTransaction::Ok(Transaction::new_basic(
+                Address::burn_address(),
+                Address::burn_address(),
+                Coin::from_u64_unchecked(value),
+                Coin::from_u64_unchecked(0),
+                0,
+                NetworkId::Dummy,
+            ))),
+        }
+    }
+
+    #[test]
+    fn it_can_persist_and_resume_chunks() {
+        let env = VolatileDatabase::new(20).unwrap();
+        let history_store = HistoryStore::new(env.clone());
+        let sync_store = HistorySyncStore::new(env.clone());
+
+        let block_number = Policy::genesis_block_number() + Policy::blocks_per_batch();
+        let hist_txs: Vec<_> = (0..5)
+            .map(|i| create_transaction(block_number, i))
+            .collect();
+
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, 1, &hist_txs);
+        let root = history_store.get_history_tree_root(1, Some(&txn)).unwrap();
+        let chunks: Vec<_> = (0..3)
+            .map(|i| {
+                history_store
+                    .prove_chunk(1, block_number, 2, i, Some(&txn))
+                    .unwrap()
+            })
+            .collect();
+        txn.abort();
+
+        // Persist the first and the last chunk only.
+        sync_store.put_chunks([(block_number, 0, &chunks[0]), (block_number, 2, &chunks[2])]);
+
+        // Only the contiguous range starting at the requested index is returned.
+        let stored = sync_store.get_chunks(block_number, 0);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].verify(&root, 0), Some(true));
+        assert_eq!(stored[0].history, hist_txs[0..2]);
+
+        let stored = sync_store.get_chunks(block_number, 1);
+        assert!(stored.is_empty());
+
+        sync_store.put_chunks([(block_number, 1, &chunks[1])]);
+        let stored = sync_store.get_chunks(block_number, 0);
+        assert_eq!(stored.len(), 3);
+        let history: Vec<_> = stored.into_iter().flat_map(|chunk| chunk.history).collect();
+        assert_eq!(history, hist_txs);
+
+        // Chunks of other blocks are not affected.
+        sync_store.put_chunks([(block_number + 1, 0, &chunks[0])]);
+        sync_store.remove_chunks(block_number);
+        assert!(sync_store.get_chunks(block_number, 0).is_empty());
+        assert_eq!(sync_store.get_chunks(block_number + 1, 0).len(), 1);
+
+        let mut txn = env.write_transaction();
+        sync_store.remove_chunks_up_to(&mut txn, block_number + 1);
+        txn.commit();
+        assert!(sync_store.get_chunks(block_number + 1, 0).is_empty());
+    }
+}
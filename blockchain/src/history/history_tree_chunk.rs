@@ -1,5 +1,9 @@
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    io,
+};
 
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
 use nimiq_hash::Blake2bHash;
 use nimiq_mmr::mmr::proof::RangeProof;
 use nimiq_transaction::historic_transaction::HistoricTransaction;
@@ -9,7 +13,7 @@ use serde::{Deserialize, Serialize};
 /// TODO: Update number.
 pub const CHUNK_SIZE: usize = 1024;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct HistoryTreeChunk {
     pub(crate) proof: RangeProof<Blake2bHash>,
     pub history: Vec<HistoricTransaction>,
@@ -38,3 +42,23 @@ impl HistoryTreeChunk {
             .ok()
     }
 }
+
+impl IntoDatabaseValue for HistoryTreeChunk {
+    fn database_byte_size(&self) -> usize {
+        nimiq_serde::Serialize::serialized_size(self)
+    }
+
+    fn copy_into_database(&self, mut bytes: &mut [u8]) {
+        nimiq_serde::Serialize::serialize_to_writer(self, &mut bytes).unwrap();
+    }
+}
+
+impl FromDatabaseValue for HistoryTreeChunk {
+    fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+    where
+        Self: Sized,
+    {
+        nimiq_serde::Deserialize::deserialize_from_vec(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
@@ -29,6 +29,10 @@ pub trait HistoryInterface {
     /// Removes the full history associated with a given epoch.
     fn remove_history(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) -> Option<()>;
 
+    /// Prunes the history associated with a given epoch. Unlike `remove_history`, this keeps the
+    /// history tree root of the epoch.
+    fn prune_history(&self, txn: &mut WriteTransactionProxy, epoch_number: u32) -> Option<()>;
+
     /// Obtains the current history root at the given epoch.
     fn get_history_tree_root(
         &self,
@@ -134,6 +138,27 @@ pub trait HistoryInterface {
         txn_option: Option<&TransactionProxy>,
     ) -> Vec<Blake2bHash>;
 
+    /// Same as `get_tx_hashes_by_address`, but also returns the index of each transaction hash at
+    /// the given address. If `start_at` is given as the index and hash of a transaction, only
+    /// transactions older than it are returned, which allows to page through the transactions of
+    /// an address. Returns `None` if `start_at` is not a transaction of the given address.
+    fn get_indexed_tx_hashes_by_address(
+        &self,
+        address: &Address,
+        max: u16,
+        start_at: Option<(u32, &Blake2bHash)>,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Option<Vec<(u32, Blake2bHash)>>;
+
+    /// Returns the equivocation and punishment events of the given validator. It fetches the events
+    /// from most recent to least recent up to the maximum number given.
+    fn get_punishments_by_address(
+        &self,
+        validator_address: &Address,
+        max: u16,
+        txn_option: Option<&TransactionProxy>,
+    ) -> Vec<HistoricTransaction>;
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -101,6 +101,12 @@ impl HistoryInterface for LightHistoryStore {
         todo!()
     }
 
+    fn prune_history(&self, _txn: &mut WriteTransactionProxy, _epoch_number: u32) -> Option<()> {
+        // The light history store doesn't keep any historic transactions, so there is nothing to
+        // prune.
+        Some(())
+    }
+
     fn get_history_tree_root(
         &self,
         epoch_number: u32,
@@ -271,6 +277,27 @@ impl HistoryInterface for LightHistoryStore {
         unimplemented!()
     }
 
+    fn get_indexed_tx_hashes_by_address(
+        &self,
+        _address: &nimiq_keys::Address,
+        _max: u16,
+        _start_at: Option<(u32, &Blake2bHash)>,
+        _txn_option: Option<&TransactionProxy>,
+    ) -> Option<Vec<(u32, Blake2bHash)>> {
+        // The light history store doesn't index transactions by address.
+        Some(vec![])
+    }
+
+    fn get_punishments_by_address(
+        &self,
+        _validator_address: &nimiq_keys::Address,
+        _max: u16,
+        _txn_option: Option<&TransactionProxy>,
+    ) -> Vec<HistoricTransaction> {
+        // The light history store doesn't keep any historic transactions.
+        vec![]
+    }
+
     fn prove(
         &self,
         _epoch_number: u32,
@@ -1,7 +1,9 @@
 pub use history_store::HistoryStore;
+pub use history_sync_store::HistorySyncStore;
 pub use history_tree_chunk::{HistoryTreeChunk, CHUNK_SIZE};
 
 mod history_store;
+mod history_sync_store;
 mod history_tree_chunk;
 pub mod interface;
 mod light_history_store;
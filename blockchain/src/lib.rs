@@ -2,9 +2,16 @@
 extern crate log;
 
 pub use block_production::BlockProducer;
-pub use blockchain::blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache};
+pub use blockchain::{
+    archive::ArchiveDataStoreRead,
+    blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache},
+    reward_attribution::StakerReward,
+    signaling::{SignalCount, SignalTally},
+};
+pub use chain_store::ChainStore;
 pub use history::*;
 
+pub(crate) mod archive_store;
 pub(crate) mod block_production;
 pub(crate) mod blockchain;
 pub(crate) mod blockchain_state;
@@ -13,3 +20,4 @@ pub mod chain_metrics;
 pub(crate) mod chain_store;
 pub(crate) mod history;
 pub mod reward;
+pub mod reward_calculator;
@@ -0,0 +1,141 @@
+use nimiq_primitives::{coin::Coin, policy::Policy};
+
+/// The average length of a year in milliseconds.
+pub const YEAR_MS: u64 = 31_557_600_000;
+
+/// The chain parameters the reward projection is based on.
+#[derive(Clone, Debug)]
+pub struct RewardParameters {
+    /// The supply at the genesis block, in Lunas.
+    pub genesis_supply: Coin,
+    /// The timestamp of the genesis block, in milliseconds.
+    pub genesis_timestamp: u64,
+    /// The timestamp the projection starts at, in milliseconds.
+    pub timestamp: u64,
+    /// The total stake of all active validators.
+    pub total_stake: Coin,
+    /// The average delay of the recently produced batches, in milliseconds.
+    pub average_batch_delay: u64,
+}
+
+/// The projected rewards for a stake.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardEstimate {
+    /// The stake the rewards are projected for.
+    pub stake: Coin,
+    /// The total stake of all active validators, including the projected stake.
+    pub total_stake: Coin,
+    /// The expected duration of an epoch given the observed batch delays, in milliseconds.
+    pub epoch_duration: u64,
+    /// The share of the batch rewards that is paid out given the observed batch delays.
+    pub batch_delay_penalty: f64,
+    /// The projected reward for the stake in the next epoch.
+    pub epoch_reward: Coin,
+    /// The projected annual reward for the stake without compounding.
+    pub annual_reward: Coin,
+    /// The annual percentage rate, i.e. the annual yield without compounding.
+    pub apr: f64,
+    /// The annual percentage yield if the rewards of every epoch are staked again.
+    pub apy: f64,
+}
+
+/// Returns the rewards emitted in the given time span, before the batch delay penalty is applied.
+fn emitted_rewards(parameters: &RewardParameters, start: u64, end: u64) -> u64 {
+    let genesis_supply = u64::from(parameters.genesis_supply);
+    Policy::supply_at(genesis_supply, parameters.genesis_timestamp, end)
+        - Policy::supply_at(genesis_supply, parameters.genesis_timestamp, start)
+}
+
+/// Projects the rewards of the given additional stake. Rewards are distributed pro-rata to the
+/// stake of all active validators, and the emission follows the supply curve given by
+/// `Policy::INITIAL_SUPPLY_VELOCITY` and `Policy::SUPPLY_DECAY`. Batches produced late are
+/// penalized down to `Policy::MINIMUM_REWARDS_PERCENTAGE`, so the observed average batch delay is
+/// assumed to persist. Transaction fees are not included.
+pub fn estimate_rewards(parameters: &RewardParameters, stake: Coin) -> RewardEstimate {
+    let total_stake = parameters.total_stake + stake;
+
+    let epoch_duration = Policy::blocks_per_epoch() as u64 * Policy::BLOCK_SEPARATION_TIME
+        + Policy::batches_per_epoch() as u64 * parameters.average_batch_delay;
+    let batch_delay_penalty = Policy::batch_delay_penalty(parameters.average_batch_delay);
+
+    // The share of the rewards attributed to the stake.
+    let share = if total_stake.is_zero() {
+        0.0
+    } else {
+        u64::from(stake) as f64 / u64::from(total_stake) as f64
+    };
+
+    let start = parameters.timestamp;
+    let epoch_reward = emitted_rewards(parameters, start, start + epoch_duration) as f64
+        * batch_delay_penalty
+        * share;
+    let annual_reward =
+        emitted_rewards(parameters, start, start + YEAR_MS) as f64 * batch_delay_penalty * share;
+
+    let (apr, apy) = if stake.is_zero() {
+        (0.0, 0.0)
+    } else {
+        let apr = annual_reward / u64::from(stake) as f64;
+        let epochs_per_year = YEAR_MS as f64 / epoch_duration as f64;
+        let apy = (1.0 + apr / epochs_per_year).powf(epochs_per_year) - 1.0;
+        (apr, apy)
+    };
+
+    RewardEstimate {
+        stake,
+        total_stake,
+        epoch_duration,
+        batch_delay_penalty,
+        epoch_reward: Coin::from_u64_unchecked(epoch_reward as u64),
+        annual_reward: Coin::from_u64_unchecked(annual_reward as u64),
+        apr,
+        apy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters(total_stake: u64, average_batch_delay: u64) -> RewardParameters {
+        RewardParameters {
+            genesis_supply: Coin::from_u64_unchecked(1_000_000_000_000_000),
+            genesis_timestamp: 0,
+            timestamp: YEAR_MS,
+            total_stake: Coin::from_u64_unchecked(total_stake),
+            average_batch_delay,
+        }
+    }
+
+    #[test]
+    fn rewards_are_pro_rata_to_stake() {
+        let parameters = parameters(300_000_000_000_000, 0);
+
+        let estimate = estimate_rewards(&parameters, Coin::from_u64_unchecked(100_000_000_000_000));
+        assert_eq!(estimate.batch_delay_penalty, 1.0);
+        assert_eq!(u64::from(estimate.total_stake), 400_000_000_000_000);
+
+        // A quarter of the emission is attributed to the stake.
+        let emitted = emitted_rewards(&parameters, YEAR_MS, 2 * YEAR_MS);
+        assert_eq!(u64::from(estimate.annual_reward), emitted / 4);
+        assert!(estimate.apy > estimate.apr);
+        assert!(estimate.epoch_reward < estimate.annual_reward);
+
+        let estimate = estimate_rewards(&parameters, Coin::ZERO);
+        assert_eq!(estimate.epoch_reward, Coin::ZERO);
+        assert_eq!(estimate.apr, 0.0);
+        assert_eq!(estimate.apy, 0.0);
+    }
+
+    #[test]
+    fn batch_delays_reduce_rewards() {
+        let stake = Coin::from_u64_unchecked(100_000_000_000_000);
+        let in_time = estimate_rewards(&parameters(300_000_000_000_000, 0), stake);
+        let delayed = estimate_rewards(&parameters(300_000_000_000_000, 10_000), stake);
+
+        assert!(delayed.batch_delay_penalty < 1.0);
+        assert!(delayed.batch_delay_penalty >= Policy::MINIMUM_REWARDS_PERCENTAGE);
+        assert!(delayed.epoch_duration > in_time.epoch_duration);
+        assert!(delayed.apr < in_time.apr);
+    }
+}
@@ -1,16 +1,28 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 use nimiq_block::{Block, BlockError};
-use nimiq_blockchain::Blockchain;
-use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
-use nimiq_hash::Hash;
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, BlockchainError, InclusionProofError, PushError, PushResult,
+    TransactionInclusionProof,
+};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_genesis::NetworkId;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::policy::Policy;
+use nimiq_serde::{Deserialize, Serialize};
 use nimiq_tendermint::ProposalMessage;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
     block_production::TemporaryBlockProducer,
+    blockchain::{
+        produce_macro_blocks, produce_macro_blocks_with_txns, signing_key, voting_key, REWARD_KEY,
+    },
     test_custom_block::{finalize_macro_block, next_macro_block_proposal},
 };
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
 
 #[test]
 fn prune_epoch_micro_blocks() {
@@ -112,6 +124,284 @@ fn prune_epoch_micro_blocks() {
         .is_err());
 }
 
+#[test]
+fn it_prunes_history_beyond_retention() {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+    let config = BlockchainConfig {
+        history_retention_epochs: Some(1),
+        ..Default::default()
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    // Produce three full epochs.
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        3 * Policy::batches_per_epoch() as usize,
+    );
+
+    {
+        let blockchain = blockchain.read();
+        assert_eq!(
+            Policy::epoch_at(blockchain.election_head().block_number()),
+            3
+        );
+        assert_eq!(blockchain.first_retained_history_epoch(), 1);
+        assert!(blockchain.history_store.num_epoch_transactions(1, None) > 0);
+    }
+    let roots: Vec<_> = (1..=3)
+        .map(|epoch_number| {
+            blockchain
+                .read()
+                .history_store
+                .get_history_tree_root(epoch_number, None)
+                .unwrap()
+        })
+        .collect();
+
+    // Only the last epoch is retained.
+    assert_eq!(Blockchain::prune_history_step(&blockchain), Some(1));
+    assert_eq!(Blockchain::prune_history_step(&blockchain), Some(2));
+    assert_eq!(Blockchain::prune_history_step(&blockchain), None);
+
+    let blockchain = blockchain.read();
+    assert_eq!(blockchain.first_retained_history_epoch(), 3);
+
+    assert_eq!(blockchain.history_store.num_epoch_transactions(1, None), 0);
+    assert_eq!(blockchain.history_store.num_epoch_transactions(2, None), 0);
+    assert!(blockchain.history_store.num_epoch_transactions(3, None) > 0);
+
+    // The history roots of the pruned epochs are kept.
+    for (epoch_number, root) in (1..=3).zip(roots) {
+        assert_eq!(
+            blockchain
+                .history_store
+                .get_history_tree_root(epoch_number, None),
+            Some(root)
+        );
+    }
+}
+
+#[test]
+fn it_creates_verifiable_transaction_inclusion_proofs() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let blockchain = Arc::clone(&temp_producer.blockchain);
+
+    // Produce two full epochs and one batch of the third epoch.
+    produce_macro_blocks(
+        &temp_producer.producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize + 1,
+    );
+
+    let blockchain = blockchain.read();
+    let election_head = blockchain.election_head();
+    let macro_head = blockchain.macro_head();
+
+    // A transaction of a finalized epoch is proven against the election block of its epoch.
+    let tx_hash: Blake2bHash = blockchain.history_store.get_epoch_transactions(1, None)[0]
+        .tx_hash()
+        .into();
+    let proof = blockchain.prove_transaction_inclusion(&tx_hash).unwrap();
+    assert_eq!(
+        proof.block.block_number(),
+        Policy::election_block_of(1).unwrap()
+    );
+    assert_eq!(proof.verify(&tx_hash, &election_head.hash()), Ok(()));
+    assert_eq!(
+        proof.verify(&tx_hash, &macro_head.hash()),
+        Err(InclusionProofError::UntrustedAnchorBlock)
+    );
+    assert_eq!(
+        proof.verify(&macro_head.hash(), &election_head.hash()),
+        Err(InclusionProofError::TransactionNotIncluded)
+    );
+
+    // The proof is self-contained.
+    let proof = TransactionInclusionProof::deserialize_from_vec(&proof.serialize_to_vec()).unwrap();
+    assert_eq!(proof.verify(&tx_hash, &election_head.hash()), Ok(()));
+
+    // A transaction of a finalized batch in the current epoch is proven against the checkpoint block.
+    let tx_hash: Blake2bHash = blockchain.history_store.get_epoch_transactions(3, None)[0]
+        .tx_hash()
+        .into();
+    let proof = blockchain.prove_transaction_inclusion(&tx_hash).unwrap();
+    assert_eq!(proof.block.block_number(), macro_head.block_number());
+    assert_eq!(proof.verify(&tx_hash, &macro_head.hash()), Ok(()));
+
+    // Unknown transactions can't be proven.
+    assert_eq!(
+        blockchain
+            .prove_transaction_inclusion(&macro_head.hash())
+            .err(),
+        Some(BlockchainError::TransactionNotFound)
+    );
+}
+
+#[test]
+fn it_archives_the_accounts_state_at_old_macro_blocks() {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+    let config = BlockchainConfig {
+        archive: true,
+        ..Default::default()
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let address = Address::from(&KeyPair::from(PrivateKey::from_str(REWARD_KEY).unwrap()));
+
+    // Push past several macro blocks, including an election block, and remember the state of
+    // the sending account at each of them.
+    let mut accounts = vec![];
+    for _ in 0..Policy::batches_per_epoch() + 2 {
+        produce_macro_blocks_with_txns(&producer, &blockchain, 1, 1, 2);
+
+        let blockchain = blockchain.read();
+        accounts.push((
+            blockchain.block_number(),
+            blockchain.get_account_if_complete(&address).unwrap(),
+        ));
+    }
+
+    let blockchain = blockchain.read();
+    assert_eq!(
+        blockchain.archived_range(),
+        Some((Policy::genesis_block_number(), blockchain.block_number()))
+    );
+    assert_ne!(accounts[0].1, accounts.last().unwrap().1);
+
+    // The archive returns the state at every old macro block.
+    for (block_number, account) in &accounts {
+        assert_eq!(
+            blockchain.get_account_at(&address, *block_number).as_ref(),
+            Ok(account)
+        );
+    }
+
+    // Only macro blocks are archived.
+    assert_eq!(
+        blockchain.get_account_at(&address, accounts[0].0 - 1),
+        Err(BlockchainError::BlockIsNotMacro)
+    );
+    assert_eq!(
+        blockchain.get_account_at(
+            &address,
+            Policy::macro_block_after(blockchain.block_number())
+        ),
+        Err(BlockchainError::StateNotArchived(
+            Policy::macro_block_after(blockchain.block_number())
+        ))
+    );
+}
+
+#[test]
+fn it_attributes_validator_rewards_to_stakers() {
+    let time = Arc::new(OffsetTime::new());
+    let env = VolatileDatabase::new(20).unwrap();
+    let config = BlockchainConfig {
+        archive: true,
+        ..Default::default()
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    // Produce two full epochs and one batch of the third epoch, such that the rewards of the
+    // last batch of the second epoch are paid out.
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize + 1,
+    );
+
+    let blockchain = blockchain.read();
+    let staker_address =
+        Address::from_user_friendly_address("NQ39 VBTN P2HX Q3MF KHF1 CCLA G6FS 9B8S VY28")
+            .unwrap();
+
+    let rewards = blockchain
+        .get_staker_rewards(&staker_address, 1, 3)
+        .unwrap();
+    assert_eq!(rewards.len(), 2 * Policy::batches_per_epoch() as usize);
+    for reward in rewards {
+        assert!(reward.active_balance < reward.validator_stake);
+        assert_eq!(
+            u64::from(reward.value),
+            (u128::from(u64::from(reward.validator_reward))
+                * u128::from(u64::from(reward.active_balance))
+                / u128::from(u64::from(reward.validator_stake))) as u64
+        );
+        assert_eq!(
+            reward.block_number,
+            Policy::macro_block_of(reward.batch_number + 1).unwrap()
+        );
+    }
+
+    // Addresses that are not staking don't earn rewards.
+    assert!(blockchain
+        .get_staker_rewards(&Address::burn_address(), 1, 2)
+        .unwrap()
+        .is_empty());
+
+    // The genesis epoch has no rewards.
+    assert_eq!(
+        blockchain.get_staker_rewards(&staker_address, 0, 2),
+        Err(BlockchainError::InvalidEpoch)
+    );
+}
+
+#[test]
+fn it_tallies_validator_signals_at_election_blocks() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let blockchain = Arc::clone(&temp_producer.blockchain);
+
+    // Produce two full epochs.
+    produce_macro_blocks(
+        &temp_producer.producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize,
+    );
+
+    let blockchain = blockchain.read();
+    let election_block = Policy::election_block_of(1).unwrap();
+
+    // The only validator doesn't signal anything and holds all slots.
+    let tally = blockchain.get_signal_tally(election_block).unwrap();
+    assert_eq!(tally.block_number, election_block);
+    assert_eq!(tally.signals.len(), 1);
+    assert_eq!(tally.signals[0].signal_data, None);
+    assert_eq!(tally.signals[0].num_validators, 1);
+    assert_eq!(tally.total_slots(), Policy::SLOTS);
+    assert!(!tally.total_stake().is_zero());
+
+    // The history contains a tally for every election block.
+    let tallies = blockchain.get_signal_tallies(1, 3);
+    assert_eq!(tallies.len(), 2);
+    assert_eq!(tallies[0], tally);
+    assert_eq!(
+        tallies[1].block_number,
+        Policy::election_block_of(2).unwrap()
+    );
+
+    assert_eq!(
+        blockchain.get_signal_tally(election_block - 1),
+        Err(BlockchainError::BlockIsNotElection)
+    );
+    assert_eq!(
+        blockchain.get_signal_tally(Policy::election_block_of(3).unwrap()),
+        Err(BlockchainError::SignalTallyNotFound(
+            Policy::election_block_of(3).unwrap()
+        ))
+    );
+}
+
 #[test]
 fn can_detect_invalid_punished_set() {
     let temp_producer = TemporaryBlockProducer::new();
@@ -1,14 +1,15 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
 use nimiq_database::volatile::VolatileDatabase;
 use nimiq_genesis::NetworkId;
+use nimiq_keys::{Address, KeyPair, PrivateKey};
 use nimiq_primitives::policy::Policy;
 use nimiq_test_log::test;
 use nimiq_test_utils::blockchain::{
     fill_micro_blocks_with_txns, produce_macro_blocks, produce_macro_blocks_with_txns, signing_key,
-    voting_key,
+    voting_key, REWARD_KEY,
 };
 use nimiq_utils::time::OffsetTime;
 use parking_lot::RwLock;
@@ -179,6 +180,89 @@ fn history_sync_works() {
         ),
         Ok(PushResult::Extended)
     );
+
+    // The signals are tallied at the election blocks pushed with history sync as well.
+    let blockchain2 = blockchain2.read();
+    for epoch in 1..=2 {
+        let election_block = Policy::election_block_of(epoch).unwrap();
+        assert_eq!(
+            blockchain2.get_signal_tally(election_block),
+            blockchain.get_signal_tally(election_block)
+        );
+        assert!(blockchain2.get_signal_tally(election_block).is_ok());
+    }
+}
+
+// Tests that a blockchain in archive mode archives the accounts state at every macro block that is
+// pushed with history sync, including the checkpoint blocks that are skipped.
+#[test]
+fn history_sync_archives_every_macro_block() {
+    let time = Arc::new(OffsetTime::new());
+    let config = || BlockchainConfig {
+        archive: true,
+        ..Default::default()
+    };
+
+    // Create a blockchain to produce the macro blocks.
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            config(),
+            NetworkId::UnitAlbatross,
+            Arc::clone(&time),
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks_with_txns(
+        &producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize,
+        1,
+        2,
+    );
+
+    // Push the election blocks to a second blockchain using history sync.
+    let blockchain2 = Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            config(),
+            NetworkId::UnitAlbatross,
+            time,
+        )
+        .unwrap(),
+    ));
+
+    let blockchain = blockchain.read();
+    for epoch in 1..=2 {
+        let election_block = blockchain
+            .chain_store
+            .get_block_at(Policy::election_block_of(epoch).unwrap(), true, None)
+            .unwrap();
+        let history = blockchain.history_store.get_epoch_transactions(epoch, None);
+        assert_eq!(
+            Blockchain::push_history_sync(blockchain2.upgradable_read(), election_block, &history),
+            Ok(PushResult::Extended)
+        );
+    }
+
+    // The archive of the second blockchain matches the one of the producing blockchain.
+    let blockchain2 = blockchain2.read();
+    assert_eq!(blockchain2.archived_range(), blockchain.archived_range());
+
+    let address = Address::from(&KeyPair::from(PrivateKey::from_str(REWARD_KEY).unwrap()));
+    let mut block_number = Policy::genesis_block_number();
+    while block_number <= blockchain.block_number() {
+        assert_eq!(
+            blockchain2.get_account_at(&address, block_number),
+            blockchain.get_account_at(&address, block_number)
+        );
+        assert_eq!(
+            blockchain2.get_validators_at(block_number),
+            blockchain.get_validators_at(block_number)
+        );
+        block_number += Policy::blocks_per_batch();
+    }
 }
 
 // Tests if the history sync works when micro blocks have already been pushed in the blockchain.
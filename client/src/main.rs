@@ -1,18 +1,19 @@
 use std::time::Duration;
 
 use log::info;
-use nimiq::prover::prover_main;
 pub use nimiq::{
     client::Client,
     config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile},
     error::Error,
     extras::{
+        config_reload::ConfigReloader,
         logging::{initialize_logging, log_error_cause_chain},
         metrics_server::NimiqTaskMonitor,
         panic::initialize_panic_reporting,
         signal_handling::initialize_signal_handler,
     },
 };
+use nimiq::{prover::prover_main, shutdown::ShutdownStage};
 
 async fn main_inner() -> Result<(), Error> {
     // Keep for potential future reactivation
@@ -40,7 +41,7 @@ async fn main_inner() -> Result<(), Error> {
     initialize_panic_reporting();
 
     // Initialize signal handler
-    initialize_signal_handler();
+    let signal_handler = initialize_signal_handler();
 
     // Early return in case of a proving process.
     if command_line.prove {
@@ -60,18 +61,35 @@ async fn main_inner() -> Result<(), Error> {
 
     // Clone config for RPC and metrics server
     let rpc_config = config.rpc_server.clone();
+    let webhooks_config = config.webhooks.clone();
     let metrics_config = config.metrics_server.clone();
     let metrics_enabled = metrics_config.is_some();
 
     // Create client from config.
     let mut client: Client = Client::from_config(config).await?;
 
+    // The config file can be reloaded via SIGHUP or RPC. This has to happen before the validator
+    // is taken out of the client, such that the mempool filter rules can be changed.
+    let config_reloader = ConfigReloader::new(&client, command_line, config_file.clone());
+
+    // From now on, signals shut the client down gracefully and reload the configuration.
+    signal_handler.set_shutdown(client.shutdown_handle());
+    signal_handler.set_config_reloader(config_reloader.clone());
+
+    // Start notifying webhooks
+    let webhooks = nimiq::extras::rpc_server::initialize_webhooks(&client, webhooks_config)?;
+
     // Initialize RPC server
     if let Some(rpc_config) = rpc_config {
-        use nimiq::extras::rpc_server::initialize_rpc_server;
-        let rpc_server = initialize_rpc_server(&client, rpc_config, client.wallet_store())
-            .expect("Failed to initialize RPC server");
-        tokio::spawn(async move { rpc_server.run().await });
+        use nimiq::extras::rpc_server::RpcServerFactory;
+        let rpc_server = RpcServerFactory::new(
+            &client,
+            client.wallet_store(),
+            Some(config_reloader.clone()),
+            Some(webhooks),
+        );
+        let rpc_config = config_reloader.watch_rpc_server(rpc_config);
+        client.spawn(ShutdownStage::Rpc, "rpc-server", rpc_server.run(rpc_config));
     }
 
     // Vector for task monitors (Tokio task metrics)
@@ -83,29 +101,31 @@ async fn main_inner() -> Result<(), Error> {
     if metrics_enabled {
         let con_metrics_monitor = tokio_metrics::TaskMonitor::new();
         let instr_con = con_metrics_monitor.instrument(consensus);
-        tokio::spawn(instr_con);
+        client.spawn(ShutdownStage::Consensus, "consensus", instr_con);
         nimiq_task_metric.push(NimiqTaskMonitor {
             name: "consensus".to_string(),
             monitor: con_metrics_monitor,
         });
     } else {
-        tokio::spawn(consensus);
+        client.spawn(ShutdownStage::Consensus, "consensus", consensus);
     }
     let consensus = client.consensus_proxy();
     let mempool = client.mempool();
 
     let zkp_component = client.take_zkp_component().unwrap();
-    tokio::spawn(zkp_component); //ITODO get metrics on this? ask JD
+    client.spawn(ShutdownStage::ZkpProver, "zkp-component", zkp_component); //ITODO get metrics on this? ask JD
 
     // Start validator
     let val_metric_monitor = tokio_metrics::TaskMonitor::new();
+    let mut tendermint_telemetry = None;
     if let Some(validator) = client.take_validator() {
         info!("Initializing validator {}", validator.validator_address());
 
         if metrics_enabled {
+            tendermint_telemetry = Some(validator.tendermint_telemetry());
             let mp_metrics_monitor = validator.get_mempool_monitor();
             let inst_validator = val_metric_monitor.instrument(validator);
-            tokio::spawn(inst_validator);
+            client.spawn(ShutdownStage::Validator, "validator", inst_validator);
             nimiq_task_metric.push(NimiqTaskMonitor {
                 name: "mempool".to_string(),
                 monitor: mp_metrics_monitor,
@@ -115,8 +135,19 @@ async fn main_inner() -> Result<(), Error> {
                 monitor: val_metric_monitor,
             });
         } else {
-            tokio::spawn(validator);
+            client.spawn(ShutdownStage::Validator, "validator", validator);
+        }
+    }
+
+    // Start watchtower
+    let mut watchtower_metrics = None;
+    if let Some(watchtower) = client.take_watchtower() {
+        info!("Initializing watchtower");
+
+        if metrics_enabled {
+            watchtower_metrics = Some(watchtower.metrics());
         }
+        client.spawn(ShutdownStage::Validator, "watchtower", watchtower);
     }
 
     // Start metrics server
@@ -127,7 +158,12 @@ async fn main_inner() -> Result<(), Error> {
             mempool,
             client.consensus_proxy(),
             client.network(),
+            watchtower_metrics,
+            tendermint_telemetry,
             &nimiq_task_metric,
+            client.environment(),
+            client.validator_proxy(),
+            metrics_config.health,
         )
     }
 
@@ -35,7 +35,9 @@ use crate::{
     messages::{
         AddressNotification, AddressSubscriptionOperation, AddressSubscriptionTopic,
         RequestBlocksProof, RequestSubscribeToAddress, RequestTransactionReceiptsByAddress,
-        RequestTransactionsProof, ResponseBlocksProof,
+        RequestTransactionReceiptsByAddressPage, RequestTransactionsProof, ResponseBlocksProof,
+        ResponseTransactionReceiptsByAddressPage, ResponseTransactionReceiptsPageError,
+        TransactionReceiptsCursor,
     },
     ConsensusEvent,
 };
@@ -140,6 +142,60 @@ impl<N: Network> ConsensusProxy<N> {
         Ok(receipts)
     }
 
+    /// Requests a page of transaction receipts of the given address, newest first. The page
+    /// starts after `start_at` and skips transactions in blocks after `until`. Peers are asked
+    /// one after the other until one of them answers the request.
+    pub async fn request_transaction_receipts_page(
+        &self,
+        address: Address,
+        min_peers: usize,
+        max: Option<u16>,
+        start_at: Option<TransactionReceiptsCursor>,
+        until: Option<u32>,
+    ) -> Result<
+        Result<ResponseTransactionReceiptsByAddressPage, ResponseTransactionReceiptsPageError>,
+        RequestError,
+    > {
+        let mut result = Err(RequestError::OutboundRequest(
+            OutboundRequestError::NoReceiver,
+        ));
+
+        for peer_id in self
+            .get_peers_for_service(Services::TRANSACTION_INDEX, min_peers)
+            .await?
+        {
+            let response = self
+                .network
+                .request::<RequestTransactionReceiptsByAddressPage>(
+                    RequestTransactionReceiptsByAddressPage {
+                        address: address.clone(),
+                        max,
+                        start_at: start_at.clone(),
+                        until,
+                    },
+                    peer_id,
+                )
+                .await;
+
+            match response {
+                Ok(Ok(page)) => return Ok(Ok(page)),
+                Ok(Err(error)) => {
+                    // The peer might not know the start transaction yet, try with the next one.
+                    log::debug!(peer_id = %peer_id, %error, "Peer couldn't provide txn receipts page");
+                    result = Ok(Err(error));
+                }
+                Err(error) => {
+                    log::error!(peer=%peer_id, err=%error,"There was an error requesting a transaction receipts page from peer");
+                    if result.is_err() {
+                        result = Err(error);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
     pub async fn request_transactions_by_address(
         &self,
         address: Address,
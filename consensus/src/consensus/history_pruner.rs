@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use parking_lot::RwLock;
+
+/// Prunes the history of epochs that are beyond the configured history retention of the blockchain.
+/// Pruning is done one epoch at a time, releasing the blockchain lock in between, and is resumed
+/// whenever the blockchain emits a new event. The task ends when the blockchain notifier is closed.
+pub(crate) async fn prune_history(blockchain: Arc<RwLock<Blockchain>>) {
+    let mut events = blockchain.read().notifier_as_stream();
+
+    loop {
+        let pruned_epoch = Blockchain::prune_history_step(&blockchain);
+        match pruned_epoch {
+            Some(epoch_number) => {
+                trace!(epoch_number, "Pruned history in the background");
+                tokio::task::yield_now().await;
+            }
+            None => {
+                // Nothing left to prune, wait until the chain advances.
+                if events.next().await.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -39,13 +39,16 @@ use crate::{
 use crate::{
     messages::{
         RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
-        RequestTransactionReceiptsByAddress, RequestTransactionsProof, RequestTrieProof,
+        RequestTransactionReceiptsByAddress, RequestTransactionReceiptsByAddressPage,
+        RequestTransactionsProof, RequestTrieProof,
     },
     sync::live::{diff_queue::RequestTrieDiff, state_queue::RequestChunk},
 };
 
 pub mod consensus_proxy;
 mod head_requests;
+#[cfg(feature = "full")]
+mod history_pruner;
 mod remote_data_store;
 #[cfg(feature = "full")]
 mod remote_event_dispatcher;
@@ -191,6 +194,9 @@ impl<N: Network> Consensus<N> {
         #[cfg(feature = "full")]
         Self::init_remote_event_dispatcher(&network, &blockchain);
 
+        #[cfg(feature = "full")]
+        Self::init_history_pruner(&blockchain);
+
         let established_flag = Arc::new(AtomicBool::new(false));
 
         Consensus {
@@ -226,6 +232,16 @@ impl<N: Network> Consensus<N> {
         }
     }
 
+    #[cfg(feature = "full")]
+    fn init_history_pruner(blockchain: &BlockchainProxy) {
+        // We spawn the history pruner into its own task if a history retention is configured.
+        if let BlockchainProxy::Full(blockchain) = blockchain {
+            if blockchain.read().history_retention_epochs().is_some() {
+                spawn(history_pruner::prune_history(Arc::clone(blockchain)));
+            }
+        }
+    }
+
     fn init_network_request_receivers(network: &Arc<N>, blockchain: &BlockchainProxy) {
         let stream = network.receive_requests::<RequestMacroChain>();
         spawn(Box::pin(request_handler(network, stream, blockchain)));
@@ -259,6 +275,9 @@ impl<N: Network> Consensus<N> {
                 let stream = network.receive_requests::<RequestTransactionReceiptsByAddress>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
 
+                let stream = network.receive_requests::<RequestTransactionReceiptsByAddressPage>();
+                spawn(Box::pin(request_handler(network, stream, blockchain)));
+
                 let stream = network.receive_requests::<RequestTrieProof>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
 
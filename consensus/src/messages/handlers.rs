@@ -690,6 +690,64 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTransactionReceip
     }
 }
 
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTransactionReceiptsByAddressPage {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseTransactionReceiptsByAddressPage, ResponseTransactionReceiptsPageError>
+    {
+        let blockchain = blockchain.read();
+        let max = self.max.unwrap_or(500).min(500);
+
+        // Get the transaction hashes for this address, starting after the given one.
+        let raw_tx_hashes = blockchain
+            .history_store
+            .get_indexed_tx_hashes_by_address(
+                &self.address,
+                max,
+                self.start_at
+                    .as_ref()
+                    .map(|start_at| (start_at.index, &start_at.hash)),
+                None,
+            )
+            .ok_or(ResponseTransactionReceiptsPageError::UnknownStartAt)?;
+
+        // If the page is full, there might be more transactions to continue with.
+        let next = if raw_tx_hashes.len() == max as usize {
+            raw_tx_hashes
+                .last()
+                .map(|(index, hash)| TransactionReceiptsCursor {
+                    index: *index,
+                    hash: hash.clone(),
+                })
+        } else {
+            None
+        };
+
+        let mut receipts = vec![];
+
+        for (_, hash) in raw_tx_hashes {
+            // Get all the historic transactions that correspond to this hash, skipping the ones
+            // after the requested block.
+            receipts.extend(
+                blockchain
+                    .history_store
+                    .get_hist_tx_by_hash(&hash, None)
+                    .iter()
+                    .filter(|hist_tx| {
+                        self.until
+                            .map_or(true, |until| hist_tx.block_number <= until)
+                    })
+                    .map(|hist_tx| (hist_tx.tx_hash().into(), hist_tx.block_number)),
+            );
+        }
+
+        Ok(ResponseTransactionReceiptsByAddressPage { receipts, next })
+    }
+}
+
 #[cfg(feature = "full")]
 impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTrieProof {
     fn handle(
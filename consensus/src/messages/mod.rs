@@ -418,6 +418,52 @@ pub struct ResponseTransactionReceiptsByAddress {
     pub receipts: Vec<(Blake2bHash, u32)>,
 }
 
+/// Position of a transaction in the list of transactions of an address, used to page through them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceiptsCursor {
+    /// The index of the transaction at the address.
+    pub index: u32,
+    /// The hash of the transaction.
+    pub hash: Blake2bHash,
+}
+
+/// Same as [`RequestTransactionReceiptsByAddress`], but returns the receipts page by page.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestTransactionReceiptsByAddressPage {
+    pub address: Address,
+    pub max: Option<u16>,
+    /// If set, only receipts of transactions older than this transaction are returned.
+    pub start_at: Option<TransactionReceiptsCursor>,
+    /// If set, receipts of transactions in blocks after this block number are skipped.
+    pub until: Option<u32>,
+}
+
+impl RequestCommon for RequestTransactionReceiptsByAddressPage {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 219;
+    type Response =
+        Result<ResponseTransactionReceiptsByAddressPage, ResponseTransactionReceiptsPageError>;
+    const MAX_REQUESTS: u32 = MAX_REQUEST_TRANSACTIONS_BY_ADDRESS;
+}
+
+/// Response to [`RequestTransactionReceiptsByAddressPage`].
+#[derive(Serialize, Deserialize)]
+pub struct ResponseTransactionReceiptsByAddressPage {
+    /// Tuples of `(transaction_hash, block_number)`
+    pub receipts: Vec<(Blake2bHash, u32)>,
+    /// The position to continue from to get the next page, `None` if this was the last page.
+    pub next: Option<TransactionReceiptsCursor>,
+}
+
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum ResponseTransactionReceiptsPageError {
+    #[error("unknown start transaction")]
+    UnknownStartAt,
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
 /// Request a proof for the values corresponding to some keys or their absence from the accounts trie.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestTrieProof {
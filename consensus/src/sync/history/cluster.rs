@@ -11,7 +11,7 @@ use std::{
 
 use futures::{FutureExt, Stream, StreamExt};
 use nimiq_block::{Block, MacroBlock};
-use nimiq_blockchain::{Blockchain, HistoryTreeChunk, CHUNK_SIZE};
+use nimiq_blockchain::{Blockchain, HistorySyncStore, HistoryTreeChunk, CHUNK_SIZE};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::Network, request::RequestError};
@@ -19,6 +19,7 @@ use nimiq_primitives::{networks::NetworkId, policy::Policy, slots_allocation::Va
 use nimiq_transaction::historic_transaction::HistoricTransaction;
 use parking_lot::RwLock;
 use thiserror::Error;
+use tokio::task::spawn_blocking;
 
 use crate::{
     messages::{
@@ -152,6 +153,9 @@ pub struct SyncCluster<TNetwork: Network> {
     pending_batch_sets: VecDeque<PendingBatchSet>,
     num_epochs_finished: usize,
 
+    /// Persists the downloaded history chunks, so that the download can be resumed after a restart.
+    history_sync_store: HistorySyncStore,
+
     blockchain: Arc<RwLock<Blockchain>>,
     network: Arc<TNetwork>,
 }
@@ -206,13 +210,14 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
     ) -> Self {
         let id = SYNC_CLUSTER_ID.fetch_add(1, Ordering::SeqCst);
 
-        let batch_verify_state = {
+        let (batch_verify_state, history_sync_store) = {
             let blockchain = blockchain.read();
-            BatchSetVerifyState {
+            let verify_state = BatchSetVerifyState {
                 network: blockchain.network_id,
                 predecessor: Block::Macro(blockchain.election_head()),
                 validators: blockchain.election_head().get_validators().unwrap(),
-            }
+            };
+            (verify_state, blockchain.history_sync_store.clone())
         };
         let epoch_ids_queue = epoch_ids
             .iter()
@@ -279,6 +284,7 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
             history_queue,
             pending_batch_sets: VecDeque::with_capacity(Self::NUM_PENDING_BATCH_SETS),
             num_epochs_finished: 0,
+            history_sync_store,
             blockchain,
             network,
         }
@@ -408,26 +414,33 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
             // Now compute how many history items we need to download
             let history_len = batch_set.history_len.size() - start_txn;
 
+            // Resume from the history chunks that were already downloaded and verified for this
+            // batch set, e.g. before the node was restarted.
+            let first_chunk_index = start_txn / CHUNK_SIZE as u64;
+            let num_chunks = batch_set.history_len.size().div_ceil(CHUNK_SIZE as u64);
+            let (next_chunk_index, history) =
+                self.resume_batch_set(&batch_set.macro_block, first_chunk_index, num_chunks);
+
             // Prepare pending info.
             let pending_batch_set = PendingBatchSet {
                 macro_block: batch_set.macro_block.clone(),
                 history_len,
                 batch_set_index: index,
-                history: Vec::new(),
+                history,
             };
 
             log::debug!(
                 epoch = %batch_set.macro_block.epoch_number(),
                 block = %batch_set.macro_block,
                 history_len,
+                resumed_history_len = pending_batch_set.history.len(),
                 batch_set_index = index,
                 "Adding pending batch set",
             );
 
-            // Queue history chunks for the given batch set for download.
-            let history_chunk_ids: Vec<(HistoryChunkRequest, Option<_>)> = (start_txn
-                / CHUNK_SIZE as u64
-                ..((batch_set.history_len.size()).div_ceil(CHUNK_SIZE as u64)))
+            // Queue the remaining history chunks for the given batch set for download.
+            let history_chunk_ids: Vec<(HistoryChunkRequest, Option<_>)> = (next_chunk_index
+                ..num_chunks)
                 .map(|i| {
                     (
                         HistoryChunkRequest::from_block(&batch_set.macro_block, i),
@@ -447,6 +460,59 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         Ok(())
     }
 
+    /// Loads the history chunks that were persisted for the given batch set, starting at
+    /// `first_chunk_index`. Returns the index of the first chunk that still needs to be downloaded
+    /// together with the history contained in the loaded chunks.
+    /// The persisted chunks are verified again against the history root of the macro block. If any
+    /// of them fails to verify (e.g. because it belongs to a different chain), the persisted
+    /// progress for this batch set is discarded.
+    fn resume_batch_set(
+        &self,
+        macro_block: &MacroBlock,
+        first_chunk_index: u64,
+        num_chunks: u64,
+    ) -> (u64, Vec<HistoricTransaction>) {
+        let block_number = macro_block.block_number();
+        let chunks = self
+            .history_sync_store
+            .get_chunks(block_number, first_chunk_index);
+        if chunks.is_empty() {
+            return (first_chunk_index, Vec::new());
+        }
+
+        let mut history = Vec::new();
+        let mut chunk_index = first_chunk_index;
+        for chunk in chunks {
+            let leaf_index = chunk_index as usize * CHUNK_SIZE;
+            if chunk_index >= num_chunks
+                || !chunk
+                    .verify(&macro_block.header.history_root, leaf_index)
+                    .unwrap_or(false)
+            {
+                log::warn!(
+                    block_number,
+                    chunk_index,
+                    "Persisted history chunk failed to verify, discarding history sync progress",
+                );
+                let history_sync_store = self.history_sync_store.clone();
+                spawn_blocking(move || history_sync_store.remove_chunks(block_number));
+                return (first_chunk_index, Vec::new());
+            }
+
+            history.extend(chunk.history);
+            chunk_index += 1;
+        }
+
+        log::info!(
+            "Resuming history download for epoch #{} at block #{}: {} history items already downloaded",
+            macro_block.epoch_number(),
+            block_number,
+            history.len(),
+        );
+
+        (chunk_index, history)
+    }
+
     fn on_history_chunk_received(
         &mut self,
         epoch_number: u32,
@@ -649,6 +715,13 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
     type Item = Result<BatchSet, SyncClusterResult>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Emit finished epochs first. Batch sets that were fully resumed from persisted history
+        // chunks are complete without any chunk being downloaded for them, so they are only
+        // emitted here once they reach the front of the queue.
+        if let Some(batch_set) = self.pop_complete_epoch() {
+            return Poll::Ready(Some(Ok(batch_set.into())));
+        }
+
         while self.pending_batch_sets.len() < Self::NUM_PENDING_BATCH_SETS {
             let result = match self.batch_set_queue.poll_next_unpin(cx) {
                 Poll::Ready(Some(result)) => result,
@@ -661,11 +734,8 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
                         return Poll::Ready(Some(Err(e)));
                     }
 
-                    // Immediately emit the next epoch if it is already complete. This can only
-                    // happen for empty epochs. Currently, only the first epoch can be empty
-                    // as there are no rewards distributed in that epoch. Therefore, it is
-                    // sufficient to check for this condition here as opposed to in every call
-                    // to poll_next().
+                    // Immediately emit the next epoch if it is already complete, e.g. because it
+                    // is empty or its history was resumed from persisted chunks.
                     if let Some(batch_set) = self.pop_complete_epoch() {
                         return Poll::Ready(Some(Ok(batch_set.into())));
                     }
@@ -681,22 +751,13 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
             }
         }
 
+        // Collect all history chunks that are ready, such that they can be persisted in a single
+        // transaction.
+        let mut history_chunks = vec![];
+        let mut history_error = None;
         while let Poll::Ready(Some(result)) = self.history_queue.poll_next_unpin(cx) {
             match result {
-                Ok((request, history_chunk)) => {
-                    if let Err(e) = self.on_history_chunk_received(
-                        request.epoch_number,
-                        request.block_number,
-                        history_chunk,
-                    ) {
-                        return Poll::Ready(Some(Err(e)));
-                    }
-
-                    // Emit finished epochs.
-                    if let Some(batch_set) = self.pop_complete_epoch() {
-                        return Poll::Ready(Some(Ok(batch_set.into())));
-                    }
-                }
+                Ok(history_chunk) => history_chunks.push(history_chunk),
                 Err(e) => {
                     log::debug!(
                         epoch_number = e.epoch_number,
@@ -704,11 +765,56 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
                         chunk_index = e.chunk_index,
                         "Polling the history queue resulted in an error"
                     );
-                    return Poll::Ready(Some(Err(SyncClusterResult::Error)));
-                } // TODO Error
+                    history_error = Some(SyncClusterResult::Error); // TODO Error
+                    break;
+                }
+            }
+        }
+
+        if !history_chunks.is_empty() {
+            // Persist the verified chunks, so that we can resume from them after a restart.
+            // The write transaction is committed on a blocking thread to not stall the executor.
+            // If it races with the removal of the chunks after their batch set has been pushed,
+            // stale chunks are removed with the next pushed macro block at the latest.
+            let history_sync_store = self.history_sync_store.clone();
+            let chunks: Vec<_> = history_chunks
+                .iter()
+                .map(|(request, history_chunk)| {
+                    (
+                        request.block_number,
+                        request.chunk_index,
+                        history_chunk.clone(),
+                    )
+                })
+                .collect();
+            spawn_blocking(move || {
+                history_sync_store.put_chunks(chunks.iter().map(
+                    |(block_number, chunk_index, history_chunk)| {
+                        (*block_number, *chunk_index, history_chunk)
+                    },
+                ))
+            });
+
+            for (request, history_chunk) in history_chunks {
+                if let Err(e) = self.on_history_chunk_received(
+                    request.epoch_number,
+                    request.block_number,
+                    history_chunk,
+                ) {
+                    return Poll::Ready(Some(Err(e)));
+                }
             }
         }
 
+        if let Some(e) = history_error {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        // Emit finished epochs.
+        if let Some(batch_set) = self.pop_complete_epoch() {
+            return Poll::Ready(Some(Ok(batch_set.into())));
+        }
+
         // We're done if there are no more epochs to process.
         if self.batch_set_queue.is_empty() && self.pending_batch_sets.is_empty() {
             return Poll::Ready(None);
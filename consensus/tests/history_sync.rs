@@ -1,7 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt;
-use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig, CHUNK_SIZE};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_bls::cache::PublicKeyCache;
@@ -12,6 +12,7 @@ use nimiq_consensus::{
             cluster::{HistoryChunkRequest, SyncCluster},
             HistoryMacroSync,
         },
+        syncer::MacroSyncReturn,
         syncer_proxy::SyncerProxy,
     },
 };
@@ -23,7 +24,7 @@ use nimiq_network_mock::MockHub;
 use nimiq_primitives::policy::Policy;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
-    blockchain::{produce_macro_blocks, signing_key, voting_key},
+    blockchain::{produce_macro_blocks, produce_macro_blocks_with_txns, signing_key, voting_key},
     node::TESTING_BLS_CACHE_MAX_CAPACITY,
     test_network::TestNetwork,
 };
@@ -96,6 +97,129 @@ async fn two_peers_can_sync_two_epochs() {
     .await
 }
 
+#[test(tokio::test)]
+async fn history_sync_resumes_persisted_batch_sets() {
+    let hub = MockHub::default();
+    let mut networks = vec![];
+
+    // Setup first peer with three epochs of history.
+    let blockchain1 = Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    produce_macro_blocks_with_txns(
+        &producer,
+        &blockchain1,
+        (Policy::batches_per_epoch() * 3) as usize,
+        1,
+        2,
+    );
+
+    let net1: Arc<Network> =
+        TestNetwork::build_network(30, Default::default(), &mut Some(hub)).await;
+    networks.push(Arc::clone(&net1));
+    let blockchain1_proxy = BlockchainProxy::from(&blockchain1);
+    let syncer1 = SyncerProxy::new_history(
+        blockchain1_proxy.clone(),
+        Arc::clone(&net1),
+        Arc::new(Mutex::new(PublicKeyCache::new(
+            TESTING_BLS_CACHE_MAX_CAPACITY,
+        ))),
+        net1.subscribe_events(),
+    )
+    .await;
+    let zkp_prover1 = ZKPComponent::new(blockchain1_proxy.clone(), Arc::clone(&net1), None).await;
+    let _consensus1 = Consensus::from_network(
+        blockchain1_proxy,
+        Arc::clone(&net1),
+        syncer1,
+        zkp_prover1.proxy(),
+    );
+
+    // Setup second peer, which has already downloaded the complete history of the second and
+    // third epoch before being restarted, but not that of the first epoch.
+    let blockchain2 = Arc::new(RwLock::new(
+        Blockchain::new(
+            VolatileDatabase::new(20).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let resumed_blocks: Vec<u32> = (2..=3)
+        .map(|epoch| Policy::election_block_of(epoch).unwrap())
+        .collect();
+    for &block_number in &resumed_blocks {
+        let blockchain1 = blockchain1.read();
+        let epoch_number = Policy::epoch_at(block_number);
+        let num_chunks = blockchain1
+            .history_store
+            .total_len_at_epoch(epoch_number, None)
+            .div_ceil(CHUNK_SIZE);
+        assert!(num_chunks > 0, "Epoch must not be empty");
+
+        let chunks: Vec<_> = (0..num_chunks)
+            .map(|chunk_index| {
+                blockchain1
+                    .history_store
+                    .prove_chunk(epoch_number, block_number, CHUNK_SIZE, chunk_index, None)
+                    .expect("Should prove history chunk")
+            })
+            .collect();
+        blockchain2.read().history_sync_store.put_chunks(
+            chunks
+                .iter()
+                .enumerate()
+                .map(|(chunk_index, chunk)| (block_number, chunk_index as u64, chunk)),
+        );
+    }
+
+    let net2: Arc<Network> =
+        TestNetwork::build_network(31, Default::default(), &mut Some(MockHub::default())).await;
+    networks.push(Arc::clone(&net2));
+    let blockchain2_proxy = BlockchainProxy::from(&blockchain2);
+    let syncer2 = SyncerProxy::new_history(
+        blockchain2_proxy,
+        Arc::clone(&net2),
+        Arc::new(Mutex::new(PublicKeyCache::new(
+            TESTING_BLS_CACHE_MAX_CAPACITY,
+        ))),
+        net2.subscribe_events(),
+    )
+    .await;
+
+    Network::connect_networks(&networks, 31).await;
+
+    let SyncerProxy::History(mut syncer2) = syncer2 else {
+        unreachable!();
+    };
+    assert_eq!(
+        syncer2.macro_sync.next().await,
+        Some(MacroSyncReturn::Good(net1.get_local_peer_id()))
+    );
+
+    // The second peer must have caught up with all three epochs and cleaned up the persisted
+    // history chunks.
+    assert_eq!(
+        blockchain2.read().election_head_hash(),
+        blockchain1.read().election_head_hash()
+    );
+    for block_number in resumed_blocks {
+        assert!(blockchain2
+            .read()
+            .history_sync_store
+            .get_chunks(block_number, 0)
+            .is_empty());
+    }
+}
+
 #[test(tokio::test)]
 async fn three_peers_can_sync() {
     // FIXME: Add more tests
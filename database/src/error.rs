@@ -4,6 +4,8 @@ use thiserror::Error;
 pub enum Error {
     #[error("Couldn't create directory: {0}")]
     CreateDirectory(#[from] std::io::Error),
+    #[error("Couldn't back up database: {0}")]
+    Backup(std::io::Error),
     #[error("Mdbx error: {0}")]
     Mdbx(#[from] libmdbx::Error),
 }
@@ -11,6 +11,9 @@ pub mod volatile;
 pub use error::*;
 pub use proxy::*;
 
+/// The maximum number of tables a client opens its database with by default.
+pub const DEFAULT_MAX_TABLES: u32 = 32;
+
 bitflags! {
     #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
     pub struct TableFlags: u32 {
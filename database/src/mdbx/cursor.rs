@@ -191,6 +191,17 @@ where
         ))
     }
 
+    fn seek_key_value<K, V>(&mut self, key: &K, value: &V) -> bool
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: AsDatabaseBytes + ?Sized,
+    {
+        let key = AsDatabaseBytes::as_database_bytes(key);
+        let value = AsDatabaseBytes::as_database_bytes(value);
+        let result: Option<Cow<[u8]>> = self.cursor.get_both(key.as_ref(), value.as_ref()).unwrap();
+        result.is_some()
+    }
+
     fn count_duplicates(&mut self) -> usize {
         let result: Option<DbKvPair> = self.cursor.get_current().unwrap();
 
@@ -1,6 +1,6 @@
-use std::{borrow::Cow, fs, path::Path, sync::Arc};
+use std::{borrow::Cow, fs, io, path::Path, sync::Arc};
 
-use libmdbx::NoWriteMap;
+use libmdbx::{NoWriteMap, WriteFlags};
 use log::info;
 
 use super::{MdbxReadTransaction, MdbxWriteTransaction};
@@ -8,11 +8,25 @@ use crate::{traits::Database, DatabaseProxy, Error, TableFlags};
 
 pub(super) type DbKvPair<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>);
 
+/// The name of the data file mdbx stores in the database directory.
+const DATA_FILE: &str = "mdbx.dat";
+
+/// The number of entries copied per write transaction when backing up the database.
+const BACKUP_BATCH_SIZE: usize = 10_000;
+
 /// Wrapper around the mdbx database handle.
 /// A database can hold multiple tables.
 #[derive(Clone, Debug)]
 pub struct MdbxDatabase {
     pub(super) db: Arc<libmdbx::Database<NoWriteMap>>,
+    read_only: bool,
+}
+
+/// The space used by a database and the size of its memory map, in bytes.
+#[derive(Clone, Copy, Debug)]
+pub struct DatabaseSize {
+    pub used: usize,
+    pub map_size: usize,
 }
 
 impl Database for MdbxDatabase {
@@ -29,6 +43,11 @@ impl Database for MdbxDatabase {
     }
 
     fn open_table_with_flags(&self, name: String, flags: TableFlags) -> Self::Table {
+        // A read-only database can't create tables, they have to exist already.
+        if self.read_only {
+            return MdbxTable { name };
+        }
+
         // This is an implicit transaction, so take the lock first.
         let mut table_flags = libmdbx::TableFlags::CREATE;
 
@@ -91,6 +110,27 @@ impl MdbxDatabase {
         )?))
     }
 
+    /// Opens an existing database without the ability to write to it, e.g. to read it while a
+    /// client is running on it. Its tables are not created if they don't exist.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new_read_only<P: AsRef<Path>>(path: P, max_tables: u32) -> Result<DatabaseProxy, Error> {
+        let path = path.as_ref();
+        let db = libmdbx::Database::open_with_options(
+            path,
+            libmdbx::DatabaseOptions {
+                max_tables: Some(max_tables.into()),
+                no_rdahead: true,
+                mode: libmdbx::Mode::ReadOnly,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(DatabaseProxy::Persistent(MdbxDatabase {
+            db: Arc::new(db),
+            read_only: true,
+        }))
+    }
+
     pub(crate) fn new_mdbx_database(
         path: &Path,
         size: usize,
@@ -119,7 +159,10 @@ impl MdbxDatabase {
         let cur_mapsize = info.map_size();
         info!(cur_mapsize, "MDBX memory map size");
 
-        let mdbx = MdbxDatabase { db: Arc::new(db) };
+        let mdbx = MdbxDatabase {
+            db: Arc::new(db),
+            read_only: false,
+        };
         if mdbx.need_resize(0) {
             info!("MDBX memory needs to be resized.");
         }
@@ -127,6 +170,17 @@ impl MdbxDatabase {
         Ok(mdbx)
     }
 
+    /// Returns the space used by the database and the size of its memory map.
+    pub fn size(&self) -> Result<DatabaseSize, Error> {
+        let info = self.db.info()?;
+        let stat = self.db.stat()?;
+
+        Ok(DatabaseSize {
+            used: (stat.page_size() as usize) * (info.last_pgno() + 1),
+            map_size: info.map_size(),
+        })
+    }
+
     pub fn need_resize(&self, threshold_size: usize) -> bool {
         let info = self.db.info().unwrap();
         let stat = self.db.stat().unwrap();
@@ -161,6 +215,91 @@ impl MdbxDatabase {
 
         false
     }
+
+    /// Flushes all committed transactions to disk.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.db.sync(true)?;
+        Ok(())
+    }
+
+    /// Copies the database into the given directory, which must not contain a database yet.
+    /// Returns the size of the backup's data file in bytes.
+    ///
+    /// The tables are copied from a single read transaction, so the backup is a consistent
+    /// snapshot and writers are not blocked while it is taken.
+    pub fn backup<P: AsRef<Path>>(&self, dir: P) -> Result<u64, Error> {
+        let dir = dir.as_ref();
+        let target = dir.join(DATA_FILE);
+        if target.exists() {
+            return Err(Error::Backup(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", target.display()),
+            )));
+        }
+        fs::create_dir_all(dir).map_err(Error::CreateDirectory)?;
+
+        let source = self.db.begin_ro_txn()?;
+
+        // The named tables are the keys of the main table.
+        let mut tables = Vec::new();
+        {
+            let main = source.open_table(None)?;
+            let mut cursor = source.cursor(&main)?;
+            let mut entry: Option<DbKvPair> = cursor.first()?;
+            while let Some((name, _)) = entry {
+                tables.push(String::from_utf8_lossy(&name).into_owned());
+                entry = cursor.next()?;
+            }
+        }
+
+        {
+            let backup = libmdbx::Database::<NoWriteMap>::open_with_options(
+                dir,
+                libmdbx::DatabaseOptions {
+                    max_tables: Some(tables.len().max(1).try_into().unwrap()),
+                    no_rdahead: true,
+                    mode: libmdbx::Mode::ReadWrite(libmdbx::ReadWriteOptions {
+                        sync_mode: libmdbx::SyncMode::Durable,
+                        min_size: Some(0),
+                        max_size: Some(self.db.info()?.map_size().try_into().unwrap()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )?;
+
+            for name in &tables {
+                let table = source.open_table(Some(name))?;
+                let flags = source.table_flags(&table)? | libmdbx::TableFlags::CREATE;
+                let mut cursor = source.cursor(&table)?;
+                let mut entry: Option<DbKvPair> = cursor.first()?;
+
+                // Copy in batches to keep the write transactions of the backup small.
+                loop {
+                    let txn = backup.begin_rw_txn()?;
+                    {
+                        let backup_table = txn.create_table(Some(name), flags)?;
+                        for _ in 0..BACKUP_BATCH_SIZE {
+                            let Some((key, value)) = entry else {
+                                break;
+                            };
+                            txn.put(&backup_table, key, value, WriteFlags::empty())?;
+                            entry = cursor.next()?;
+                        }
+                    }
+                    txn.commit()?;
+
+                    if entry.is_none() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let size = fs::metadata(&target).map_err(Error::Backup)?.len();
+        info!(path = %dir.display(), tables = tables.len(), size, "Backed up database");
+        Ok(size)
+    }
 }
 
 /// A table handle for the mdbx database.
@@ -234,4 +234,40 @@ mod tests {
         }
         tempdir.close().unwrap();
     }
+
+    #[test]
+    fn backup_test() {
+        let tempdir = tempdir().unwrap();
+        {
+            let db = MdbxDatabase::new(tempdir.path().join("test"), 0, 2).unwrap();
+            let table = db.open_table("test".to_string());
+            let dup_table = db.open_table_with_flags("dup".to_string(), TableFlags::DUPLICATE_KEYS);
+
+            let mut tx = db.write_transaction();
+            tx.put_reserve(&table, "test", "one");
+            tx.put::<str, u32>(&dup_table, "test", &1);
+            tx.put::<str, u32>(&dup_table, "test", &2);
+            tx.commit();
+
+            let backup_path = tempdir.path().join("backup");
+            assert!(db.backup(&backup_path).unwrap() > 0);
+            // An existing backup is not overwritten.
+            assert!(db.backup(&backup_path).is_err());
+
+            let backup = MdbxDatabase::new(&backup_path, 0, 2).unwrap();
+            let table = backup.open_table("test".to_string());
+            let dup_table =
+                backup.open_table_with_flags("dup".to_string(), TableFlags::DUPLICATE_KEYS);
+            let tx = backup.read_transaction();
+            assert_eq!(
+                tx.get::<str, String>(&table, "test"),
+                Some("one".to_string())
+            );
+            let mut cursor = tx.cursor(&dup_table);
+            assert_eq!(cursor.first::<String, u32>(), Some(("test".to_string(), 1)));
+            assert_eq!(cursor.next::<String, u32>(), Some(("test".to_string(), 2)));
+            assert!(cursor.next::<String, u32>().is_none());
+        }
+        tempdir.close().unwrap();
+    }
 }
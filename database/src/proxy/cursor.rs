@@ -160,6 +160,17 @@ impl<'txn> ReadCursor<'txn> for CursorProxy<'txn> {
         }
     }
 
+    fn seek_key_value<K, V>(&mut self, key: &K, value: &V) -> bool
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: AsDatabaseBytes + ?Sized,
+    {
+        match self {
+            CursorProxy::ReadCursor(cursor) => cursor.seek_key_value(key, value),
+            CursorProxy::WriteCursor(cursor) => cursor.seek_key_value(key, value),
+        }
+    }
+
     fn count_duplicates(&mut self) -> usize {
         match self {
             CursorProxy::ReadCursor(cursor) => cursor.count_duplicates(),
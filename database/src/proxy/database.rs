@@ -1,5 +1,5 @@
 use crate::{
-    mdbx, traits::Database, volatile, TableProxy, TransactionProxy, WriteTransactionProxy,
+    mdbx, traits::Database, volatile, Error, TableProxy, TransactionProxy, WriteTransactionProxy,
 };
 
 /// A database handle that can hold multiple tables.
@@ -52,3 +52,37 @@ impl Database for DatabaseProxy {
         }
     }
 }
+
+impl DatabaseProxy {
+    fn mdbx(&self) -> &mdbx::MdbxDatabase {
+        match self {
+            DatabaseProxy::Volatile(ref db) => &db.db,
+            DatabaseProxy::Persistent(ref db) => db,
+        }
+    }
+
+    /// Flushes all committed transactions to disk. This is a no-op for volatile databases.
+    pub fn flush(&self) -> Result<(), Error> {
+        match self {
+            DatabaseProxy::Volatile(_) => Ok(()),
+            DatabaseProxy::Persistent(ref db) => db.flush(),
+        }
+    }
+
+    /// Returns the space used by the database and the size of its memory map.
+    pub fn size(&self) -> Result<mdbx::DatabaseSize, Error> {
+        self.mdbx().size()
+    }
+
+    /// Returns whether the database is running out of space.
+    pub fn need_resize(&self, threshold_size: usize) -> bool {
+        self.mdbx().need_resize(threshold_size)
+    }
+
+    /// Copies the database into the given directory, see [`MdbxDatabase::backup`].
+    ///
+    /// [`MdbxDatabase::backup`]: mdbx::MdbxDatabase::backup
+    pub fn backup<P: AsRef<std::path::Path>>(&self, dir: P) -> Result<u64, Error> {
+        self.mdbx().backup(dir)
+    }
+}
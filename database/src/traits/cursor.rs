@@ -73,6 +73,13 @@ pub trait ReadCursor<'txn>: Clone {
         K: AsDatabaseBytes + FromDatabaseValue,
         V: FromDatabaseValue;
 
+    /// Places the cursor on the given key/value pair of a table with duplicate keys.
+    /// Returns whether the pair exists.
+    fn seek_key_value<K, V>(&mut self, key: &K, value: &V) -> bool
+    where
+        K: AsDatabaseBytes + ?Sized,
+        V: AsDatabaseBytes + ?Sized;
+
     fn count_duplicates(&mut self) -> usize;
 
     fn into_iter_start<K, V>(self) -> Self::IntoIter<K, V>
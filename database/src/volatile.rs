@@ -9,7 +9,7 @@ use crate::traits::Database;
 #[derive(Debug)]
 pub struct VolatileDatabase {
     temp_dir: Arc<TempDir>,
-    db: MdbxDatabase,
+    pub(crate) db: MdbxDatabase,
 }
 
 impl Clone for VolatileDatabase {
@@ -1,4 +1,10 @@
-use std::{fs, num::NonZeroU8, sync::Arc};
+use std::{
+    fs,
+    future::Future,
+    num::NonZeroU8,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use nimiq_block::Block;
 #[cfg(feature = "full-consensus")]
@@ -10,6 +16,8 @@ use nimiq_consensus::{
     sync::syncer_proxy::SyncerProxy, Consensus as AbstractConsensus,
     ConsensusProxy as AbstractConsensusProxy, Error::BlockchainError,
 };
+#[cfg(feature = "database-storage")]
+use nimiq_database::DatabaseProxy;
 #[cfg(feature = "zkp-prover")]
 use nimiq_genesis::NetworkId;
 use nimiq_genesis::NetworkInfo;
@@ -17,7 +25,7 @@ use nimiq_light_blockchain::LightBlockchain;
 #[cfg(feature = "validator")]
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::{
-    network::Network as NetworkInterface,
+    network::{CloseReason, Network as NetworkInterface},
     peer_info::{NodeType, Services},
     Multiaddr, Protocol,
 };
@@ -26,12 +34,17 @@ use nimiq_network_libp2p::{
     TlsConfig as NetworkTls,
 };
 use nimiq_primitives::policy::Policy;
-use nimiq_utils::time::OffsetTime;
+use nimiq_utils::{
+    shutdown::{Shutdown, ShutdownReason, ShutdownStage},
+    time::OffsetTime,
+};
 #[cfg(feature = "validator")]
 use nimiq_validator::validator::Validator as AbstractValidator;
 #[cfg(feature = "validator")]
 use nimiq_validator::validator::ValidatorProxy as AbstractValidatorProxy;
 #[cfg(feature = "validator")]
+use nimiq_validator::watchtower::Watchtower as AbstractWatchtower;
+#[cfg(feature = "validator")]
 use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
 #[cfg(feature = "wallet")]
 use nimiq_wallet::WalletStore;
@@ -53,7 +66,10 @@ use rand_chacha::ChaCha20Rng;
 use rustls_pemfile::Item;
 
 use crate::{
-    config::config::{ClientConfig, SyncMode},
+    config::{
+        config::{ClientConfig, SyncMode},
+        user_agent::UserAgent,
+    },
     error::Error,
 };
 
@@ -64,6 +80,8 @@ pub type ConsensusProxy = AbstractConsensusProxy<Network>;
 pub type Validator = AbstractValidator<ValidatorNetworkImpl<Network>>;
 #[cfg(feature = "validator")]
 pub type ValidatorProxy = AbstractValidatorProxy;
+#[cfg(feature = "validator")]
+pub type Watchtower = AbstractWatchtower<ValidatorNetworkImpl<Network>>;
 
 pub type ZKPComponent = AbstractZKPComponent<Network>;
 pub type ZKPComponentProxy = AbstractZKPComponentProxy<Network>;
@@ -93,6 +111,17 @@ pub(crate) struct ClientInner {
     wallet_store: Arc<WalletStore>,
 
     zkp_component: ZKPComponentProxy,
+
+    /// Coordinates the shutdown of the client's components.
+    shutdown: Shutdown,
+
+    #[cfg(feature = "database-storage")]
+    environment: DatabaseProxy,
+
+    user_agent: UserAgent,
+    sync_mode: SyncMode,
+    provided_services: Services,
+    started_at: Instant,
 }
 
 /// This function is used to generate the services flags (provided, needed) based upon the configured sync mode
@@ -206,6 +235,8 @@ impl ClientInner {
             identity_keypair.public().to_peer_id().to_base58()
         );
 
+        let user_agent = config.network.user_agent.clone();
+        let sync_mode = config.consensus.sync_mode;
         let (mut provided_services, required_services) =
             generate_service_flags(config.consensus.sync_mode);
 
@@ -215,6 +246,13 @@ impl ClientInner {
             provided_services |= Services::VALIDATOR;
         }
 
+        // A history node that prunes its history can't serve the full history, so it doesn't
+        // advertise the history services.
+        if sync_mode == SyncMode::History && config.consensus.history_retention_epochs.is_some() {
+            log::info!("History retention is configured, not providing the history services");
+            provided_services.remove(Services::HISTORY | Services::TRANSACTION_INDEX);
+        }
+
         // Generate my peer contact from identity keypair, our own addresses
         // (from the configured advertised addresses) and my provided services
         // Filter out unspecified IP addresses since those are not addresses suitable
@@ -331,6 +369,7 @@ impl ClientInner {
         #[cfg(feature = "full-consensus")]
         let mut blockchain_config = BlockchainConfig {
             max_epochs_stored: config.consensus.max_epochs_stored,
+            archive: config.consensus.archive,
             ..Default::default()
         };
 
@@ -352,6 +391,8 @@ impl ClientInner {
             #[cfg(feature = "full-consensus")]
             SyncMode::History => {
                 blockchain_config.keep_history = true;
+                blockchain_config.history_retention_epochs =
+                    config.consensus.history_retention_epochs;
                 let blockchain = match Blockchain::new(
                     environment.clone(),
                     blockchain_config,
@@ -521,6 +562,51 @@ impl ClientInner {
             None => (None, None),
         };
 
+        // Validators report equivocations themselves, so the watchtower is only started without one.
+        #[cfg(feature = "validator")]
+        let watchtower = match blockchain_proxy {
+            BlockchainProxy::Full(ref blockchain) if config.watchtower && validator.is_none() => {
+                let validator_network = Arc::new(ValidatorNetworkImpl::new(Arc::clone(&network)));
+                Some(Watchtower::new(
+                    environment.clone(),
+                    consensus.proxy(),
+                    Arc::clone(blockchain),
+                    validator_network,
+                ))
+            }
+            _ => None,
+        };
+
+        // Register the shutdown steps of the components that aren't tasks themselves.
+        let shutdown = Shutdown::default();
+        #[cfg(feature = "validator")]
+        if let Some(ref validator) = validator {
+            let mempool = Arc::clone(&validator.mempool);
+            let network = Arc::clone(&network);
+            shutdown.on_shutdown(ShutdownStage::Mempool, "mempool", move || async move {
+                mempool.stop_executors(network).await;
+            });
+        }
+        #[cfg(feature = "zkp-prover")]
+        {
+            let proof_finished = zkp_component.running_proof_finished();
+            shutdown.on_shutdown(ShutdownStage::ZkpProver, "zkp-prover", move || {
+                proof_finished
+            });
+        }
+        let network1 = Arc::clone(&network);
+        shutdown.on_shutdown(ShutdownStage::Network, "network", move || async move {
+            network1.disconnect(CloseReason::GoingOffline).await;
+        });
+        #[cfg(feature = "database-storage")]
+        let environment1 = environment.clone();
+        #[cfg(feature = "database-storage")]
+        shutdown.on_shutdown(ShutdownStage::Database, "database", move || async move {
+            if let Err(error) = environment1.flush() {
+                log::error!(%error, "Failed to flush the database");
+            }
+        });
+
         // Start network.
         network.listen_on(config.network.listen_addresses).await;
         network.start_connecting().await;
@@ -535,10 +621,19 @@ impl ClientInner {
                 #[cfg(feature = "wallet")]
                 wallet_store,
                 zkp_component: zkp_component.proxy(),
+                shutdown,
+                #[cfg(feature = "database-storage")]
+                environment,
+                user_agent,
+                sync_mode,
+                provided_services,
+                started_at: Instant::now(),
             }),
             consensus: Some(consensus),
             #[cfg(feature = "validator")]
             validator,
+            #[cfg(feature = "validator")]
+            watchtower,
             zkp_component: Some(zkp_component),
         })
     }
@@ -567,6 +662,8 @@ pub struct Client {
     consensus: Option<Consensus>,
     #[cfg(feature = "validator")]
     validator: Option<Validator>,
+    #[cfg(feature = "validator")]
+    watchtower: Option<Watchtower>,
     zkp_component: Option<ZKPComponent>,
 }
 
@@ -610,6 +707,12 @@ impl Client {
         self.validator.take()
     }
 
+    /// Returns the *Watchtower* or `None`.
+    #[cfg(feature = "validator")]
+    pub fn take_watchtower(&mut self) -> Option<Watchtower> {
+        self.watchtower.take()
+    }
+
     #[cfg(feature = "validator")]
     /// Returns a reference to the *Validator proxy*.
     pub fn validator_proxy(&self) -> Option<ValidatorProxy> {
@@ -632,4 +735,53 @@ impl Client {
     pub fn zkp_component(&self) -> ZKPComponentProxy {
         self.inner.zkp_component.clone()
     }
+
+    /// Returns the database of the client.
+    #[cfg(feature = "database-storage")]
+    pub fn environment(&self) -> DatabaseProxy {
+        self.inner.environment.clone()
+    }
+
+    /// Returns the user agent the client was configured with.
+    pub fn user_agent(&self) -> &UserAgent {
+        &self.inner.user_agent
+    }
+
+    /// Returns the sync mode the client was configured with.
+    pub fn sync_mode(&self) -> SyncMode {
+        self.inner.sync_mode
+    }
+
+    /// Returns the services the client provides to the network.
+    pub fn provided_services(&self) -> Services {
+        self.inner.provided_services
+    }
+
+    /// Returns the time since the client was created.
+    pub fn uptime(&self) -> Duration {
+        self.inner.started_at.elapsed()
+    }
+
+    /// Returns the handle coordinating the shutdown of the client. It can be used to request a
+    /// shutdown, e.g. from a signal handler, and to register further tasks.
+    pub fn shutdown_handle(&self) -> Shutdown {
+        self.inner.shutdown.clone()
+    }
+
+    /// Spawns a task of the client's component belonging to the given shutdown stage, e.g. the
+    /// consensus or validator future. The task is aborted when its stage is stopped.
+    pub fn spawn<F>(&self, stage: ShutdownStage, name: &str, future: F)
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.inner.shutdown.spawn(stage, name, future);
+    }
+
+    /// Shuts the client down. The RPC server, validator, consensus, mempool executors, network,
+    /// ZKP prover and database are stopped in that order, each stage given a timeout.
+    pub async fn shutdown(&self, reason: ShutdownReason) {
+        self.inner.shutdown.request(reason);
+        self.inner.shutdown.run().await;
+    }
 }
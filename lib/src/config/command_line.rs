@@ -7,7 +7,7 @@ use thiserror::Error;
 
 use crate::config::config_file::SyncMode;
 
-#[derive(Debug, Parser)]
+#[derive(Clone, Debug, Parser)]
 pub struct CommandLine {
     /// Use a custom configuration file.
     ///
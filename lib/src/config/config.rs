@@ -9,6 +9,8 @@ use std::{
     string::ToString,
 };
 
+#[cfg(feature = "rpc-server")]
+use clap::ValueEnum;
 use derive_builder::Builder;
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
@@ -22,6 +24,10 @@ use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
 use nimiq_network_interface::Multiaddr;
 use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
+#[cfg(feature = "rpc-server")]
+use nimiq_rpc_interface::types::LogType;
+#[cfg(feature = "rpc-server")]
+use nimiq_rpc_server::webhooks::WebhookFilter;
 use nimiq_serde::Deserialize;
 #[cfg(feature = "validator")]
 use nimiq_utils::key_rng::SecureGenerate;
@@ -36,6 +42,8 @@ use crate::config::config_file::DatabaseSettings;
 use crate::config::consts;
 #[cfg(feature = "metrics-server")]
 use crate::config::consts::default_bind;
+#[cfg(feature = "metrics-server")]
+use crate::extras::metrics_server::HealthThresholds;
 use crate::{
     config::{
         command_line::CommandLine,
@@ -84,6 +92,14 @@ pub struct ConsensusConfig {
     #[builder(default = "10800")]
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: u32,
+    #[builder(default)]
+    /// Whether the accounts state at every macro block should be archived.
+    /// This is only supported by full and history nodes.
+    pub archive: bool,
+    #[builder(default)]
+    /// Number of epochs (other than the current one) for which a history node retains the full
+    /// history. Older history is pruned in the background. If not set, the full history is kept.
+    pub history_retention_epochs: Option<u32>,
 }
 
 impl Default for ConsensusConfig {
@@ -93,6 +109,8 @@ impl Default for ConsensusConfig {
             min_peers: 3,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
             full_sync_threshold: 10800,
+            archive: false,
+            history_retention_epochs: None,
         }
     }
 }
@@ -256,8 +274,8 @@ pub struct DatabaseConfig {
     #[builder(default = "1024 * 1024 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 20
-    #[builder(default = "20")]
+    /// Max number of DBs. Recommended: 32
+    #[builder(default = "nimiq_database::DEFAULT_MAX_TABLES")]
     max_dbs: u32,
 
     /// Max number of threads that can open read transactions.
@@ -273,7 +291,7 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: nimiq_database::DEFAULT_MAX_TABLES,
             max_readers: 600,
         }
     }
@@ -562,6 +580,76 @@ pub struct RpcServerConfig {
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
+
+    /// If specified, serve the admin methods on a separate port with their own credentials.
+    /// Otherwise, the admin methods are not available.
+    #[builder(setter(strip_option))]
+    pub admin: Option<AdminRpcServerConfig>,
+
+    /// If specified, serve the REST gateway on this port, on the same IP address as the RPC
+    /// server.
+    #[builder(setter(strip_option))]
+    pub rest_port: Option<u16>,
+
+    /// If not empty, only these clients may use the RPC server, each only calling the methods of
+    /// its role. This can't be combined with `credentials`.
+    #[builder(default)]
+    pub access: Vec<RpcAccess>,
+}
+
+/// A client of the RPC server.
+#[cfg(feature = "rpc-server")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RpcAccess {
+    /// How the client authenticates itself.
+    pub secret: RpcSecret,
+
+    /// The name of the role of the client.
+    pub role: String,
+
+    /// The patterns of the methods the role allows.
+    pub methods: Vec<String>,
+
+    /// The maximum number of requests per minute, if limited.
+    pub rate_limit: Option<u32>,
+}
+
+/// How a client of the RPC server authenticates itself.
+#[cfg(feature = "rpc-server")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcSecret {
+    /// HTTP basic auth.
+    Credentials(Credentials),
+    /// A bearer token, given by its hash.
+    Token(Sensitive<Blake2bHash>),
+}
+
+/// The endpoint of the RPC server serving the admin methods.
+#[cfg(feature = "rpc-server")]
+#[derive(Clone, Debug)]
+pub struct AdminRpcServerConfig {
+    /// Bind the admin endpoint to this port, on the same IP address as the RPC server.
+    ///
+    /// Default: `8649`
+    ///
+    pub port: u16,
+
+    /// Require HTTP basic auth with these credentials.
+    pub credentials: Credentials,
+}
+
+/// A webhook set in the config file.
+#[cfg(feature = "rpc-server")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookConfig {
+    /// The URL the notifications are POSTed to.
+    pub url: String,
+
+    /// The key the notifications are signed with.
+    pub secret: Sensitive<String>,
+
+    /// What the webhook is notified of.
+    pub filter: WebhookFilter,
 }
 
 #[cfg(feature = "metrics-server")]
@@ -577,6 +665,10 @@ pub struct MetricsServerConfig {
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
+
+    /// Thresholds of the `/health` and `/ready` endpoints.
+    #[builder(default)]
+    pub health: HealthThresholds,
 }
 
 /// Client configuration
@@ -632,6 +724,12 @@ pub struct ClientConfig {
     #[builder(default)]
     pub validator: Option<ValidatorConfig>,
 
+    /// Whether to run a watchtower that reports equivocations without holding validator keys.
+    ///
+    #[cfg(feature = "validator")]
+    #[builder(default)]
+    pub watchtower: bool,
+
     /// The optional zkp configuration
     ///
     #[builder(default)]
@@ -643,6 +741,12 @@ pub struct ClientConfig {
     #[builder(default)]
     pub rpc_server: Option<RpcServerConfig>,
 
+    /// The webhooks set in the config file. More can be added via the admin RPC methods.
+    ///
+    #[cfg(feature = "rpc-server")]
+    #[builder(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
     #[cfg(feature = "metrics-server")]
     #[builder(default)]
     pub metrics_server: Option<MetricsServerConfig>,
@@ -756,6 +860,8 @@ impl ClientConfigBuilder {
         // Configure consensus
         let mut consensus = ConsensusConfigBuilder::default()
             .sync_mode(config_file.consensus.sync_mode)
+            .archive(config_file.consensus.archive)
+            .history_retention_epochs(config_file.consensus.history_retention_epochs)
             .build()
             .unwrap();
         if let Some(min_peers) = config_file.consensus.min_peers {
@@ -812,6 +918,12 @@ impl ClientConfigBuilder {
         }
         self.storage = Some(file_storage.into());
 
+        // Configure watchtower
+        #[cfg(feature = "validator")]
+        if let Some(watchtower_config) = config_file.watchtower.as_ref() {
+            self.watchtower(watchtower_config.enabled);
+        }
+
         // Configure database
         #[cfg(feature = "database-storage")]
         self.database(config_file.database.clone());
@@ -869,6 +981,65 @@ impl ClientConfigBuilder {
                     }
                 };
 
+                let admin =
+                    match (&rpc_config.admin_username, &rpc_config.admin_password) {
+                        (Some(u), Some(p)) => Some(AdminRpcServerConfig {
+                            port: rpc_config
+                                .admin_port
+                                .unwrap_or(consts::RPC_ADMIN_DEFAULT_PORT),
+                            credentials: Credentials::new(u, p),
+                        }),
+                        (None, None) => None,
+                        _ => return Err(Error::config_error(
+                            "RPC: Either both admin username and password have to be set or none.",
+                        )),
+                    };
+                if admin.is_some()
+                    && admin.as_ref().map(|admin| &admin.credentials) == credentials.as_ref()
+                {
+                    return Err(Error::config_error(
+                        "RPC: The admin credentials have to differ from the RPC credentials.",
+                    ));
+                }
+
+                let access = rpc_config
+                    .access
+                    .iter()
+                    .map(|access| {
+                        let role = rpc_config
+                            .roles
+                            .iter()
+                            .find(|role| role.name == access.role)
+                            .ok_or_else(|| {
+                                Error::config_error(format!("RPC: Unknown role {}", access.role))
+                            })?;
+                        let secret = match (&access.username, &access.password, &access.token) {
+                            (Some(u), Some(p), None) => {
+                                RpcSecret::Credentials(Credentials::new(u, p))
+                            }
+                            (None, None, Some(token)) => {
+                                RpcSecret::Token(Sensitive(token.0.as_str().hash()))
+                            }
+                            _ => {
+                                return Err(Error::config_error(
+                                    "RPC: Every access entry needs either a username and password or a token.",
+                                ))
+                            }
+                        };
+                        Ok(RpcAccess {
+                            secret,
+                            role: role.name.clone(),
+                            methods: role.methods.clone(),
+                            rate_limit: access.rate_limit,
+                        })
+                    })
+                    .collect::<Result<Vec<RpcAccess>, Error>>()?;
+                if !access.is_empty() && credentials.is_some() {
+                    return Err(Error::config_error(
+                        "RPC: Either username and password or access entries can be set, not both.",
+                    ));
+                }
+
                 self.rpc_server = Some(Some(RpcServerConfig {
                     bind_to,
                     port: rpc_config.port.unwrap_or(consts::RPC_DEFAULT_PORT),
@@ -876,8 +1047,46 @@ impl ClientConfigBuilder {
                     allow_ips,
                     allowed_methods: Some(rpc_config.methods.clone()),
                     credentials,
+                    admin,
+                    rest_port: rpc_config.rest_port,
+                    access,
                 }));
             }
+
+            let webhooks = config_file
+                .webhooks
+                .iter()
+                .map(|webhook| {
+                    let addresses = |addresses: &[String]| {
+                        addresses
+                            .iter()
+                            .map(|address| Address::from_any_str(address))
+                            .collect::<Result<Vec<Address>, _>>()
+                    };
+                    let log_types = webhook
+                        .log_types
+                        .iter()
+                        .map(|log_type| {
+                            <LogType as ValueEnum>::from_str(log_type, true).map_err(|_| {
+                                Error::config_error(format!(
+                                    "Webhooks: Unknown log type {log_type}"
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<LogType>, Error>>()?;
+                    Ok(WebhookConfig {
+                        url: webhook.url.clone(),
+                        secret: webhook.secret.clone(),
+                        filter: WebhookFilter {
+                            addresses: addresses(&webhook.addresses)?,
+                            log_types,
+                            validators: addresses(&webhook.validators)?,
+                            finalized: webhook.finalized,
+                        },
+                    })
+                })
+                .collect::<Result<Vec<WebhookConfig>, Error>>()?;
+            self.webhooks(webhooks);
         }
 
         // Configure metrics server
@@ -910,7 +1119,28 @@ impl ClientConfigBuilder {
                         )),
                     };
 
-                self.metrics_server = Some(Some(MetricsServerConfig { addr, credentials }));
+                let defaults = HealthThresholds::default();
+                let health = HealthThresholds {
+                    max_head_age_blocks: metrics_config
+                        .health
+                        .max_head_age_blocks
+                        .unwrap_or(defaults.max_head_age_blocks),
+                    min_peers: metrics_config
+                        .health
+                        .min_peers
+                        .or(config_file.consensus.min_peers)
+                        .unwrap_or(defaults.min_peers),
+                    min_db_free_space: metrics_config
+                        .health
+                        .min_db_free_space
+                        .unwrap_or(defaults.min_db_free_space),
+                };
+
+                self.metrics_server = Some(Some(MetricsServerConfig {
+                    addr,
+                    credentials,
+                    health,
+                }));
             }
         }
 
@@ -26,7 +26,7 @@ mod serialization;
 // TODO: We have to make more settings `Option`s, so that they can use the `ConfigBuilder`'s
 // default and don't overwrite a setting even though it's not set in the config file.
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 #[serde(deny_unknown_fields)]
 pub struct ConfigFile {
@@ -47,6 +47,10 @@ pub struct ConfigFile {
     pub mempool: Option<MempoolSettings>,
     #[serde(default)]
     pub validator: Option<ValidatorSettings>,
+    #[serde(default)]
+    pub watchtower: Option<WatchtowerSettings>,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookSettings>,
 }
 
 impl ConfigFile {
@@ -121,7 +125,7 @@ impl FromStr for ConfigFile {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct NetworkSettings {
     pub peer_key_file: Option<String>,
@@ -156,13 +160,13 @@ impl NetworkSettings {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 pub struct Seed {
     pub address: Multiaddr,
 }
 
 /// Settings for configuring TLS for secure WebSocket
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct TlsSettings {
     /// Path to a file containing the private key (PEM-encoded ASN.1 in either PKCS#8 or PKCS#1 format).
@@ -172,7 +176,7 @@ pub struct TlsSettings {
     pub certificates: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 /// Different knobs used to tweak the consensus mechanism and settings
 pub struct ConsensusSettings {
@@ -188,6 +192,12 @@ pub struct ConsensusSettings {
     pub min_peers: Option<usize>,
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: Option<u32>,
+    #[serde(default)]
+    /// Archive the accounts state at every macro block. Only supported for full and history nodes.
+    pub archive: bool,
+    /// Number of epochs (other than the current one) for which a history node retains the full
+    /// history. If not set, the full history is kept.
+    pub history_retention_epochs: Option<u32>,
 }
 
 #[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
@@ -230,7 +240,7 @@ impl From<SyncMode> for config::SyncMode {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct RpcServerSettings {
     #[serde(deserialize_with = "deserialize_string_option")]
@@ -245,9 +255,56 @@ pub struct RpcServerSettings {
     pub methods: Vec<String>,
     pub username: Option<String>,
     pub password: Option<Sensitive<String>>,
+    pub admin_port: Option<u16>,
+    pub admin_username: Option<String>,
+    pub admin_password: Option<Sensitive<String>>,
+    pub rest_port: Option<u16>,
+    #[serde(default)]
+    pub roles: Vec<RpcRoleSettings>,
+    #[serde(default)]
+    pub access: Vec<RpcAccessSettings>,
+}
+
+/// A named set of RPC methods, given as patterns in which `*` matches any sequence of characters.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RpcRoleSettings {
+    pub name: String,
+    pub methods: Vec<String>,
+}
+
+/// A client of the RPC server, authenticated either by username and password or by a bearer
+/// token.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RpcAccessSettings {
+    pub role: String,
+    pub username: Option<String>,
+    pub password: Option<Sensitive<String>>,
+    pub token: Option<Sensitive<String>>,
+    /// Requests per minute.
+    pub rate_limit: Option<u32>,
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+/// A URL that is notified of the logs of addresses, validator events or finalized blocks.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSettings {
+    pub url: String,
+    /// The key the notifications are signed with.
+    pub secret: Sensitive<String>,
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Only send logs of these types. All types are sent if empty.
+    #[serde(default)]
+    pub log_types: Vec<String>,
+    #[serde(default)]
+    pub validators: Vec<String>,
+    #[serde(default)]
+    pub finalized: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct MetricsServerSettings {
     #[serde(deserialize_with = "deserialize_string_option")]
@@ -256,9 +313,20 @@ pub struct MetricsServerSettings {
     pub port: Option<u16>,
     pub username: Option<String>,
     pub password: Option<Sensitive<String>>,
+    #[serde(default)]
+    pub health: MetricsHealthSettings,
+}
+
+/// Thresholds of the `/health` and `/ready` endpoints of the metrics server.
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsHealthSettings {
+    pub max_head_age_blocks: Option<u64>,
+    pub min_peers: Option<usize>,
+    pub min_db_free_space: Option<u64>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct LokiConfig {
     pub url: Url,
@@ -268,11 +336,33 @@ pub struct LokiConfig {
     pub extra_fields: HashMap<String, String>,
 }
 
+/// Export spans to an OpenTelemetry collector via OTLP over gRPC.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OtlpSettings {
+    pub endpoint: Url,
+    #[serde(default = "OtlpSettings::default_service_name")]
+    pub service_name: String,
+    /// The fraction of traces that are exported, from 0 to 1.
+    #[serde(default = "OtlpSettings::default_sample_ratio")]
+    pub sample_ratio: f64,
+}
+
+impl OtlpSettings {
+    pub fn default_service_name() -> String {
+        "nimiq-client".to_string()
+    }
+
+    pub fn default_sample_ratio() -> f64 {
+        1.0
+    }
+}
+
 const fn default_true() -> bool {
     true
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct LogSettings {
     #[serde(deserialize_with = "deserialize_string_option")]
@@ -290,6 +380,8 @@ pub struct LogSettings {
     #[serde(default)]
     pub loki: Option<LokiConfig>,
     #[serde(default)]
+    pub otlp: Option<OtlpSettings>,
+    #[serde(default)]
     pub tokio_console_bind_address: Option<String>,
 }
 
@@ -308,12 +400,13 @@ impl Default for LogSettings {
             statistics: 10,
             file: None,
             loki: None,
+            otlp: None,
             tokio_console_bind_address: None,
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct DatabaseSettings {
     pub path: Option<String>,
@@ -322,7 +415,7 @@ pub struct DatabaseSettings {
     pub max_readers: Option<u32>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct MempoolSettings {
     pub filter: Option<MempoolFilterSettings>,
@@ -331,7 +424,7 @@ pub struct MempoolSettings {
     pub blacklist_limit: Option<usize>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct MempoolFilterSettings {
     #[serde(deserialize_with = "deserialize_coin")]
@@ -406,7 +499,7 @@ impl From<MempoolFilterSettings> for MempoolRules {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ValidatorSettings {
     pub validator_address: String,
@@ -420,7 +513,14 @@ pub struct ValidatorSettings {
     pub automatic_reactivate: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WatchtowerSettings {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ZKPSettings {
     #[serde(default)]
@@ -9,6 +9,9 @@ pub const REVERSE_PROXY_DEFAULT_PORT: u16 = 8444;
 /// The default port for the RPC server
 pub const RPC_DEFAULT_PORT: u16 = 8648;
 
+/// The default port for the admin endpoint of the RPC server
+pub const RPC_ADMIN_DEFAULT_PORT: u16 = 8649;
+
 /// The default port for the metrics server
 pub const METRICS_DEFAULT_PORT: u16 = 9100;
 
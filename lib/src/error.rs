@@ -37,10 +37,18 @@ pub enum Error {
     #[error("Logger error: {0}")]
     Logging(#[from] tracing_subscriber::filter::FromEnvError),
 
+    #[cfg(feature = "logging")]
+    #[error("Failed to reload log filter: {0}")]
+    LoggingReload(#[from] tracing_subscriber::reload::Error),
+
     #[cfg(feature = "loki")]
     #[error("Loki logger error: {0}")]
     LoggingLoki(#[from] tracing_loki::Error),
 
+    #[cfg(feature = "otlp")]
+    #[error("OTLP trace exporter error: {0}")]
+    LoggingOtlp(#[from] opentelemetry::trace::TraceError),
+
     #[error("Failed to parse multiaddr: {0}")]
     Multiaddr(#[from] nimiq_network_libp2p::libp2p::core::multiaddr::Error),
 
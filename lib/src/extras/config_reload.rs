@@ -0,0 +1,283 @@
+use std::sync::Arc;
+
+#[cfg(feature = "validator")]
+use nimiq_mempool::{filter::MempoolRules, mempool::Mempool};
+use nimiq_network_libp2p::Network;
+#[cfg(feature = "rpc-server")]
+use parking_lot::Mutex;
+#[cfg(feature = "rpc-server")]
+use tokio::sync::watch;
+
+#[cfg(feature = "rpc-server")]
+use crate::config::config::RpcServerConfig;
+use crate::{
+    client::Client,
+    config::{command_line::CommandLine, config::ClientConfig, config_file::ConfigFile},
+    error::Error,
+};
+
+/// The outcome of a configuration reload. Settings are named by their section and key in the
+/// config file, e.g. `network.desired_peer_count`.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigReloadReport {
+    /// The settings that changed and are in effect now.
+    pub applied: Vec<String>,
+    /// The settings that changed but only take effect once the client is restarted.
+    pub requires_restart: Vec<String>,
+}
+
+impl ConfigReloadReport {
+    fn restart_if_changed<T: PartialEq>(&mut self, setting: &str, old: &T, new: &T) {
+        if old != new {
+            self.require_restart(setting);
+        }
+    }
+
+    fn require_restart(&mut self, setting: &str) {
+        if !self.requires_restart.iter().any(|s| s == setting) {
+            self.requires_restart.push(setting.to_string());
+        }
+    }
+}
+
+/// The changed settings that can be applied while the client is running.
+#[derive(Default)]
+struct HotChanges(Vec<&'static str>);
+
+impl HotChanges {
+    fn add_if_changed<T: PartialEq>(&mut self, setting: &'static str, old: &T, new: &T) {
+        if old != new {
+            self.0.push(setting);
+        }
+    }
+
+    fn in_section(&self, section: &str) -> Vec<&'static str> {
+        self.0
+            .iter()
+            .copied()
+            .filter(|setting| setting.split('.').next() == Some(section))
+            .collect()
+    }
+}
+
+struct ConfigReloaderInner {
+    command_line: CommandLine,
+    /// The config file the client was started with.
+    initial: ConfigFile,
+    /// The config file that was applied last. Also serializes reloads.
+    applied: tokio::sync::Mutex<ConfigFile>,
+    network: Arc<Network>,
+    #[cfg(feature = "validator")]
+    mempool: Option<Arc<Mempool>>,
+    #[cfg(feature = "rpc-server")]
+    rpc_server: Mutex<Option<watch::Sender<RpcServerConfig>>>,
+}
+
+/// Re-reads the config file and applies the settings that can be changed at runtime: the log
+/// level and tags, the mempool filter rules, the desired peer count and the IP addresses and
+/// methods the RPC server allows. Changes to all other settings are reported as requiring a
+/// restart.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    inner: Arc<ConfigReloaderInner>,
+}
+
+impl ConfigReloader {
+    /// Creates a reloader for the given client, which was configured from the given command line
+    /// and config file. It has to be created before the validator is taken out of the client,
+    /// otherwise the mempool filter rules can't be changed.
+    pub fn new(client: &Client, command_line: CommandLine, config_file: ConfigFile) -> Self {
+        Self {
+            inner: Arc::new(ConfigReloaderInner {
+                command_line,
+                initial: config_file.clone(),
+                applied: tokio::sync::Mutex::new(config_file),
+                network: client.network(),
+                #[cfg(feature = "validator")]
+                mempool: client.mempool(),
+                #[cfg(feature = "rpc-server")]
+                rpc_server: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Lets the reloader change the settings of the RPC server, which is started with the given
+    /// settings. The returned receiver yields the settings the RPC server has to be restarted
+    /// with.
+    #[cfg(feature = "rpc-server")]
+    pub fn watch_rpc_server(&self, config: RpcServerConfig) -> watch::Receiver<RpcServerConfig> {
+        let (tx, rx) = watch::channel(config);
+        *self.inner.rpc_server.lock() = Some(tx);
+        rx
+    }
+
+    /// Re-reads the config file and applies the changes that can be applied at runtime. If the
+    /// new configuration is invalid, nothing is applied. Changes that require a restart are
+    /// reported relative to the config file the client was started with, so they are reported
+    /// again on every reload until the client is restarted.
+    pub async fn reload(&self) -> Result<ConfigReloadReport, Error> {
+        let mut applied = self.inner.applied.lock().await;
+
+        let command_line = &self.inner.command_line;
+        let config_file = ConfigFile::find(Some(command_line))?;
+        let mut builder = ClientConfig::builder();
+        builder.config_file(&config_file)?;
+        builder.command_line(command_line)?;
+        let config = builder.build()?;
+
+        let (_, changes) = Self::compare(&applied, &config_file);
+        let (mut report, _) = Self::compare(&self.inner.initial, &config_file);
+        self.apply(&config_file, &config, changes, &mut report)
+            .await?;
+        *applied = config_file;
+
+        log::info!(
+            applied = ?report.applied,
+            requires_restart = ?report.requires_restart,
+            "Reloaded configuration"
+        );
+        Ok(report)
+    }
+
+    /// Compares the two config files. The hot-swappable settings are left out of the comparison
+    /// of the remaining settings of their section.
+    fn compare(old: &ConfigFile, new: &ConfigFile) -> (ConfigReloadReport, HotChanges) {
+        let mut report = ConfigReloadReport::default();
+        let mut changes = HotChanges::default();
+
+        changes.add_if_changed("log.level", &old.log.level, &new.log.level);
+        changes.add_if_changed("log.tags", &old.log.tags, &new.log.tags);
+        let mut log = new.log.clone();
+        log.level = old.log.level;
+        log.tags = old.log.tags.clone();
+        report.restart_if_changed("log", &old.log, &log);
+
+        changes.add_if_changed(
+            "network.desired_peer_count",
+            &old.network.desired_peer_count,
+            &new.network.desired_peer_count,
+        );
+        let mut network = new.network.clone();
+        network.desired_peer_count = old.network.desired_peer_count;
+        report.restart_if_changed("network", &old.network, &network);
+
+        match (&old.rpc_server, &new.rpc_server) {
+            (Some(old_rpc), Some(new_rpc)) => {
+                changes.add_if_changed("rpc-server.allowip", &old_rpc.allowip, &new_rpc.allowip);
+                changes.add_if_changed("rpc-server.methods", &old_rpc.methods, &new_rpc.methods);
+                let mut rpc = new_rpc.clone();
+                rpc.allowip = old_rpc.allowip.clone();
+                rpc.methods = old_rpc.methods.clone();
+                report.restart_if_changed("rpc-server", old_rpc, &rpc);
+            }
+            (old_rpc, new_rpc) => {
+                report.restart_if_changed("rpc-server", &old_rpc.is_some(), &new_rpc.is_some())
+            }
+        }
+
+        #[cfg(feature = "nimiq-mempool")]
+        {
+            let old_mempool = old.mempool.clone().unwrap_or_default();
+            let mut mempool = new.mempool.clone().unwrap_or_default();
+            changes.add_if_changed("mempool.filter", &old_mempool.filter, &mempool.filter);
+            mempool.filter = old_mempool.filter.clone();
+            report.restart_if_changed("mempool", &old_mempool, &mempool);
+        }
+
+        report.restart_if_changed("consensus", &old.consensus, &new.consensus);
+        report.restart_if_changed("zkp", &old.zkp, &new.zkp);
+        report.restart_if_changed("metrics-server", &old.metrics_server, &new.metrics_server);
+        report.restart_if_changed("prover-log", &old.prover_log, &new.prover_log);
+        report.restart_if_changed("database", &old.database, &new.database);
+        report.restart_if_changed("validator", &old.validator, &new.validator);
+        report.restart_if_changed("watchtower", &old.watchtower, &new.watchtower);
+        report.restart_if_changed("webhooks", &old.webhooks, &new.webhooks);
+
+        (report, changes)
+    }
+
+    async fn apply(
+        &self,
+        config_file: &ConfigFile,
+        config: &ClientConfig,
+        changes: HotChanges,
+        report: &mut ConfigReloadReport,
+    ) -> Result<(), Error> {
+        let log = changes.in_section("log");
+        if !log.is_empty() {
+            #[cfg(feature = "logging")]
+            {
+                crate::extras::logging::reload_log_filter(
+                    Some(&self.inner.command_line),
+                    Some(&config_file.log),
+                )?;
+                report.applied.extend(log.iter().map(ToString::to_string));
+            }
+            #[cfg(not(feature = "logging"))]
+            report.require_restart("log");
+        }
+
+        let mempool_changes = changes.in_section("mempool");
+        if !mempool_changes.is_empty() {
+            #[cfg(feature = "validator")]
+            if let Some(mempool) = &self.inner.mempool {
+                let rules = config_file
+                    .mempool
+                    .as_ref()
+                    .and_then(|mempool| mempool.filter.clone())
+                    .map(MempoolRules::from)
+                    .unwrap_or_default();
+                mempool.set_rules(rules);
+                report
+                    .applied
+                    .extend(mempool_changes.iter().map(ToString::to_string));
+            } else {
+                report.require_restart("mempool");
+            }
+            #[cfg(not(feature = "validator"))]
+            report.require_restart("mempool");
+        }
+
+        let network = changes.in_section("network");
+        if !network.is_empty() {
+            self.inner
+                .network
+                .set_desired_peer_count(config_file.network.desired_peer_count)
+                .await;
+            report
+                .applied
+                .extend(network.iter().map(ToString::to_string));
+        }
+
+        let rpc_server = changes.in_section("rpc-server");
+        if !rpc_server.is_empty() {
+            #[cfg(feature = "rpc-server")]
+            let applied = match (&*self.inner.rpc_server.lock(), &config.rpc_server) {
+                (Some(tx), Some(rpc_config)) => {
+                    // Only the allowed IPs and methods are applied, all other settings require a
+                    // restart.
+                    let mut new_config = tx.borrow().clone();
+                    new_config.allow_ips = rpc_config.allow_ips.clone();
+                    new_config.allowed_methods = rpc_config.allowed_methods.clone();
+                    tx.send(new_config).is_ok()
+                }
+                _ => false,
+            };
+            #[cfg(not(feature = "rpc-server"))]
+            let applied = {
+                let _ = config;
+                false
+            };
+
+            if applied {
+                report
+                    .applied
+                    .extend(rpc_server.iter().map(ToString::to_string));
+            } else {
+                report.require_restart("rpc-server");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -4,15 +4,18 @@ use std::{
     env,
     fs::{self, File},
     io,
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 
 use log::{level_filters::LevelFilter, Level, Subscriber};
 use nimiq_log::{Formatting, MaybeSystemTime, TargetsExt};
 use tracing_subscriber::{
-    filter::Targets, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, Layer,
+    filter::Targets, layer::SubscriberExt, registry::LookupSpan, reload, util::SubscriberInitExt,
+    Layer,
 };
 
+#[cfg(feature = "otlp")]
+use crate::config::config_file::OtlpSettings;
 use crate::{
     config::{command_line::CommandLine, config_file::LogSettings},
     error::Error,
@@ -74,10 +77,16 @@ impl io::Write for LoggingTarget {
     }
 }
 
-pub fn initialize_logging(
+/// Replaces the filter of the formatting layer installed by [`initialize_logging`].
+type ReloadFilter = Box<dyn Fn(Targets) -> Result<(), reload::Error> + Send + Sync>;
+
+static RELOAD_FILTER: OnceLock<ReloadFilter> = OnceLock::new();
+
+/// Returns the log settings from the config file, overridden by the command line.
+fn log_settings(
     command_line_opt: Option<&CommandLine>,
     settings_opt: Option<&LogSettings>,
-) -> Result<(), Error> {
+) -> LogSettings {
     // Get config from config file
     let mut settings = settings_opt.cloned().unwrap_or_default();
 
@@ -91,6 +100,10 @@ pub fn initialize_logging(
         }
     }
 
+    settings
+}
+
+fn log_filter(settings: &LogSettings) -> Targets {
     // Set logging level for Nimiq and all other modules
     // Creating ZKPs with a log level below WARN will consume huge amounts of memory due to tracing annotations in the dependency.
     // That's why we specifically set its log level to WARN.
@@ -99,9 +112,31 @@ pub fn initialize_logging(
         .with_nimiq_targets(settings.level.unwrap_or(DEFAULT_LEVEL))
         .with_target("r1cs", LevelFilter::WARN);
     // Set logging level for specific selected modules
-    filter = filter.with_targets(settings.tags);
+    filter = filter.with_targets(settings.tags.clone());
     // Set logging level from the environment
-    filter = filter.with_env();
+    filter.with_env()
+}
+
+/// Applies the log level and tags of the given settings to the logger set up by
+/// [`initialize_logging`]. All other log settings only take effect on restart.
+pub fn reload_log_filter(
+    command_line_opt: Option<&CommandLine>,
+    settings_opt: Option<&LogSettings>,
+) -> Result<(), Error> {
+    let reload_filter = RELOAD_FILTER
+        .get()
+        .ok_or_else(|| Error::config_error("Logging has not been initialized"))?;
+    let settings = log_settings(command_line_opt, settings_opt);
+    reload_filter(log_filter(&settings))?;
+    Ok(())
+}
+
+pub fn initialize_logging(
+    command_line_opt: Option<&CommandLine>,
+    settings_opt: Option<&LogSettings>,
+) -> Result<(), Error> {
+    let settings = log_settings(command_line_opt, settings_opt);
+    let (filter, reload_handle) = reload::Layer::new(log_filter(&settings));
 
     let file = match &settings.file {
         Some(filename) => {
@@ -167,6 +202,49 @@ pub fn initialize_logging(
         None
     }
 
+    #[cfg(feature = "otlp")]
+    fn initialize_otlp<S>(otlp: &OtlpSettings) -> Result<Box<dyn Layer<S> + Send + Sync>, Error>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        use opentelemetry::KeyValue;
+        use opentelemetry_otlp::WithExportConfig;
+        use opentelemetry_sdk::{
+            runtime,
+            trace::{self, Sampler},
+            Resource,
+        };
+
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(otlp.endpoint.as_str()),
+            )
+            .with_trace_config(
+                trace::config()
+                    .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                        otlp.sample_ratio,
+                    ))))
+                    .with_resource(Resource::new([KeyValue::new(
+                        "service.name",
+                        otlp.service_name.clone(),
+                    )])),
+            )
+            .install_batch(runtime::Tokio)?;
+
+        Ok(Box::new(otlp_layer(tracer)))
+    }
+    #[cfg(feature = "otlp")]
+    let otlp_layer = settings.otlp.as_ref().map(initialize_otlp).transpose()?;
+    #[cfg(not(feature = "otlp"))]
+    if settings.otlp.is_some() {
+        eprintln!(
+            "Cannot export traces via OTLP, the client was built without the `otlp` feature."
+        );
+    }
+
     let tokio_console_bind_address =
         settings
             .tokio_console_bind_address
@@ -191,9 +269,16 @@ pub fn initialize_logging(
         .event_format(Formatting(MaybeSystemTime(settings.timestamps)))
         .with_filter(filter);
 
+    let _ = RELOAD_FILTER.set(Box::new(move |filter| reload_handle.reload(filter)));
+
+    #[cfg(feature = "otlp")]
+    let registry = tracing_subscriber::registry().with(otlp_layer);
+    #[cfg(not(feature = "otlp"))]
+    let registry = tracing_subscriber::registry();
+
     #[cfg(feature = "loki")]
     {
-        tracing_subscriber::registry()
+        registry
             .with(loki_layer)
             .with(tokio_console_layer)
             .with(formatting_layer)
@@ -204,9 +289,33 @@ pub fn initialize_logging(
         }
     }
     #[cfg(not(feature = "loki"))]
-    tracing_subscriber::registry()
+    registry
         .with(tokio_console_layer)
         .with(formatting_layer)
         .init();
     Ok(())
 }
+
+/// Creates the layer that exports spans with the given tracer.
+#[cfg(feature = "otlp")]
+pub fn otlp_layer<S>(tracer: opentelemetry_sdk::trace::Tracer) -> impl Layer<S> + Send + Sync
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(
+            // Spans of the key paths of the node are created on the debug level.
+            Targets::new()
+                .with_default(DEFAULT_LEVEL)
+                .with_nimiq_targets(LevelFilter::DEBUG)
+                .with_target("r1cs", LevelFilter::WARN),
+        )
+}
+
+/// Exports the spans that have not been exported yet. Spans created afterwards are not exported.
+#[cfg(feature = "otlp")]
+pub async fn shutdown_tracing() {
+    // Flushing blocks until the exporter is done, which needs the runtime.
+    let _ = tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider).await;
+}
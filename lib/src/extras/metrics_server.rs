@@ -2,10 +2,15 @@ use std::{net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+use nimiq_database::DatabaseProxy;
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::mempool::Mempool;
-pub use nimiq_metrics_server::NimiqTaskMonitor;
+pub use nimiq_metrics_server::{HealthThresholds, NimiqTaskMonitor};
 use nimiq_network_interface::network::Network;
+use nimiq_validator::{
+    telemetry::TendermintTelemetry, validator::ValidatorProxy,
+    watchtower_metrics::WatchtowerMetrics,
+};
 
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
@@ -13,7 +18,12 @@ pub fn start_metrics_server<TNetwork: Network>(
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    watchtower_metrics: Option<Arc<WatchtowerMetrics>>,
+    tendermint_telemetry: Option<Arc<TendermintTelemetry>>,
     task_monitors: &[NimiqTaskMonitor],
+    database: DatabaseProxy,
+    validator_proxy: Option<ValidatorProxy>,
+    health_thresholds: HealthThresholds,
 ) {
     #[cfg(not(feature = "nimiq-mempool"))]
     let mempool = None;
@@ -23,6 +33,11 @@ pub fn start_metrics_server<TNetwork: Network>(
         mempool,
         consensus_proxy,
         network,
+        watchtower_metrics,
+        tendermint_telemetry,
         task_monitors,
+        database,
+        validator_proxy,
+        health_thresholds,
     );
 }
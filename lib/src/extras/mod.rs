@@ -1,3 +1,5 @@
+#[cfg(feature = "config-reload")]
+pub mod config_reload;
 #[cfg(feature = "deadlock")]
 pub mod deadlock;
 #[cfg(feature = "launcher")]
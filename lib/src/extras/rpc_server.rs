@@ -1,71 +1,415 @@
-use std::{collections::HashSet, iter::FromIterator, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    future::{self, Future},
+    iter::FromIterator,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use nimiq_jsonrpc_server::{
     AllowListDispatcher, Config, Credentials, ModularDispatcher, Server as _Server,
 };
-use nimiq_rpc_server::dispatchers::*;
+use nimiq_rpc_interface::types::ConfigReloadReport;
+use nimiq_rpc_server::{
+    authorization::{Authorization, Grant, MethodPattern, RateLimit, Role, Secret},
+    authorizing_server::AuthorizingServer,
+    dispatchers::*,
+    rest::RestServer,
+    wallets::UnlockedWallets,
+    webhooks::Webhooks,
+};
 use nimiq_wallet::WalletStore;
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::watch;
 
-#[cfg(feature = "rpc-server")]
-use crate::config::config::RpcServerConfig;
-use crate::{client::Client, config::consts::default_bind, error::Error};
+#[cfg(feature = "logging")]
+use crate::config::config_file::LogSettings;
+use crate::{
+    client::Client,
+    config::{
+        config::{
+            Credentials as ConfigCredentials, RpcAccess, RpcSecret, RpcServerConfig, WebhookConfig,
+        },
+        consts::default_bind,
+    },
+    error::Error,
+    extras::config_reload::ConfigReloader,
+    shutdown::ShutdownStage,
+};
 
 pub type Server = _Server<AllowListDispatcher<ModularDispatcher>>;
 
-#[cfg(feature = "rpc-server")]
-pub fn initialize_rpc_server(
-    client: &Client,
-    config: RpcServerConfig,
-    wallet_store: Arc<WalletStore>,
-) -> Result<Server, Error> {
-    let ip = config.bind_to.unwrap_or_else(default_bind);
-    log::info!("Initializing RPC server: {}:{}", ip, config.port);
-
-    // Configure RPC server
-    let basic_auth = config.credentials.map(|credentials| {
-        Credentials::new_from_blake2b(credentials.username, credentials.password_hash.0 .0)
-    });
-
-    let allowed_methods = config.allowed_methods.unwrap_or_default();
-    let allowed_methods = if allowed_methods.is_empty() {
-        None
-    } else {
-        Some(HashSet::from_iter(allowed_methods))
-    };
-
-    // TODO: Pass this to the rpc server config
-    let _corsdomain = config.corsdomain.unwrap_or_default();
-
-    let mut dispatcher = ModularDispatcher::default();
-
-    let wallet_dispatcher = WalletDispatcher::new(wallet_store);
-    let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
-
-    dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
-
-    dispatcher.add(ConsensusDispatcher::new(
-        client.consensus_proxy(),
-        Some(unlocked_wallets),
-    ));
-    dispatcher.add(NetworkDispatcher::new(client.network()));
-    if let Some(mempool) = client.mempool() {
-        dispatcher.add(MempoolDispatcher::new(mempool));
+/// An RPC server. If clients with roles are configured, it only lets each client call the methods
+/// of its role.
+pub enum RpcServer {
+    Basic(Server),
+    Authorizing(AuthorizingServer<AllowListDispatcher<ModularDispatcher>>),
+}
+
+impl RpcServer {
+    pub async fn run(&self) {
+        match self {
+            RpcServer::Basic(server) => server.run().await,
+            RpcServer::Authorizing(server) => server.run().await,
+        }
+    }
+}
+
+/// Creates the RPC server of a client. The server can be created again with other settings, in
+/// which case the wallets unlocked through the previous server stay unlocked and the rate limits of
+/// its clients carry over.
+pub struct RpcServerFactory {
+    create_dispatcher: Box<dyn Fn(Arc<RwLock<UnlockedWallets>>) -> ModularDispatcher + Send>,
+    create_admin_dispatcher: Box<dyn Fn() -> ModularDispatcher + Send>,
+    create_rest_dispatchers:
+        Box<dyn Fn() -> (BlockchainDispatcher, Option<MempoolDispatcher>) + Send>,
+    /// The wallets unlocked through a server without clients with roles.
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    /// The clients of the last server created, whose state is kept by the next one.
+    clients: Mutex<Authorization>,
+}
+
+impl RpcServerFactory {
+    pub fn new(
+        client: &Client,
+        wallet_store: Arc<WalletStore>,
+        config_reloader: Option<ConfigReloader>,
+        webhooks: Option<Webhooks>,
+    ) -> Self {
+        let blockchain = client.blockchain();
+        let consensus = client.consensus_proxy();
+        let network = client.network();
+        let mempool = client.mempool();
+        let validator_proxy = client.validator_proxy();
+        let zkp_component = client.zkp_component();
+
+        let rest_blockchain = blockchain.clone();
+        let rest_mempool = mempool.clone();
+        let create_rest_dispatchers = move || {
+            (
+                BlockchainDispatcher::new(rest_blockchain.clone()),
+                rest_mempool
+                    .as_ref()
+                    .map(|mempool| MempoolDispatcher::new(Arc::clone(mempool))),
+            )
+        };
+
+        let create_dispatcher = move |unlocked_wallets: Arc<RwLock<UnlockedWallets>>| {
+            let mut dispatcher = ModularDispatcher::default();
+
+            dispatcher.add(BlockchainDispatcher::new(blockchain.clone()));
+
+            dispatcher.add(ConsensusDispatcher::new(
+                consensus.clone(),
+                Some(Arc::clone(&unlocked_wallets)),
+            ));
+            dispatcher.add(NetworkDispatcher::new(Arc::clone(&network)));
+            if let Some(mempool) = &mempool {
+                dispatcher.add(MempoolDispatcher::new(Arc::clone(mempool)));
+            }
+            dispatcher.add(PolicyDispatcher {});
+            if let Some(validator_proxy) = &validator_proxy {
+                dispatcher.add(ValidatorDispatcher::new(validator_proxy.clone()));
+            }
+            dispatcher.add(WalletDispatcher::with_unlocked_wallets(
+                Arc::clone(&wallet_store),
+                unlocked_wallets,
+            ));
+
+            dispatcher.add(ZKPComponentDispatcher::new(zkp_component.clone()));
+
+            dispatcher
+        };
+
+        let node = NodeDescription {
+            version: option_env!("CARGO_PKG_VERSION")
+                .unwrap_or("unknown")
+                .to_string(),
+            user_agent: client.user_agent().to_string(),
+            sync_mode: client.sync_mode().to_string(),
+            services: client.provided_services(),
+            started_at: Instant::now() - client.uptime(),
+        };
+        let network = client.network();
+        let database = Some(client.environment());
+        let shutdown = client.shutdown_handle();
+        let hooks = AdminHooks {
+            reload_config: config_reloader.map(reload_config),
+            set_log_level: set_log_level(),
+        };
+
+        let create_admin_dispatcher = move || {
+            let mut dispatcher = ModularDispatcher::default();
+            dispatcher.add(AdminDispatcher::new(
+                node.clone(),
+                Arc::clone(&network),
+                database.clone(),
+                shutdown.clone(),
+                hooks.clone(),
+                webhooks.clone(),
+            ));
+            dispatcher
+        };
+
+        Self {
+            create_dispatcher: Box::new(create_dispatcher),
+            create_admin_dispatcher: Box::new(create_admin_dispatcher),
+            create_rest_dispatchers: Box::new(create_rest_dispatchers),
+            unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+            clients: Mutex::new(Authorization::default()),
+        }
+    }
+
+    /// Creates the authorization of the given clients. Clients of the previously created server
+    /// keep their unlocked wallets and rate limits.
+    fn client_authorization(&self, access: Vec<RpcAccess>) -> Authorization {
+        let mut authorization = authorization(access);
+        let mut clients = self.clients.lock();
+        authorization.inherit(&clients);
+        *clients = authorization.clone();
+        authorization
+    }
+
+    /// Creates an RPC server with the given settings.
+    pub fn create(&self, config: RpcServerConfig) -> RpcServer {
+        let ip = config.bind_to.unwrap_or_else(default_bind);
+        log::info!("Initializing RPC server: {}:{}", ip, config.port);
+
+        let allowed_methods = config.allowed_methods.unwrap_or_default();
+        let allowed_methods = if allowed_methods.is_empty() {
+            None
+        } else {
+            Some(HashSet::from_iter(allowed_methods))
+        };
+
+        // TODO: Pass this to the rpc server config
+        let _corsdomain = config.corsdomain.unwrap_or_default();
+
+        if !config.access.is_empty() {
+            // Every client gets its own dispatcher, so it can only use the wallets it unlocked.
+            return RpcServer::Authorizing(AuthorizingServer::new(
+                (ip, config.port).into(),
+                config.allow_ips.map(|ips| ips.into_iter().collect()),
+                self.client_authorization(config.access),
+                |grant| {
+                    AllowListDispatcher::new(
+                        (self.create_dispatcher)(grant.unlocked_wallets()),
+                        allowed_methods.clone(),
+                    )
+                },
+            ));
+        }
+
+        let dispatcher = AllowListDispatcher::new(
+            (self.create_dispatcher)(Arc::clone(&self.unlocked_wallets)),
+            allowed_methods,
+        );
+
+        RpcServer::Basic(Server::new(
+            Config {
+                bind_to: (ip, config.port).into(),
+                enable_websocket: false,
+                ip_whitelist: config.allow_ips.map(|ips| ips.into_iter().collect()),
+                basic_auth: config.credentials.map(basic_auth),
+            },
+            dispatcher,
+        ))
     }
-    dispatcher.add(PolicyDispatcher {});
-    if let Some(validator_proxy) = client.validator_proxy() {
-        dispatcher.add(ValidatorDispatcher::new(validator_proxy));
+
+    /// Creates the server for the admin endpoint, if it is configured. It is bound to the same IP
+    /// address as the RPC server and only serves the admin methods, which are always allowed.
+    pub fn create_admin(&self, config: &RpcServerConfig) -> Option<Server> {
+        let admin = config.admin.clone()?;
+        let ip = config.bind_to.unwrap_or_else(default_bind);
+        log::info!("Initializing RPC admin endpoint: {}:{}", ip, admin.port);
+
+        Some(Server::new(
+            Config {
+                bind_to: (ip, admin.port).into(),
+                enable_websocket: false,
+                ip_whitelist: config
+                    .allow_ips
+                    .clone()
+                    .map(|ips| ips.into_iter().collect()),
+                basic_auth: Some(basic_auth(admin.credentials)),
+            },
+            AllowListDispatcher::new((self.create_admin_dispatcher)(), None),
+        ))
     }
-    dispatcher.add(wallet_dispatcher);
 
-    dispatcher.add(ZKPComponentDispatcher::new(client.zkp_component()));
+    /// Creates the REST gateway, if it is configured. It is bound to the same IP address as the
+    /// RPC server and requires the same credentials, allowing the same methods.
+    pub fn create_rest(&self, config: &RpcServerConfig) -> Option<RestServer> {
+        let port = config.rest_port?;
+        let ip = config.bind_to.unwrap_or_else(default_bind);
+
+        let authorization = if !config.access.is_empty() {
+            Some(self.client_authorization(config.access.clone()))
+        } else {
+            config.credentials.clone().map(|credentials| {
+                self.client_authorization(vec![RpcAccess {
+                    secret: RpcSecret::Credentials(credentials),
+                    role: "rpc".to_string(),
+                    methods: vec!["*".to_string()],
+                    rate_limit: None,
+                }])
+            })
+        };
+        let allowed_methods = config
+            .allowed_methods
+            .clone()
+            .filter(|methods| !methods.is_empty())
+            .map(|methods| Role {
+                name: "allowed".to_string(),
+                methods: methods
+                    .iter()
+                    .map(|method| MethodPattern::new(method))
+                    .collect(),
+            });
+
+        let (blockchain, mempool) = (self.create_rest_dispatchers)();
+        Some(RestServer::new(
+            (ip, port).into(),
+            config
+                .allow_ips
+                .clone()
+                .map(|ips| ips.into_iter().collect()),
+            blockchain,
+            mempool,
+            authorization,
+            allowed_methods,
+        ))
+    }
+
+    /// Runs an RPC server, its admin endpoint and the REST gateway with the settings of the given receiver.
+    /// Whenever the settings change, e.g. because the config file was reloaded, the servers are
+    /// restarted with the new settings.
+    pub async fn run(self, mut config: watch::Receiver<RpcServerConfig>) {
+        loop {
+            let config_now = config.borrow_and_update().clone();
+            let admin = self.create_admin(&config_now);
+            let rest = self.create_rest(&config_now);
+            let server = self.create(config_now);
+            let run_admin = async {
+                match &admin {
+                    Some(admin) => admin.run().await,
+                    None => future::pending().await,
+                }
+            };
+            let run_rest = async {
+                match &rest {
+                    Some(rest) => rest.run().await,
+                    None => future::pending().await,
+                }
+            };
+
+            tokio::select! {
+                _ = server.run() => return,
+                _ = run_admin => return,
+                _ = run_rest => return,
+                Ok(()) = config.changed() => log::info!("Restarting RPC server with new settings"),
+            }
+        }
+    }
+}
+
+fn basic_auth(credentials: ConfigCredentials) -> Credentials {
+    Credentials::new_from_blake2b(credentials.username, credentials.password_hash.0 .0)
+}
 
-    Ok(Server::new(
-        Config {
-            bind_to: (config.bind_to.unwrap_or_else(default_bind), config.port).into(),
-            enable_websocket: false,
-            ip_whitelist: None,
-            basic_auth,
+fn authorization(access: Vec<RpcAccess>) -> Authorization {
+    let mut roles: HashMap<String, Arc<Role>> = HashMap::new();
+    let grants = access
+        .into_iter()
+        .map(|access| {
+            let role = roles.entry(access.role.clone()).or_insert_with(|| {
+                Arc::new(Role {
+                    name: access.role,
+                    methods: access
+                        .methods
+                        .iter()
+                        .map(|pattern| MethodPattern::new(pattern))
+                        .collect(),
+                })
+            });
+            let secret = match access.secret {
+                RpcSecret::Credentials(credentials) => Secret::Basic {
+                    username: credentials.username,
+                    password_hash: credentials.password_hash.0,
+                },
+                RpcSecret::Token(token_hash) => Secret::Token {
+                    token_hash: token_hash.0,
+                },
+            };
+            let rate_limit = access.rate_limit.map(|requests| RateLimit {
+                requests,
+                per: Duration::from_secs(60),
+            });
+            Grant::new(secret, Arc::clone(role), rate_limit)
+        })
+        .collect();
+    Authorization::new(grants)
+}
+
+/// Lets the admin RPC set the log level, if logging is set up by this crate.
+fn set_log_level() -> Option<SetLogLevel> {
+    #[cfg(feature = "logging")]
+    {
+        Some(Arc::new(|level, tags| {
+            let settings = LogSettings {
+                level: Some(level),
+                tags,
+                ..Default::default()
+            };
+            crate::extras::logging::reload_log_filter(None, Some(&settings))
+                .map_err(|error| error.to_string())
+        }))
+    }
+    #[cfg(not(feature = "logging"))]
+    None
+}
+
+/// Lets the admin RPC reload the configuration with the given reloader.
+fn reload_config(config_reloader: ConfigReloader) -> ReloadConfig {
+    Arc::new(
+        move || -> Pin<Box<dyn Future<Output = Result<ConfigReloadReport, String>> + Send>> {
+            let config_reloader = config_reloader.clone();
+            Box::pin(async move {
+                config_reloader
+                    .reload()
+                    .await
+                    .map(|report| ConfigReloadReport {
+                        applied: report.applied,
+                        requires_restart: report.requires_restart,
+                    })
+                    .map_err(|error| error.to_string())
+            })
         },
-        AllowListDispatcher::new(dispatcher, allowed_methods),
-    ))
+    )
+}
+
+pub fn initialize_rpc_server(
+    client: &Client,
+    config: RpcServerConfig,
+    wallet_store: Arc<WalletStore>,
+) -> Result<RpcServer, Error> {
+    Ok(RpcServerFactory::new(client, wallet_store, None, None).create(config))
+}
+
+/// Creates the webhooks of a client, those added via RPC and those set in the config file, and
+/// starts notifying them.
+pub fn initialize_webhooks(client: &Client, config: Vec<WebhookConfig>) -> Result<Webhooks, Error> {
+    let webhooks = Webhooks::new(client.environment());
+    for webhook in config {
+        webhooks
+            .configure(webhook.url, webhook.secret.0, webhook.filter)
+            .map_err(|error| Error::config_error(format!("Webhooks: {error}")))?;
+    }
+    client.spawn(
+        ShutdownStage::Rpc,
+        "webhooks",
+        webhooks.clone().run(client.blockchain()),
+    );
+    Ok(webhooks)
 }
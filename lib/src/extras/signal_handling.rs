@@ -1,19 +1,116 @@
-use signal_hook::{consts::SIGINT, iterator::Signals};
-use tokio::time::{sleep, Duration};
+use std::{sync::Arc, time::Duration};
 
-pub fn initialize_signal_handler() {
-    let signals = Signals::new([SIGINT]);
+use nimiq_utils::shutdown::{Shutdown, ShutdownReason};
+use parking_lot::Mutex;
+#[cfg(feature = "config-reload")]
+use signal_hook::consts::SIGHUP;
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+};
+
+#[cfg(feature = "config-reload")]
+use crate::extras::config_reload::ConfigReloader;
+
+/// Handle to the signal handler, used to hand the shutdown over to the client once it is running.
+#[derive(Clone, Default)]
+pub struct SignalHandler {
+    shutdown: Arc<Mutex<Option<Shutdown>>>,
+    #[cfg(feature = "config-reload")]
+    config_reloader: Arc<Mutex<Option<ConfigReloader>>>,
+}
+
+impl SignalHandler {
+    /// Lets the given shutdown handle SIGINT and SIGTERM from now on. The shutdown is run as soon
+    /// as it is requested, be it by a signal or otherwise, and the process exits afterwards.
+    pub fn set_shutdown(&self, shutdown: Shutdown) {
+        *self.shutdown.lock() = Some(shutdown.clone());
 
-    if let Ok(mut signals) = signals {
         tokio::spawn(async move {
-            if signals.forever().next().is_some() {
-                log::warn!("Received Ctrl+C. Closing client");
-                // Add some delay for the log message to propagate into loki
-                sleep(Duration::from_millis(200)).await;
-                std::process::exit(0);
+            let reason = shutdown.requested().await;
+            log::warn!(%reason, "Shutting down client");
+            shutdown.run().await;
+            #[cfg(feature = "otlp")]
+            crate::extras::logging::shutdown_tracing().await;
+            exit(0).await;
+        });
+    }
+
+    /// Lets SIGHUP reload the configuration with the given reloader from now on.
+    #[cfg(feature = "config-reload")]
+    pub fn set_config_reloader(&self, config_reloader: ConfigReloader) {
+        *self.config_reloader.lock() = Some(config_reloader);
+    }
+
+    #[cfg(feature = "config-reload")]
+    fn reload_config(&self, runtime: &tokio::runtime::Handle) {
+        let Some(config_reloader) = self.config_reloader.lock().clone() else {
+            log::warn!(
+                "Received SIGHUP before the client was started, not reloading configuration"
+            );
+            return;
+        };
+
+        log::info!("Received SIGHUP, reloading configuration");
+        runtime.spawn(async move {
+            if let Err(error) = config_reloader.reload().await {
+                log::error!(%error, "Failed to reload configuration");
+            }
+        });
+    }
+}
+
+/// Exits the process after adding some delay for the log messages to propagate into loki.
+async fn exit(code: i32) {
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    std::process::exit(code);
+}
+
+/// Listens for SIGINT and SIGTERM. Until a shutdown is set on the returned handler, the process
+/// exits right away. Afterwards, a signal requests the shutdown and a second one forces the exit.
+///
+/// With the `config-reload` feature, SIGHUP reloads the configuration once a reloader is set.
+pub fn initialize_signal_handler() -> SignalHandler {
+    let handler = SignalHandler::default();
+    #[cfg(feature = "config-reload")]
+    let signals = Signals::new([SIGINT, SIGTERM, SIGHUP]);
+    #[cfg(not(feature = "config-reload"))]
+    let signals = Signals::new([SIGINT, SIGTERM]);
+
+    if let Ok(mut signals) = signals {
+        let handler = handler.clone();
+        let runtime = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || {
+            for signal in signals.forever() {
+                #[cfg(feature = "config-reload")]
+                if signal == SIGHUP {
+                    handler.reload_config(&runtime);
+                    continue;
+                }
+
+                let reason = if signal == SIGTERM {
+                    ShutdownReason::Terminate
+                } else {
+                    ShutdownReason::Interrupt
+                };
+
+                let shutdown = handler.shutdown.lock().clone();
+                match shutdown {
+                    Some(shutdown) if shutdown.request(reason) => {}
+                    Some(_) => {
+                        log::warn!(%reason, "Received another signal, exiting immediately");
+                        runtime.block_on(exit(1));
+                    }
+                    None => {
+                        log::warn!(%reason, "Closing client");
+                        runtime.block_on(exit(0));
+                    }
+                }
             }
         });
     } else {
-        log::error!("Could not obtain SIGINT signal");
+        log::error!("Could not obtain SIGINT and SIGTERM signals");
     }
+
+    handler
 }
@@ -3,6 +3,10 @@ pub mod config;
 pub mod error;
 pub mod extras;
 
+pub mod shutdown {
+    pub use nimiq_utils::shutdown::{Shutdown, ShutdownReason, ShutdownStage};
+}
+
 #[cfg(feature = "zkp-prover")]
 pub mod prover {
     pub use nimiq_zkp_component::prover_binary::prover_main;
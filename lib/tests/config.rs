@@ -113,3 +113,86 @@ fn config_file_partial_db_entry() {
 
     assert_eq!(config.storage, db_config.into());
 }
+
+#[test]
+fn config_file_otlp_entry() {
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [log.otlp]
+    endpoint = "http://localhost:4317"
+    "#,
+    )
+    .unwrap();
+
+    let otlp = config_file.log.otlp.unwrap();
+    assert_eq!(otlp.endpoint.as_str(), "http://localhost:4317/");
+    assert_eq!(otlp.service_name, "nimiq-client");
+    assert_eq!(otlp.sample_ratio, 1.0);
+
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [log.otlp]
+    endpoint = "http://collector:4317"
+    service_name = "validator-1"
+    sample_ratio = 0.25
+    "#,
+    )
+    .unwrap();
+
+    let otlp = config_file.log.otlp.unwrap();
+    assert_eq!(otlp.service_name, "validator-1");
+    assert_eq!(otlp.sample_ratio, 0.25);
+}
+
+#[cfg(feature = "metrics-server")]
+#[test]
+fn config_file_metrics_health_entry() {
+    use nimiq_lib::extras::metrics_server::HealthThresholds;
+
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [consensus]
+    min_peers = 5
+
+    [metrics-server]
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    // The minimum number of peers defaults to the one required for consensus.
+    let health = config.metrics_server.unwrap().health;
+    assert_eq!(
+        health,
+        HealthThresholds {
+            min_peers: 5,
+            ..Default::default()
+        }
+    );
+
+    let config_file: ConfigFile = toml::from_str(
+        r#"
+    [metrics-server.health]
+    max_head_age_blocks = 60
+    min_peers = 1
+    min_db_free_space = 1024
+    "#,
+    )
+    .unwrap();
+
+    let mut config_builder = ClientConfigBuilder::default();
+    config_builder.config_file(&config_file).unwrap();
+    let config = config_builder.build().unwrap();
+
+    assert_eq!(
+        config.metrics_server.unwrap().health,
+        HealthThresholds {
+            max_head_age_blocks: 60,
+            min_peers: 1,
+            min_db_free_space: 1024,
+        }
+    );
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "otlp")]
+
+use nimiq_lib::extras::logging::otlp_layer;
+use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_test_utils::{
+    block_production::TemporaryBlockProducer,
+    blockchain::{generate_transactions, signing_key},
+};
+use opentelemetry::{trace::TracerProvider as _, Value};
+use opentelemetry_sdk::{testing::trace::InMemorySpanExporter, trace::TracerProvider};
+use tracing_subscriber::layer::SubscriberExt;
+
+#[tokio::test]
+async fn spans_are_exported() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber =
+        tracing_subscriber::registry().with(otlp_layer(provider.tracer("nimiq-client")));
+    // The test runs on a single thread, so the subscriber sees all spans.
+    let _guard = log::subscriber::set_default(subscriber);
+
+    let producer = TemporaryBlockProducer::new();
+    let block = producer.next_block(vec![], false);
+    let block_number = block.block_number();
+    producer.push(block).unwrap();
+
+    let mempool = Mempool::new(producer.blockchain.clone(), MempoolConfig::default());
+    let transaction =
+        generate_transactions(&signing_key(), block_number, NetworkId::UnitAlbatross, 1, 0)
+            .remove(0);
+    // The transaction is verified whether it is accepted or not.
+    let _ = mempool.add_transaction(transaction, None).await;
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let span = |name: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("No {name} span was exported"))
+    };
+    assert!(span("push_block").attributes.iter().any(|attribute| {
+        attribute.key.as_str() == "block_number"
+            && attribute.value == Value::I64(block_number.into())
+    }));
+    assert!(span("verify_tx")
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key.as_str() == "tx_hash"));
+}
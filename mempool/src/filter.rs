@@ -39,6 +39,12 @@ impl MempoolFilter {
         self
     }
 
+    /// Replaces the filter rules. The blacklist is kept.
+    pub fn set_rules(&mut self, rules: MempoolRules) -> &mut Self {
+        self.rules = rules;
+        self
+    }
+
     /// Checks whether a transaction is blacklisted
     pub fn blacklisted(&self, hash: &Blake2bHash) -> bool {
         self.blacklist.contains_key(hash)
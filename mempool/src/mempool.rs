@@ -616,6 +616,11 @@ impl Mempool {
         self.filter.read().rules.clone()
     }
 
+    /// Replaces the rules for the mempool. They only apply to transactions added from now on.
+    pub fn set_rules(&self, rules: MempoolRules) {
+        self.filter.write().set_rules(rules);
+    }
+
     /// Checks if a transactions is in the mempool, by its hash.
     pub fn contains_transaction_by_hash(&self, hash: &Blake2bHash) -> bool {
         self.state.read().contains(hash)
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
+use log::Instrument;
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::AbstractBlockchain;
-use nimiq_hash::Hash;
+use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_primitives::{networks::NetworkId, transaction::TransactionError};
 use nimiq_transaction::Transaction;
 use parking_lot::RwLock;
@@ -38,6 +39,27 @@ pub(crate) async fn verify_tx(
     mempool_state: &Arc<RwLock<MempoolState>>,
     filter: Arc<RwLock<MempoolFilter>>,
     priority: TxPriority,
+) -> Result<(), VerifyErr> {
+    let span = log::debug_span!("verify_tx", tx_hash = %transaction.hash::<Blake2bHash>());
+    do_verify_tx(
+        transaction,
+        blockchain,
+        network_id,
+        mempool_state,
+        filter,
+        priority,
+    )
+    .instrument(span)
+    .await
+}
+
+async fn do_verify_tx(
+    transaction: &Transaction,
+    blockchain: Arc<RwLock<Blockchain>>,
+    network_id: NetworkId,
+    mempool_state: &Arc<RwLock<MempoolState>>,
+    filter: Arc<RwLock<MempoolFilter>>,
+    priority: TxPriority,
 ) -> Result<(), VerifyErr> {
     // 1. Verify transaction signature (and other stuff)
     // FIXME Do we really gain anything by spawning here?
@@ -0,0 +1,291 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_database::DatabaseProxy;
+use nimiq_network_libp2p::Network;
+use nimiq_primitives::policy::Policy;
+use nimiq_validator::validator::ValidatorProxy;
+use prometheus_client::registry::Registry;
+use serde_json::{json, Value};
+
+use crate::NumericClosureMetric;
+
+/// The thresholds of the health checks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthThresholds {
+    /// The maximum age of the head block, in multiples of the block separation time.
+    pub max_head_age_blocks: u64,
+    /// The minimum number of connected peers.
+    pub min_peers: usize,
+    /// The minimum space left in the database before it reaches its maximum size, in bytes.
+    pub min_db_free_space: u64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_head_age_blocks: 30,
+            min_peers: 3,
+            // 1 GB
+            min_db_free_space: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// Which probe a check is part of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Probe {
+    /// The node is working and doesn't need to be restarted or taken care of. Failed liveness
+    /// checks also make the node not ready.
+    Liveness,
+    /// The node can serve requests.
+    Readiness,
+    /// The check is only reported.
+    None,
+}
+
+/// The outcome of a health check.
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub ok: bool,
+    pub value: Value,
+    pub probe: Probe,
+}
+
+/// The outcome of all health checks.
+#[derive(Clone, Debug)]
+pub struct HealthReport {
+    pub checks: Vec<Check>,
+}
+
+impl HealthReport {
+    /// Whether all liveness checks passed.
+    pub fn is_healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.probe == Probe::Liveness)
+            .all(|check| check.ok)
+    }
+
+    /// Whether all liveness and readiness checks passed.
+    pub fn is_ready(&self) -> bool {
+        self.checks
+            .iter()
+            .filter(|check| check.probe != Probe::None)
+            .all(|check| check.ok)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let checks: serde_json::Map<String, Value> = self
+            .checks
+            .iter()
+            .map(|check| {
+                (
+                    check.name.to_string(),
+                    json!({ "ok": check.ok, "value": check.value }),
+                )
+            })
+            .collect();
+        json!({
+            "healthy": self.is_healthy(),
+            "ready": self.is_ready(),
+            "checks": checks,
+        })
+    }
+}
+
+/// Derives the health of the node from its state, for the `/health` and `/ready` endpoints.
+pub struct Health {
+    blockchain: BlockchainProxy,
+    consensus_established: Box<dyn Fn() -> bool + Send + Sync>,
+    network: Arc<Network>,
+    validator: Option<ValidatorProxy>,
+    database: DatabaseProxy,
+    thresholds: HealthThresholds,
+}
+
+impl std::fmt::Debug for Health {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Health")
+            .field("thresholds", &self.thresholds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Health {
+    pub fn new(
+        blockchain: BlockchainProxy,
+        consensus_established: Box<dyn Fn() -> bool + Send + Sync>,
+        network: Arc<Network>,
+        validator: Option<ValidatorProxy>,
+        database: DatabaseProxy,
+        thresholds: HealthThresholds,
+    ) -> Self {
+        Self {
+            blockchain,
+            consensus_established,
+            network,
+            validator,
+            database,
+            thresholds,
+        }
+    }
+
+    /// The time since the head block was produced, in milliseconds.
+    fn head_age(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        now.saturating_sub(self.blockchain.read().head().timestamp())
+    }
+
+    fn db_free_space(&self) -> Option<u64> {
+        match self.database.size() {
+            Ok(size) => Some(size.map_size.saturating_sub(size.used) as u64),
+            Err(error) => {
+                log::warn!(%error, "Failed to get database size for health check");
+                None
+            }
+        }
+    }
+
+    fn validator_synced(&self) -> Option<bool> {
+        self.validator.as_ref().map(|validator| {
+            let state = validator.consensus_state.read();
+            state.consensus_established && state.validity_window_synced
+        })
+    }
+
+    fn validator_elected(&self) -> Option<bool> {
+        self.validator.as_ref().map(|validator| {
+            let address = validator.validator_address.read().clone();
+            validator
+                .blockchain
+                .read()
+                .current_validators()
+                .is_some_and(|validators| {
+                    validators
+                        .iter()
+                        .any(|validator| validator.address == address)
+                })
+        })
+    }
+
+    pub fn check(&self) -> HealthReport {
+        let mut checks = vec![];
+
+        let consensus_established = (self.consensus_established)();
+        checks.push(Check {
+            name: "consensus_established",
+            ok: consensus_established,
+            value: consensus_established.into(),
+            probe: Probe::Readiness,
+        });
+
+        let head_age = self.head_age();
+        checks.push(Check {
+            name: "head_age",
+            ok: head_age <= self.thresholds.max_head_age_blocks * Policy::BLOCK_SEPARATION_TIME,
+            value: head_age.into(),
+            probe: Probe::Readiness,
+        });
+
+        let peer_count = self.network.peer_count();
+        checks.push(Check {
+            name: "peer_count",
+            ok: peer_count >= self.thresholds.min_peers,
+            value: peer_count.into(),
+            probe: Probe::Readiness,
+        });
+
+        if let Some(synced) = self.validator_synced() {
+            checks.push(Check {
+                name: "validator_synced",
+                ok: synced,
+                value: synced.into(),
+                probe: Probe::Readiness,
+            });
+        }
+
+        if let Some(elected) = self.validator_elected() {
+            checks.push(Check {
+                name: "validator_elected",
+                ok: elected,
+                value: elected.into(),
+                probe: Probe::None,
+            });
+        }
+
+        let db_free_space = self.db_free_space();
+        checks.push(Check {
+            name: "db_free_space",
+            ok: db_free_space.is_some_and(|free| free >= self.thresholds.min_db_free_space),
+            value: db_free_space.into(),
+            probe: Probe::Liveness,
+        });
+
+        HealthReport { checks }
+    }
+
+    pub fn register(registry: &mut Registry, health: Arc<Health>) {
+        let sub_registry = registry.sub_registry_with_prefix("health");
+
+        let h = Arc::clone(&health);
+        let closure =
+            NumericClosureMetric::new_gauge(Box::new(move || h.check().is_healthy() as i64));
+        sub_registry.register("healthy", "Whether all liveness checks pass", closure);
+
+        let h = Arc::clone(&health);
+        let closure =
+            NumericClosureMetric::new_gauge(Box::new(move || h.check().is_ready() as i64));
+        sub_registry.register(
+            "ready",
+            "Whether all liveness and readiness checks pass",
+            closure,
+        );
+
+        let h = Arc::clone(&health);
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || h.head_age() as i64));
+        sub_registry.register(
+            "head_age_milliseconds",
+            "Time since the head block was produced",
+            closure,
+        );
+
+        let h = Arc::clone(&health);
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+            h.db_free_space().map_or(-1, |free| free as i64)
+        }));
+        sub_registry.register(
+            "db_free_space_bytes",
+            "Space left in the database before it reaches its maximum size",
+            closure,
+        );
+
+        if health.validator.is_some() {
+            let h = Arc::clone(&health);
+            let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+                h.validator_synced().unwrap_or(false) as i64
+            }));
+            sub_registry.register(
+                "validator_synced",
+                "Whether the validator is synced",
+                closure,
+            );
+
+            let closure = NumericClosureMetric::new_gauge(Box::new(move || {
+                health.validator_elected().unwrap_or(false) as i64
+            }));
+            sub_registry.register(
+                "validator_elected",
+                "Whether the validator is part of the current validator set",
+                closure,
+            );
+        }
+    }
+}
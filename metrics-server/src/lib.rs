@@ -2,8 +2,13 @@ use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_consensus::ConsensusProxy;
+use nimiq_database::DatabaseProxy;
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
+use nimiq_validator::{
+    telemetry::TendermintTelemetry, validator::ValidatorProxy,
+    watchtower_metrics::WatchtowerMetrics as WatchtowerCounters,
+};
 use parking_lot::RwLock;
 use prometheus_client::{
     encoding::{EncodeGaugeValue, EncodeMetric, MetricEncoder},
@@ -14,21 +19,26 @@ use prometheus_client::{
 use tokio_metrics::RuntimeMonitor;
 use tokio_metrics::TaskMonitor;
 
+pub use crate::health::{Health, HealthReport, HealthThresholds};
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
     chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
-    network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    network::NetworkMetrics, server::metrics_server, tendermint::TendermintMetrics,
+    tokio_task::TokioTaskMetrics, watchtower::WatchtowerMetrics,
 };
 
 mod chain;
 mod consensus;
+mod health;
 mod mempool;
 mod network;
 mod server;
+mod tendermint;
 #[cfg(tokio_unstable)]
 mod tokio_runtime;
 mod tokio_task;
+mod watchtower;
 
 #[derive(Clone)]
 pub struct NimiqTaskMonitor {
@@ -84,11 +94,27 @@ pub fn start_metrics_server<TNetwork: Network>(
     mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    watchtower_metrics: Option<Arc<WatchtowerCounters>>,
+    tendermint_telemetry: Option<Arc<TendermintTelemetry>>,
     task_monitors: &[NimiqTaskMonitor],
+    database: DatabaseProxy,
+    validator_proxy: Option<ValidatorProxy>,
+    health_thresholds: HealthThresholds,
 ) {
     let mut registry = Registry::default();
     let nimiq_registry = registry.sub_registry_with_prefix("nimiq");
 
+    let consensus = consensus_proxy.clone();
+    let health = Arc::new(Health::new(
+        blockchain_proxy.clone(),
+        Box::new(move || consensus.is_established()),
+        Arc::clone(&network),
+        validator_proxy,
+        database,
+        health_thresholds,
+    ));
+    Health::register(nimiq_registry, Arc::clone(&health));
+
     BlockMetrics::register(nimiq_registry, blockchain_proxy);
     ConsensusMetrics::register(nimiq_registry, consensus_proxy);
     NetworkMetrics::register(nimiq_registry, network);
@@ -97,6 +123,14 @@ pub fn start_metrics_server<TNetwork: Network>(
         MempoolMetrics::register(nimiq_registry, mempool);
     }
 
+    if let Some(watchtower_metrics) = watchtower_metrics {
+        WatchtowerMetrics::register(nimiq_registry, watchtower_metrics);
+    }
+
+    if let Some(tendermint_telemetry) = tendermint_telemetry {
+        TendermintMetrics::register(nimiq_registry, tendermint_telemetry);
+    }
+
     // Setup the task metrics
     let task_metrics = Arc::new(RwLock::new(TokioTaskMetrics::new()));
     task_metrics.write().register(
@@ -124,7 +158,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     }
 
     // Spawn the metrics server
-    tokio::spawn(metrics_server(addr, registry));
+    tokio::spawn(metrics_server(addr, registry, health));
 
     // Spawn Tokio task monitor updaters
     for i in 0..task_monitors.len() {
@@ -13,10 +13,16 @@ use parking_lot::RwLock;
 use prometheus_client::{encoding::text::encode, registry::Registry};
 use tokio::net::TcpListener;
 
-pub async fn metrics_server(addr: SocketAddr, registry: Registry) -> Result<(), std::io::Error> {
+use crate::health::Health;
+
+pub async fn metrics_server(
+    addr: SocketAddr,
+    registry: Registry,
+    health: Arc<Health>,
+) -> Result<(), std::io::Error> {
     let listener = TcpListener::bind(&addr).await?;
     info!("Metrics server on http://{}/metrics", addr);
-    let metrics_service = MetricService::new(registry);
+    let metrics_service = MetricService::new(registry, health);
     loop {
         let (stream, _) = listener.accept().await?;
         let io = TokioIo::new(stream);
@@ -35,14 +41,16 @@ pub async fn metrics_server(addr: SocketAddr, registry: Registry) -> Result<(),
 #[derive(Debug, Clone)]
 pub struct MetricService {
     reg: Arc<RwLock<Registry>>,
+    health: Arc<Health>,
 }
 
 type SharedRegistry = Arc<RwLock<Registry>>;
 
 impl MetricService {
-    pub fn new(registry: Registry) -> Self {
+    pub fn new(registry: Registry, health: Arc<Health>) -> Self {
         Self {
             reg: Arc::new(RwLock::new(registry)),
+            health,
         }
     }
     fn get_reg(&self) -> SharedRegistry {
@@ -59,11 +67,31 @@ impl MetricService {
             .body(Full::new(Bytes::from(encoded)))
             .unwrap()
     }
+    /// Responds with the outcome of the health checks, with status 503 if the liveness checks
+    /// (or, if `ready` is set, the readiness checks) failed.
+    fn respond_with_health(&self, ready: bool) -> Response<Full<Bytes>> {
+        let report = self.health.check();
+        let ok = if ready {
+            report.is_ready()
+        } else {
+            report.is_healthy()
+        };
+        let status = if ok {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        Response::builder()
+            .status(status)
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(report.to_json().to_string())))
+            .unwrap()
+    }
     fn respond_with_404_not_found(&self) -> Response<Full<Bytes>> {
         Response::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Full::new(Bytes::from(
-                "Not found try localhost:[port]/metrics",
+                "Not found try localhost:[port]/metrics, /health or /ready",
             )))
             .unwrap()
     }
@@ -77,11 +105,12 @@ impl Service<Request<IncomingBody>> for MetricService {
     fn call(&self, req: Request<IncomingBody>) -> Self::Future {
         let req_path = req.uri().path();
         let req_method = req.method();
-        let resp = if (req_method == Method::GET) && (req_path == "/metrics") {
+        let resp = match (req_method, req_path) {
             // Encode and serve metrics from registry.
-            self.respond_with_metrics()
-        } else {
-            self.respond_with_404_not_found()
+            (&Method::GET, "/metrics") => self.respond_with_metrics(),
+            (&Method::GET, "/health") => self.respond_with_health(false),
+            (&Method::GET, "/ready") => self.respond_with_health(true),
+            _ => self.respond_with_404_not_found(),
         };
         Box::pin(async { Ok(resp) })
     }
@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use nimiq_validator::telemetry::TendermintTelemetry;
+use prometheus_client::registry::Registry;
+
+pub struct TendermintMetrics {}
+
+impl TendermintMetrics {
+    pub fn register(registry: &mut Registry, tendermint_telemetry: Arc<TendermintTelemetry>) {
+        let sub_registry = registry.sub_registry_with_prefix("tendermint");
+
+        tendermint_telemetry.metrics().register(sub_registry);
+    }
+}
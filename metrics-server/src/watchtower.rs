@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use nimiq_validator::watchtower_metrics::WatchtowerMetrics as WatchtowerCounters;
+use prometheus_client::registry::Registry;
+
+pub struct WatchtowerMetrics {}
+
+impl WatchtowerMetrics {
+    pub fn register(registry: &mut Registry, watchtower_metrics: Arc<WatchtowerCounters>) {
+        let sub_registry = registry.sub_registry_with_prefix("watchtower");
+
+        watchtower_metrics.register(sub_registry);
+    }
+}
@@ -468,6 +468,16 @@ impl Behaviour {
         self.active = false;
     }
 
+    /// Changes the number of connections the behaviour tries to maintain. Lowering it doesn't
+    /// close any connections, it only stops dialing new peers until the count dropped below it.
+    pub fn set_desired_peer_count(&mut self, desired_peer_count: usize) {
+        self.config.desired_peer_count = desired_peer_count;
+        self.peer_ids.desired_connections = desired_peer_count;
+        self.addresses.desired_connections = desired_peer_count;
+        debug!(desired_peer_count, "Changed desired peer count");
+        self.maintain_peers();
+    }
+
     /// Closes a peer connection with a reason
     ///
     /// This will take actions depending on the close reason. For instance:
@@ -14,6 +14,7 @@ use libp2p::websocket_websys;
 use libp2p::{
     gossipsub, request_response::InboundRequestId, swarm::NetworkInfo, Multiaddr, PeerId, Swarm,
 };
+use log::Instrument;
 use nimiq_network_interface::{
     network::{
         CloseReason, MsgAcceptance, Network as NetworkInterface, NetworkEvent, SubscribeEvents,
@@ -202,6 +203,18 @@ impl Network {
         }
     }
 
+    /// Changes the number of peers the network tries to stay connected to
+    pub async fn set_desired_peer_count(&self, desired_peer_count: usize) {
+        if let Err(error) = self
+            .action_tx
+            .clone()
+            .send(NetworkAction::SetDesiredPeerCount { desired_peer_count })
+            .await
+        {
+            error!(%error, "Failed to send NetworkAction::SetDesiredPeerCount");
+        }
+    }
+
     async fn request_impl<Req: RequestCommon>(
         &self,
         request: Req,
@@ -759,7 +772,13 @@ impl NetworkInterface for Network {
         request: Req,
         peer_id: PeerId,
     ) -> Result<Req::Response, RequestError> {
-        self.request_impl(request, peer_id).await
+        self.request_impl(request, peer_id)
+            .instrument(log::debug_span!(
+                "request",
+                request = std::any::type_name::<Req>(),
+                %peer_id,
+            ))
+            .await
     }
 
     fn receive_messages<M: Message>(&self) -> BoxStream<'static, (M, PeerId)> {
@@ -100,6 +100,9 @@ pub(crate) enum NetworkAction {
     UnbanPeer {
         peer_id: PeerId,
     },
+    SetDesiredPeerCount {
+        desired_peer_count: usize,
+    },
 }
 
 pub(crate) struct ValidateMessage<P: Clone> {
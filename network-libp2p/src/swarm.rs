@@ -1165,6 +1165,10 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
         NetworkAction::UnbanPeer { peer_id } => {
             swarm.behaviour_mut().pool.unban_connection(peer_id)
         }
+        NetworkAction::SetDesiredPeerCount { desired_peer_count } => swarm
+            .behaviour_mut()
+            .pool
+            .set_desired_peer_count(desired_peer_count),
     }
 }
 
@@ -13,7 +13,7 @@ pub use crate::{
         basic_account::BasicAccount, htlc_contract::HashedTimeLockedContract, staking_contract::*,
         vesting_contract::VestingContract, Account,
     },
-    data_store_ops::DataStoreReadOps,
+    data_store_ops::{DataStoreIterOps, DataStoreReadOps},
     logs::*,
     receipts::*,
     reserved_balance::ReservedBalance,
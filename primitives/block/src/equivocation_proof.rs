@@ -6,6 +6,7 @@ use nimiq_hash::{Blake2bHash, Blake2sHash, Hash as _, HashOutput, SerializeConte
 use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, Ed25519Signature as SchnorrSignature,
 };
+use nimiq_network_interface::network::Topic;
 use nimiq_primitives::{
     networks::NetworkId,
     policy::Policy,
@@ -20,6 +21,19 @@ use thiserror::Error;
 
 use crate::{MacroHeader, MicroHeader};
 
+/// This network topic is used to gossip equivocation proofs to the validators, such that they get
+/// included in a block.
+#[derive(Clone, Debug, Default)]
+pub struct EquivocationProofTopic;
+
+impl Topic for EquivocationProofTopic {
+    type Item = EquivocationProof;
+
+    const BUFFER_SIZE: usize = 16;
+    const NAME: &'static str = "equivocation-proof";
+    const VALIDATE: bool = true;
+}
+
 /// An equivocation proof proves that a validator misbehaved.
 ///
 /// This can come in several forms, but e.g. producing two blocks in a single slot or voting twice
@@ -216,6 +216,7 @@ impl<H: Merge + Clone + Eq> Proof<H> {
 
 /// A Merkle proof for a MMR. This is equal to the regular Merkle proof, but has the `assume_previous`
 /// flag which can be used when we are verifying consecutive range proofs.
+#[derive(Clone)]
 #[cfg_attr(
     feature = "serde-derive",
     derive(nimiq_serde::Serialize, nimiq_serde::Deserialize)
@@ -19,6 +19,26 @@ pub enum EquivocationLocator {
     DoubleVote(DoubleVoteLocator),
 }
 
+impl EquivocationLocator {
+    /// Address of the offending validator.
+    pub fn validator_address(&self) -> &Address {
+        match self {
+            EquivocationLocator::Fork(locator) => &locator.validator_address,
+            EquivocationLocator::DoubleProposal(locator) => &locator.validator_address,
+            EquivocationLocator::DoubleVote(locator) => &locator.validator_address,
+        }
+    }
+
+    /// The block height at which the equivocation occurred.
+    pub fn block_number(&self) -> u32 {
+        match self {
+            EquivocationLocator::Fork(locator) => locator.block_number,
+            EquivocationLocator::DoubleProposal(locator) => locator.block_number,
+            EquivocationLocator::DoubleVote(locator) => locator.block_number,
+        }
+    }
+}
+
 /// Describes the location of a single fork equivocation.
 ///
 /// See [`EquivocationLocator`] for more details.
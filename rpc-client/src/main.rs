@@ -4,9 +4,9 @@ use nimiq_jsonrpc_client::{
     websocket::WebsocketClient, ArcClient, Client as RPCclient, Credentials,
 };
 use nimiq_rpc_interface::{
-    blockchain::BlockchainProxy, consensus::ConsensusProxy, mempool::MempoolProxy,
-    network::NetworkProxy, policy::PolicyProxy, validator::ValidatorProxy, wallet::WalletProxy,
-    zkp_component::ZKPComponentProxy,
+    admin::AdminProxy, blockchain::BlockchainProxy, consensus::ConsensusProxy,
+    mempool::MempoolProxy, network::NetworkProxy, policy::PolicyProxy, validator::ValidatorProxy,
+    wallet::WalletProxy, zkp_component::ZKPComponentProxy,
 };
 use url::Url;
 pub mod subcommands;
@@ -62,6 +62,10 @@ enum Command {
     /// Shows the zkp information.
     #[clap(flatten)]
     Zkp(ZKPComponentCommand),
+
+    /// Manages the node. These commands have to be sent to the admin endpoint of the RPC server.
+    #[clap(flatten)]
+    Admin(AdminCommand),
 }
 
 impl Command {
@@ -75,6 +79,7 @@ impl Command {
             Command::Mempool(command) => command.handle_subcommand(client).await,
             Command::Validator(command) => command.handle_subcommand(client).await,
             Command::Zkp(command) => command.handle_subcommand(client).await,
+            Command::Admin(command) => command.handle_subcommand(client).await,
         }
     }
 }
@@ -89,6 +94,7 @@ pub struct Client {
     pub validator: ValidatorProxy<ArcClient<WebsocketClient>>,
     pub network: NetworkProxy<ArcClient<WebsocketClient>>,
     pub zkp_component: ZKPComponentProxy<ArcClient<WebsocketClient>>,
+    pub admin: AdminProxy<ArcClient<WebsocketClient>>,
 }
 
 impl Client {
@@ -104,6 +110,7 @@ impl Client {
             validator: ValidatorProxy::new(client.clone()),
             network: NetworkProxy::new(client.clone()),
             zkp_component: ZKPComponentProxy::new(client.clone()),
+            admin: AdminProxy::new(client.clone()),
             ws_client: client,
         })
     }
@@ -0,0 +1,128 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use nimiq_keys::Address;
+use nimiq_rpc_interface::{admin::AdminInterface, types::LogType};
+
+use super::accounts_subcommands::HandleSubcommand;
+use crate::Client;
+
+#[derive(Debug, Parser)]
+pub enum AdminCommand {
+    /// Shows the version, user agent, sync mode, uptime, services and database size of the node.
+    NodeInfo {},
+
+    /// Sets the log level of the node.
+    SetLogLevel {
+        /// The log level, e.g. `info` or `debug`.
+        level: String,
+
+        /// The levels of specific targets, e.g. `nimiq-validator:trace`.
+        #[clap(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Dials a peer, given by its peer ID or a multiaddress.
+    DialPeer { peer: String },
+
+    /// Closes the connection to a peer.
+    DisconnectPeer { peer_id: String },
+
+    /// Copies the database into a directory on the node's file system.
+    BackupDatabase { path: String },
+
+    /// Requests a graceful shutdown of the node.
+    Shutdown {},
+
+    /// Re-reads the config file of the node and applies the settings that can be changed at
+    /// runtime.
+    ReloadConfig {},
+
+    /// Registers a URL that is notified of the logs of addresses, of validator events or of
+    /// finalized macro blocks.
+    AddWebhook {
+        /// The URL the notifications are POSTed to.
+        url: String,
+
+        /// The key the notifications are signed with.
+        secret: String,
+
+        /// The addresses whose logs are sent.
+        #[clap(short = 'a', long = "address")]
+        addresses: Vec<Address>,
+
+        /// The types of the logs that are sent. All types are sent if none are given.
+        #[clap(short = 'l', long = "log-type", value_enum)]
+        log_types: Vec<LogType>,
+
+        /// The validators whose election, jailing and deactivation are sent.
+        #[clap(short = 'v', long = "validator")]
+        validators: Vec<Address>,
+
+        /// Send finalized macro blocks.
+        #[clap(short = 'f', long)]
+        finalized: bool,
+    },
+
+    /// Removes a webhook that was added via RPC.
+    RemoveWebhook { id: String },
+
+    /// Lists the webhooks of the node.
+    Webhooks {},
+}
+
+#[async_trait]
+impl HandleSubcommand for AdminCommand {
+    async fn handle_subcommand(self, mut client: Client) -> Result<Client, Error> {
+        match self {
+            AdminCommand::NodeInfo {} => {
+                println!("{:#?}", client.admin.get_node_info().await?);
+            }
+            AdminCommand::SetLogLevel { level, tags } => {
+                client.admin.set_log_level(level, tags).await?;
+                println!("Log level set");
+            }
+            AdminCommand::DialPeer { peer } => {
+                client.admin.dial_peer(peer).await?;
+                println!("Peer dialed");
+            }
+            AdminCommand::DisconnectPeer { peer_id } => {
+                client.admin.disconnect_peer(peer_id).await?;
+                println!("Peer disconnected");
+            }
+            AdminCommand::BackupDatabase { path } => {
+                let copied = client.admin.backup_database(path).await?;
+                println!("Database backed up, {copied} bytes copied");
+            }
+            AdminCommand::Shutdown {} => {
+                client.admin.shutdown().await?;
+                println!("Shutdown requested");
+            }
+            AdminCommand::ReloadConfig {} => {
+                println!("{:#?}", client.admin.reload_config().await?);
+            }
+            AdminCommand::AddWebhook {
+                url,
+                secret,
+                addresses,
+                log_types,
+                validators,
+                finalized,
+            } => {
+                let id = client
+                    .admin
+                    .add_webhook(url, secret, addresses, log_types, validators, finalized)
+                    .await?;
+                println!("Webhook added: {id}");
+            }
+            AdminCommand::RemoveWebhook { id } => {
+                client.admin.remove_webhook(id).await?;
+                println!("Webhook removed");
+            }
+            AdminCommand::Webhooks {} => {
+                println!("{:#?}", client.admin.get_webhooks().await?);
+            }
+        }
+        Ok(client)
+    }
+}
@@ -123,6 +123,17 @@ pub enum BlockchainCommand {
         address: Address,
     },
 
+    /// Returns the disciplinary record of a validator, i.e. its penalties, jailings and
+    /// equivocation proofs, from most recent to least recent.
+    DisciplinaryRecord {
+        /// The validator address to query by.
+        address: Address,
+
+        /// Max number of events to fetch. If absent it defaults to 500.
+        #[clap(long)]
+        max: Option<u16>,
+    },
+
     /// Tries to fetch all validators in the staking contract.
     /// IMPORTANT: This is a very expensive operation, iterating over all existing validators in the contract.
     Validators {},
@@ -143,6 +154,18 @@ pub enum BlockchainCommand {
     /// Lists the current stakes from the staking contract.
     Stakes {},
 
+    /// Returns the tally of the signal data of the validators elected at an election block.
+    /// If no epochs are given, the tally of the latest election block is returned.
+    SignalTally {
+        /// The first epoch whose election block tally should be returned.
+        #[clap(long, requires = "to_epoch")]
+        from_epoch: Option<u32>,
+
+        /// The last epoch whose election block tally should be returned.
+        #[clap(long, requires = "from_epoch")]
+        to_epoch: Option<u32>,
+    },
+
     /// Follow the head of the blockchain.
     FollowHead {
         /// Show the full block instead of only the hash.
@@ -303,6 +326,14 @@ impl HandleSubcommand for BlockchainCommand {
                 client.blockchain.get_validator_by_address(address).await?
             ),
 
+            BlockchainCommand::DisciplinaryRecord { address, max } => println!(
+                "{:#?}",
+                client
+                    .blockchain
+                    .get_disciplinary_record_by_address(address, max)
+                    .await?
+            ),
+
             BlockchainCommand::Validators {} => {
                 println!("{:#?}", client.blockchain.get_validators().await?)
             }
@@ -323,6 +354,22 @@ impl HandleSubcommand for BlockchainCommand {
             BlockchainCommand::Stakes {} => {
                 println!("{:#?}", client.blockchain.get_active_validators().await?);
             }
+            BlockchainCommand::SignalTally {
+                from_epoch,
+                to_epoch,
+            } => {
+                if let (Some(from_epoch), Some(to_epoch)) = (from_epoch, to_epoch) {
+                    println!(
+                        "{:#?}",
+                        client
+                            .blockchain
+                            .get_signal_tallies(from_epoch, to_epoch)
+                            .await?
+                    )
+                } else {
+                    println!("{:#?}", client.blockchain.get_signal_tally(None).await?)
+                }
+            }
 
             BlockchainCommand::FollowHead { block: show_block } => {
                 if show_block {
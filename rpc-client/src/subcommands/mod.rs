@@ -1,4 +1,5 @@
 pub use accounts_subcommands::{AccountCommand, HandleSubcommand};
+pub use admin_subcommands::AdminCommand;
 pub use blockchain_subcommands::BlockchainCommand;
 pub use mempool_subcommands::MempoolCommand;
 pub use network_subcommands::NetworkCommand;
@@ -8,6 +9,7 @@ pub use validator_subcommands::ValidatorCommand;
 pub use zkp_component_subcommands::ZKPComponentCommand;
 
 mod accounts_subcommands;
+mod admin_subcommands;
 mod blockchain_subcommands;
 mod mempool_subcommands;
 mod network_subcommands;
@@ -28,6 +28,26 @@ pub enum ValidatorCommand {
     /// Returns the voting key of the local validator.
     ValidatorVotingKey {},
 
+    /// Submits a serialized equivocation proof (fork, double proposal or double vote) to the local
+    /// validator. If the proof is valid, it is gossiped to the other validators to be included in a
+    /// block.
+    SubmitEquivocationProof {
+        /// The hex encoded equivocation proof.
+        serialized_proof: String,
+    },
+
+    /// Returns the equivocation proofs of the local validator that are waiting to be included in a
+    /// block.
+    PendingEquivocationProofs {},
+
+    /// Returns how the local validator reached agreement on a macro block with Tendermint, i.e.
+    /// the rounds, step durations and timeouts. Without a block number, the telemetry of the most
+    /// recent macro blocks is returned.
+    MacroBlockTelemetry {
+        /// The block number of the macro block.
+        block_number: Option<u32>,
+    },
+
     /// Sends a `new_validator` transaction to the network. You need to provide the address of a basic
     /// account (the sender wallet) to pay the transaction fee and the validator deposit. The sender wallet must be unlocked
     /// prior to this command.
@@ -92,6 +112,22 @@ pub enum ValidatorCommand {
         tx_commons: TxCommon,
     },
 
+    /// Sends a transaction to update the signal data of this validator, e.g. to signal readiness
+    /// for a chain upgrade. The signal data is tallied at every election block. The other fields of
+    /// the validator are left unchanged. The sender wallet must be unlocked prior to this command.
+    /// "" = Clear the signal data.
+    /// "0x29a4b..." = Signal the given hash.
+    SetSignal {
+        /// The fee will be paid from this address. This wallet must be already unlocked.
+        sender_wallet: Address,
+
+        /// The signal data to be shown by the validator.
+        signal_data: String,
+
+        #[clap(flatten)]
+        tx_commons: TxCommon,
+    },
+
     /// Sends a transaction to deactivate this validator. In order to avoid having the validator reactivated soon after
     /// this transaction takes effect, use the command set-auto-reactivate-validator to make sure the automatic reactivation
     /// configuration is turned off.
@@ -142,6 +178,35 @@ impl HandleSubcommand for ValidatorCommand {
                 println!("{:#?}", client.validator.get_voting_key().await?);
             }
 
+            ValidatorCommand::SubmitEquivocationProof { serialized_proof } => {
+                client
+                    .validator
+                    .submit_equivocation_proof(serialized_proof)
+                    .await?;
+                println!("Equivocation proof submitted");
+            }
+
+            ValidatorCommand::PendingEquivocationProofs {} => {
+                println!(
+                    "{:#?}",
+                    client.validator.get_pending_equivocation_proofs().await?
+                );
+            }
+
+            ValidatorCommand::MacroBlockTelemetry { block_number } => match block_number {
+                Some(block_number) => println!(
+                    "{:#?}",
+                    client
+                        .validator
+                        .get_macro_block_telemetry(block_number)
+                        .await?
+                ),
+                None => println!(
+                    "{:#?}",
+                    client.validator.get_recent_macro_block_telemetry().await?
+                ),
+            },
+
             ValidatorCommand::SetAutoReactivateValidator {
                 automatic_reactivate,
             } => {
@@ -236,6 +301,45 @@ impl HandleSubcommand for ValidatorCommand {
                 }
             }
 
+            ValidatorCommand::SetSignal {
+                sender_wallet,
+                signal_data,
+                tx_commons,
+            } => {
+                let validator_address = client.validator.get_address().await?.data;
+                if tx_commons.dry {
+                    let tx = client
+                        .consensus
+                        .create_update_validator_transaction(
+                            sender_wallet,
+                            validator_address,
+                            None,
+                            None,
+                            None,
+                            Some(signal_data),
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{tx:#?}");
+                } else {
+                    let txid = client
+                        .consensus
+                        .send_update_validator_transaction(
+                            sender_wallet,
+                            validator_address,
+                            None,
+                            None,
+                            None,
+                            Some(signal_data),
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{txid:#?}");
+                }
+            }
+
             ValidatorCommand::DeactivateValidator {
                 sender_wallet,
                 tx_commons,
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use nimiq_keys::Address;
+
+use crate::types::{ConfigReloadReport, LogType, NodeInfo, RPCResult, Webhook};
+
+/// Operator methods. They are only served on the separate admin endpoint of the RPC server, which
+/// requires its own credentials.
+#[nimiq_jsonrpc_derive::proxy(name = "AdminProxy", rename_all = "camelCase")]
+#[async_trait]
+pub trait AdminInterface {
+    type Error;
+
+    /// Returns the version, user agent, sync mode, uptime, enabled services and database size of
+    /// the node.
+    async fn get_node_info(&mut self) -> RPCResult<NodeInfo, (), Self::Error>;
+
+    /// Sets the log level and the levels of specific targets, given as `target:level`. They
+    /// replace the levels from the config file until it is reloaded.
+    async fn set_log_level(
+        &mut self,
+        level: String,
+        tags: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Dials the given peer, which is either a peer ID or a multiaddress.
+    async fn dial_peer(&mut self, peer: String) -> RPCResult<(), (), Self::Error>;
+
+    /// Closes the connection to the given peer.
+    async fn disconnect_peer(&mut self, peer_id: String) -> RPCResult<(), (), Self::Error>;
+
+    /// Copies the database into the given directory on the node's file system, which must not
+    /// contain a database yet. Returns the size of the backup in bytes. The backup is a consistent
+    /// snapshot and doesn't block writing to the database.
+    async fn backup_database(&mut self, path: String) -> RPCResult<u64, (), Self::Error>;
+
+    /// Requests a graceful shutdown of the node. The RPC server, validator, consensus, mempool,
+    /// network, ZKP prover and database are stopped in that order and the process exits.
+    async fn shutdown(&mut self) -> RPCResult<(), (), Self::Error>;
+
+    /// Re-reads the config file and applies the settings that can be changed at runtime. Returns
+    /// which changed settings were applied and which require a restart.
+    async fn reload_config(&mut self) -> RPCResult<ConfigReloadReport, (), Self::Error>;
+
+    /// Registers a URL that is notified of the logs of the given addresses, of the given types or
+    /// of all types if none are given, of the election, jailing and deactivation of the given
+    /// validators and, if `finalized` is set, of finalized macro blocks. Notifications are POSTed
+    /// as JSON, signed with HMAC-SHA256 using the given secret. Returns the ID of the webhook.
+    async fn add_webhook(
+        &mut self,
+        url: String,
+        secret: String,
+        addresses: Vec<Address>,
+        log_types: Vec<LogType>,
+        validators: Vec<Address>,
+        finalized: bool,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Removes a webhook that was added via RPC. Notifications that have not been delivered yet
+    /// are dropped.
+    async fn remove_webhook(&mut self, id: String) -> RPCResult<(), (), Self::Error>;
+
+    /// Returns the webhooks, both those set in the config file and those added via RPC.
+    async fn get_webhooks(&mut self) -> RPCResult<Vec<Webhook>, (), Self::Error>;
+}
@@ -2,10 +2,12 @@ use async_trait::async_trait;
 use futures::stream::BoxStream;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, Block, BlockLog, BlockchainState, DisciplinaryEvent, ExecutedTransaction,
+    HistoryRetention, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, RewardEstimate,
+    SignalTally, Slot, Staker, StakerReward, TransactionInclusionProof, Validator,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -62,6 +64,14 @@ pub trait BlockchainInterface {
         hash: Blake2bHash,
     ) -> RPCResult<ExecutedTransaction, (), Self::Error>;
 
+    /// Returns a self-contained proof that the transaction with the given hash is part of the
+    /// finalized chain. The proof can be verified offline given the hash of its anchor block.
+    /// Only transactions in a finalized batch can be proven.
+    async fn get_transaction_inclusion_proof(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionInclusionProof, (), Self::Error>;
+
     /// Returns all the transactions (including reward transactions) for the given block number. Note
     /// that this only considers blocks in the main chain.
     async fn get_transactions_by_block_number(
@@ -110,6 +120,12 @@ pub trait BlockchainInterface {
         max: Option<u16>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error>;
 
+    /// Returns which part of the history is retained by this node. Nodes with a configured history
+    /// retention prune the history of older epochs.
+    async fn get_history_retention(
+        &mut self,
+    ) -> RPCResult<HistoryRetention, BlockchainState, Self::Error>;
+
     /// Tries to fetch the account at the given address.
     async fn get_account_by_address(
         &mut self,
@@ -144,6 +160,16 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Validator, BlockchainState, Self::Error>;
 
+    /// Returns the disciplinary record of the given validator, i.e. the penalties, jailings and
+    /// equivocation proofs recorded against it, from most recent to least recent. Only the history
+    /// retained by this node is considered. It has an option to specify the maximum number of
+    /// events to fetch, it defaults to 500.
+    async fn get_disciplinary_record_by_address(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<DisciplinaryEvent>, (), Self::Error>;
+
     /// Fetches all validators in the staking contract.
     /// IMPORTANT: This operation iterates over all validators in the staking contract
     /// and thus is extremely computationally expensive.
@@ -163,6 +189,84 @@ pub trait BlockchainInterface {
         address: Address,
     ) -> RPCResult<Staker, BlockchainState, Self::Error>;
 
+    /// Tries to fetch the account at the given address as it was at the given macro block.
+    /// This is only supported by nodes running in archive mode.
+    async fn get_account_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Account, BlockchainState, Self::Error>;
+
+    /// Fetches all validators in the staking contract as they were at the given macro block.
+    /// This is only supported by nodes running in archive mode.
+    /// IMPORTANT: This operation iterates over all archived validators and thus is extremely
+    /// computationally expensive.
+    async fn get_validators_at(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error>;
+
+    /// Tries to fetch a validator information given its address as it was at the given macro block.
+    /// This is only supported by nodes running in archive mode.
+    async fn get_validator_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Validator, BlockchainState, Self::Error>;
+
+    /// Fetches all stakers for a given validator as they were at the given macro block.
+    /// This is only supported by nodes running in archive mode.
+    /// IMPORTANT: This operation iterates over all archived stakers of the staking contract
+    /// and thus is extremely computationally expensive.
+    async fn get_stakers_by_validator_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error>;
+
+    /// Tries to fetch a staker information given its address as it was at the given macro block.
+    /// This is only supported by nodes running in archive mode.
+    async fn get_staker_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Staker, BlockchainState, Self::Error>;
+
+    /// Computes the rewards earned by the given staker in the batches of the given epochs. Each
+    /// batch reward of the validator the staker delegated to is split pro-rata to the staker's
+    /// active balance at the election block preceding the epoch.
+    /// This is only supported by nodes running in archive mode.
+    async fn get_staker_rewards(
+        &mut self,
+        address: Address,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<StakerReward>, (), Self::Error>;
+
+    /// Projects the rewards and the annual yield of the given stake if it is delegated to the
+    /// given validator. The projection is based on the supply curve, the current total stake and
+    /// the batch delays observed during the last epoch. Transaction fees are not included.
+    async fn get_staking_reward_estimate(
+        &mut self,
+        validator_address: Address,
+        stake: Coin,
+    ) -> RPCResult<RewardEstimate, BlockchainState, Self::Error>;
+
+    /// Returns the tally of the `signal_data` of the validators elected at the given election
+    /// block, weighted by their slots and stake. Defaults to the latest election block.
+    async fn get_signal_tally(
+        &mut self,
+        block_number: Option<u32>,
+    ) -> RPCResult<SignalTally, (), Self::Error>;
+
+    /// Returns the signal tallies taken at the election blocks of the given epochs. Tallies that
+    /// are unknown to this node are omitted.
+    async fn get_signal_tallies(
+        &mut self,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<SignalTally>, (), Self::Error>;
+
     /// Subscribes to new block events (retrieves the full block).
     #[stream]
     async fn subscribe_for_head_block(
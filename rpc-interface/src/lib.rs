@@ -1,9 +1,11 @@
+pub mod admin;
 pub mod blockchain;
 pub mod consensus;
 pub mod error;
 pub mod mempool;
 pub mod network;
 pub mod policy;
+pub mod rest;
 mod serde_helpers;
 pub mod types;
 pub mod validator;
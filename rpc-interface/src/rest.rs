@@ -0,0 +1,40 @@
+/// The routes of the REST gateway, as pairs of the path and the RPC method serving it. Path
+/// parameters are named like the parameters of the method, all other parameters of the method
+/// are taken from the query string. Methods that return a stream are served as Server-Sent
+/// Events.
+pub const REST_ROUTES: &[(&str, &str)] = &[
+    ("/blocks/latest", "getLatestBlock"),
+    ("/blocks/{blockNumber}", "getBlockByNumber"),
+    ("/transactions/{hash}", "getTransactionByHash"),
+    ("/accounts/{address}", "getAccountByAddress"),
+    ("/validators", "getActiveValidators"),
+    ("/mempool", "mempool"),
+    ("/mempool/transactions", "mempoolContent"),
+    ("/events/head-blocks", "subscribeForHeadBlock"),
+    ("/events/logs", "subscribeForLogsByAddressesAndTypes"),
+];
+
+/// Matches a request path against the routes of the REST gateway, returning the RPC method of
+/// the route and the path parameters.
+pub fn match_route(path: &str) -> Option<(&'static str, Vec<(&'static str, String)>)> {
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    REST_ROUTES.iter().find_map(|(route, method)| {
+        let route_segments: Vec<&'static str> = route.split('/').collect();
+        if route_segments.len() != segments.len() {
+            return None;
+        }
+
+        let mut params = vec![];
+        for (route_segment, segment) in route_segments.into_iter().zip(&segments) {
+            match route_segment
+                .strip_prefix('{')
+                .and_then(|name| name.strip_suffix('}'))
+            {
+                Some(name) if !segment.is_empty() => params.push((name, segment.to_string())),
+                None if route_segment == *segment => {}
+                _ => return None,
+            }
+        }
+        Some((*method, params))
+    })
+}
@@ -22,8 +22,10 @@ use nimiq_serde::Serialize as NimiqSerialize;
 use nimiq_transaction::{
     account::htlc_contract::AnyHash,
     historic_transaction::{
-        HistoricTransaction, HistoricTransactionData, JailEvent, PenalizeEvent, RewardEvent,
+        EquivocationEvent, HistoricTransaction, HistoricTransactionData, JailEvent, PenalizeEvent,
+        RewardEvent,
     },
+    EquivocationLocator,
 };
 use nimiq_vrf::VrfSeed;
 use serde::{Deserialize, Serialize};
@@ -431,6 +433,56 @@ pub struct PenalizedSlots {
     pub disabled: BitSet,
 }
 
+/// A self-contained proof that a transaction is part of the finalized chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInclusionProof {
+    pub transaction_hash: Blake2bHash,
+    pub block_number: u32,
+    /// The macro block whose history root the transaction is proven against.
+    pub proving_block_number: u32,
+    pub proving_block_hash: Blake2bHash,
+    /// The macro block the proof is anchored at. The verifier must trust the hash of this block.
+    pub anchor_block_number: u32,
+    pub anchor_block_hash: Blake2bHash,
+    /// The serialized proof, which can be verified offline.
+    #[serde(with = "crate::serde_helpers::hex")]
+    pub proof: Vec<u8>,
+}
+
+impl TransactionInclusionProof {
+    pub fn from_proof(
+        transaction_hash: Blake2bHash,
+        proof: &nimiq_blockchain_interface::TransactionInclusionProof,
+    ) -> Self {
+        TransactionInclusionProof {
+            block_number: proof
+                .get_transaction(&transaction_hash)
+                .map(|hist_tx| hist_tx.block_number)
+                .unwrap_or_else(|| proof.block.block_number()),
+            transaction_hash,
+            proving_block_number: proof.block.block_number(),
+            proving_block_hash: proof.block.hash(),
+            anchor_block_number: proof.anchor_block.block_number(),
+            anchor_block_hash: proof.anchor_block.hash(),
+            proof: proof.serialize_to_vec(),
+        }
+    }
+}
+
+/// Describes which part of the history is retained by a node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRetention {
+    /// The number of epochs (other than the current one) for which the full history is retained.
+    /// If not set, the node keeps the full history.
+    pub retention_epochs: Option<u32>,
+    /// The first epoch whose history is retained.
+    pub first_epoch_number: u32,
+    /// The first block whose history is retained.
+    pub first_block_number: u32,
+}
+
 /// An equivocation proof proves that a validator misbehaved.
 ///
 /// This can come in several forms, but e.g. producing two blocks in a single slot or voting twice
@@ -763,6 +815,91 @@ impl Inherent {
     }
 }
 
+/// The type of an equivocation that was proven against a validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EquivocationType {
+    Fork,
+    DoubleProposal,
+    DoubleVote,
+}
+
+impl From<&EquivocationLocator> for EquivocationType {
+    fn from(locator: &EquivocationLocator) -> Self {
+        match locator {
+            EquivocationLocator::Fork(_) => EquivocationType::Fork,
+            EquivocationLocator::DoubleProposal(_) => EquivocationType::DoubleProposal,
+            EquivocationLocator::DoubleVote(_) => EquivocationType::DoubleVote,
+        }
+    }
+}
+
+/// An entry of a validator's disciplinary record. Penalties are the consequence of delayed blocks
+/// and affect a single slot, while a proven equivocation leads to the validator being jailed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum DisciplinaryEvent {
+    #[serde(rename_all = "camelCase")]
+    Penalize {
+        block_number: u32,
+        block_time: u64,
+        offense_event_block: u32,
+        slot: u16,
+    },
+    #[serde(rename_all = "camelCase")]
+    Jail {
+        block_number: u32,
+        block_time: u64,
+        offense_event_block: u32,
+        first_slot_number: u16,
+        num_slots: u16,
+    },
+    #[serde(rename_all = "camelCase")]
+    Equivocation {
+        block_number: u32,
+        block_time: u64,
+        offense_event_block: u32,
+        proof_type: EquivocationType,
+    },
+}
+
+impl DisciplinaryEvent {
+    pub fn try_from(hist_tx: HistoricTransaction) -> Option<Self> {
+        Some(match hist_tx.data {
+            HistoricTransactionData::Basic(_) | HistoricTransactionData::Reward(_) => return None,
+            HistoricTransactionData::Penalize(PenalizeEvent {
+                slot,
+                offense_event_block,
+                ..
+            }) => DisciplinaryEvent::Penalize {
+                block_number: hist_tx.block_number,
+                block_time: hist_tx.block_time,
+                offense_event_block,
+                slot,
+            },
+            HistoricTransactionData::Jail(JailEvent {
+                slots,
+                offense_event_block,
+                ..
+            }) => DisciplinaryEvent::Jail {
+                block_number: hist_tx.block_number,
+                block_time: hist_tx.block_time,
+                offense_event_block,
+                first_slot_number: slots.start,
+                num_slots: slots.len() as u16,
+            },
+            HistoricTransactionData::Equivocation(EquivocationEvent { ref locator }) => {
+                DisciplinaryEvent::Equivocation {
+                    block_number: hist_tx.block_number,
+                    block_time: hist_tx.block_time,
+                    offense_event_block: locator.block_number(),
+                    proof_type: locator.into(),
+                }
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub address: Address,
@@ -924,6 +1061,192 @@ impl Staker {
     }
 }
 
+/// The share of a validator's batch reward attributed to a staker.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakerReward {
+    pub batch_number: u32,
+    /// The macro block that paid out the reward.
+    pub block_number: u32,
+    pub validator_address: Address,
+    pub validator_reward: Coin,
+    /// The total stake of the validator at the election block of the epoch.
+    pub validator_stake: Coin,
+    /// The active balance of the staker at the election block of the epoch.
+    pub active_balance: Coin,
+    pub value: Coin,
+}
+
+impl StakerReward {
+    pub fn from_staker_reward(reward: nimiq_blockchain::StakerReward) -> Self {
+        StakerReward {
+            batch_number: reward.batch_number,
+            block_number: reward.block_number,
+            validator_address: reward.validator_address,
+            validator_reward: reward.validator_reward,
+            validator_stake: reward.validator_stake,
+            active_balance: reward.active_balance,
+            value: reward.value,
+        }
+    }
+}
+
+/// The projected rewards for a stake delegated to a validator.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardEstimate {
+    pub validator_address: Address,
+    pub stake: Coin,
+    /// The total stake of all active validators, including the projected stake.
+    pub total_stake: Coin,
+    /// The expected duration of an epoch given the observed batch delays, in milliseconds.
+    pub epoch_duration: u64,
+    /// The share of the batch rewards that is paid out given the observed batch delays.
+    pub batch_delay_penalty: f64,
+    pub epoch_reward: Coin,
+    pub annual_reward: Coin,
+    /// The annual yield without compounding.
+    pub apr: f64,
+    /// The annual yield if the rewards of every epoch are staked again.
+    pub apy: f64,
+}
+
+impl RewardEstimate {
+    pub fn from_reward_estimate(
+        validator_address: Address,
+        estimate: nimiq_blockchain::reward_calculator::RewardEstimate,
+    ) -> Self {
+        RewardEstimate {
+            validator_address,
+            stake: estimate.stake,
+            total_stake: estimate.total_stake,
+            epoch_duration: estimate.epoch_duration,
+            batch_delay_penalty: estimate.batch_delay_penalty,
+            epoch_reward: estimate.epoch_reward,
+            annual_reward: estimate.annual_reward,
+            apr: estimate.apr,
+            apy: estimate.apy,
+        }
+    }
+}
+
+/// The tally of the `signal_data` of the validators elected at an election block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalTally {
+    pub block_number: u32,
+    pub total_slots: u16,
+    pub total_stake: Coin,
+    /// The signals ordered by the number of slots (most first).
+    pub signals: Vec<SignalCount>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalCount {
+    /// The signaled data, or null for validators that don't signal anything.
+    pub signal_data: Option<Blake2bHash>,
+    pub num_validators: u16,
+    pub num_slots: u16,
+    pub stake: Coin,
+}
+
+impl SignalTally {
+    pub fn from_signal_tally(tally: nimiq_blockchain::SignalTally) -> Self {
+        SignalTally {
+            block_number: tally.block_number,
+            total_slots: tally.total_slots(),
+            total_stake: tally.total_stake(),
+            signals: tally
+                .signals
+                .into_iter()
+                .map(|signal| SignalCount {
+                    signal_data: signal.signal_data,
+                    num_validators: signal.num_validators,
+                    num_slots: signal.num_slots,
+                    stake: signal.stake,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How agreement on a macro block was reached by Tendermint, as observed by our validator. All
+/// durations are in milliseconds.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroBlockTelemetry {
+    pub block_number: u32,
+    /// Whether a decision was reached. The instance can also end without one, e.g. when the macro
+    /// block was received from the network first.
+    pub decided: bool,
+    pub duration: u64,
+    pub num_timeouts: usize,
+    pub rounds: Vec<RoundTelemetry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundTelemetry {
+    pub round: u32,
+    /// The time after the start of the round at which its proposal was known.
+    pub proposal_after: Option<u64>,
+    pub propose: StepTelemetry,
+    pub prevote: StepTelemetry,
+    pub precommit: StepTelemetry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTelemetry {
+    pub duration: u64,
+    pub timeout: Option<u64>,
+    pub timed_out: bool,
+    pub contributors: usize,
+    /// The time after the start of the step at which 2f+1 contributors were aggregated.
+    pub two_f_plus_one_after: Option<u64>,
+}
+
+impl MacroBlockTelemetry {
+    pub fn from_telemetry(block_number: u32, telemetry: nimiq_tendermint::Telemetry) -> Self {
+        MacroBlockTelemetry {
+            block_number,
+            decided: telemetry.decided,
+            duration: telemetry.duration.as_millis() as u64,
+            num_timeouts: telemetry.num_timeouts(),
+            rounds: telemetry
+                .rounds
+                .into_iter()
+                .map(RoundTelemetry::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<nimiq_tendermint::RoundTelemetry> for RoundTelemetry {
+    fn from(round: nimiq_tendermint::RoundTelemetry) -> Self {
+        RoundTelemetry {
+            round: round.round,
+            proposal_after: round.proposal_after.map(|d| d.as_millis() as u64),
+            propose: round.propose.into(),
+            prevote: round.prevote.into(),
+            precommit: round.precommit.into(),
+        }
+    }
+}
+
+impl From<nimiq_tendermint::StepTelemetry> for StepTelemetry {
+    fn from(step: nimiq_tendermint::StepTelemetry) -> Self {
+        StepTelemetry {
+            duration: step.duration.as_millis() as u64,
+            timeout: step.timeout.map(|d| d.as_millis() as u64),
+            timed_out: step.timed_out,
+            contributors: step.contributors,
+            two_f_plus_one_after: step.two_f_plus_one_after.map(|d| d.as_millis() as u64),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Validator {
@@ -1361,3 +1684,59 @@ impl MempoolInfo {
         info
     }
 }
+
+/// The outcome of a configuration reload. Settings are named by their section and key in the
+/// config file, e.g. `network.desired_peer_count`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigReloadReport {
+    /// The settings that changed and are in effect now.
+    pub applied: Vec<String>,
+    /// The settings that changed but only take effect once the node is restarted.
+    pub requires_restart: Vec<String>,
+}
+
+/// Information about the node, for operators.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub version: String,
+    pub user_agent: String,
+    pub peer_id: String,
+    pub sync_mode: String,
+    /// The time since the node was started, in seconds.
+    pub uptime: u64,
+    /// The services the node provides to the network.
+    pub services: Vec<String>,
+    pub database: Option<DatabaseInfo>,
+}
+
+/// The size of the node's database, in bytes.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseInfo {
+    pub size_used: u64,
+    pub map_size: u64,
+    /// Whether the database is running out of space and its size should be increased.
+    pub need_resize: bool,
+}
+
+/// A URL that the node notifies of events. Its secret is not included.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// The addresses whose logs are sent.
+    pub addresses: Vec<Address>,
+    /// The types of the logs that are sent. All types are sent if empty.
+    pub log_types: Vec<LogType>,
+    /// The validators whose election, jailing and deactivation are sent.
+    pub validators: Vec<Address>,
+    /// Whether finalized macro blocks are sent.
+    pub finalized: bool,
+    /// Whether the webhook is set in the config file, in which case it can't be removed via RPC.
+    pub configured: bool,
+    /// The number of notifications that have not been delivered yet.
+    pub pending: u64,
+}
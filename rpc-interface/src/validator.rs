@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use nimiq_keys::Address;
 
-use crate::types::RPCResult;
+use crate::types::{EquivocationProof, MacroBlockTelemetry, RPCResult};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -28,4 +28,31 @@ pub trait ValidatorInterface {
 
     /// Returns if our validator is currently synced.
     async fn is_validator_synced(&mut self) -> RPCResult<bool, (), Self::Error>;
+
+    /// Verifies the given serialized equivocation proof (a fork, double proposal or double vote
+    /// proof) and, if it is valid, adds it to our pool and gossips it to the other validators so
+    /// that it gets included in a block.
+    async fn submit_equivocation_proof(
+        &mut self,
+        serialized_proof: String,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Returns the equivocation proofs that are waiting to be included in a block.
+    async fn get_pending_equivocation_proofs(
+        &mut self,
+    ) -> RPCResult<Vec<EquivocationProof>, (), Self::Error>;
+
+    /// Returns how agreement on the given macro block was reached by Tendermint, i.e. the rounds,
+    /// step durations and timeouts. Only the most recent macro blocks our validator took part in
+    /// are kept.
+    async fn get_macro_block_telemetry(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<MacroBlockTelemetry, (), Self::Error>;
+
+    /// Returns the Tendermint telemetry of the most recent macro blocks our validator took part
+    /// in, oldest first.
+    async fn get_recent_macro_block_telemetry(
+        &mut self,
+    ) -> RPCResult<Vec<MacroBlockTelemetry>, (), Self::Error>;
 }
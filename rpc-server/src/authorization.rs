@@ -0,0 +1,313 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use nimiq_hash::{Blake2bHash, Hash};
+use parking_lot::{Mutex, RwLock};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+use crate::wallets::UnlockedWallets;
+
+/// A pattern for RPC method names. `*` matches any sequence of characters. Method names and
+/// patterns are compared case-insensitively and ignoring underscores, so `get_*` matches
+/// `getBlockNumber` and `unlock_account` matches `unlockAccount`.
+#[derive(Clone, Debug)]
+pub struct MethodPattern {
+    pattern: String,
+}
+
+impl MethodPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: normalize(pattern),
+        }
+    }
+
+    pub fn matches(&self, method: &str) -> bool {
+        let method = normalize(method);
+        let mut parts = self.pattern.split('*');
+        // There is always a first part, which is empty if the pattern starts with `*`.
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = method.strip_prefix(first) else {
+            return false;
+        };
+
+        let parts: Vec<&str> = parts.collect();
+        let Some((last, middle)) = parts.split_last() else {
+            // No wildcard, so the whole method has to match.
+            return rest.is_empty();
+        };
+        for part in middle {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// A named set of RPC methods.
+#[derive(Clone, Debug)]
+pub struct Role {
+    pub name: String,
+    pub methods: Vec<MethodPattern>,
+}
+
+impl Role {
+    pub fn allows(&self, method: &str) -> bool {
+        self.methods.iter().any(|pattern| pattern.matches(method))
+    }
+}
+
+/// How a client authenticates itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Secret {
+    /// HTTP basic auth with the username and the hash of the password.
+    Basic {
+        username: String,
+        password_hash: Blake2bHash,
+    },
+    /// A bearer token, given by its hash.
+    Token { token_hash: Blake2bHash },
+}
+
+/// The maximum number of requests in a time window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+/// A token bucket, which allows bursts of up to the whole limit.
+#[derive(Debug)]
+struct RateLimiter {
+    limit: RateLimit,
+    available: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            available: limit.requests as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    fn try_acquire(&mut self, requests: u32) -> bool {
+        let now = Instant::now();
+        let refill = now.duration_since(self.updated).as_secs_f64() / self.limit.per.as_secs_f64()
+            * self.limit.requests as f64;
+        self.available = (self.available + refill).min(self.limit.requests as f64);
+        self.updated = now;
+
+        if self.available < requests as f64 {
+            return false;
+        }
+        self.available -= requests as f64;
+        true
+    }
+}
+
+/// A client of the RPC server, which may call the methods of its role. Each client has its own
+/// unlocked wallets, so it can only sign with the accounts it unlocked itself.
+#[derive(Clone)]
+pub struct Grant {
+    secret: Secret,
+    role: Arc<Role>,
+    rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+    unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+}
+
+impl std::fmt::Debug for Grant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Grant")
+            .field("secret", &self.secret)
+            .field("role", &self.role)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Grant {
+    pub fn new(secret: Secret, role: Arc<Role>, rate_limit: Option<RateLimit>) -> Self {
+        Self {
+            secret,
+            role,
+            rate_limiter: rate_limit.map(|limit| Arc::new(Mutex::new(RateLimiter::new(limit)))),
+            unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
+        }
+    }
+
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+
+    /// The wallets this client unlocked.
+    pub fn unlocked_wallets(&self) -> Arc<RwLock<UnlockedWallets>> {
+        Arc::clone(&self.unlocked_wallets)
+    }
+
+    /// Takes over the unlocked wallets of a previous grant of the same client, and its rate
+    /// limiter if the limit didn't change.
+    fn inherit(&mut self, previous: &Grant) {
+        self.unlocked_wallets = Arc::clone(&previous.unlocked_wallets);
+        if let (Some(rate_limiter), Some(previous)) = (&self.rate_limiter, &previous.rate_limiter) {
+            if rate_limiter.lock().limit == previous.lock().limit {
+                self.rate_limiter = Some(Arc::clone(previous));
+            }
+        }
+    }
+
+    fn matches(&self, credentials: &Presented) -> bool {
+        match (&self.secret, credentials) {
+            (
+                Secret::Basic {
+                    username,
+                    password_hash,
+                },
+                Presented::Basic {
+                    username: presented_username,
+                    password_hash: presented_hash,
+                },
+            ) => (username.as_bytes().ct_eq(presented_username.as_bytes())
+                & password_hash.ct_eq(presented_hash))
+            .into(),
+            (
+                Secret::Token { token_hash },
+                Presented::Token {
+                    token_hash: presented,
+                },
+            ) => token_hash.ct_eq(presented).into(),
+            _ => false,
+        }
+    }
+
+    /// Checks the rate limit for the given number of requests, counting them if they are allowed.
+    pub fn check_rate_limit(&self, requests: u32) -> Result<(), AuthorizationError> {
+        match &self.rate_limiter {
+            Some(rate_limiter) if !rate_limiter.lock().try_acquire(requests) => {
+                Err(AuthorizationError::RateLimited)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn authorize(&self, method: &str) -> Result<(), AuthorizationError> {
+        if !self.role.allows(method) {
+            return Err(AuthorizationError::MethodNotAllowed {
+                role: self.role.name.clone(),
+                method: method.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The credentials of a request.
+enum Presented {
+    Basic {
+        username: String,
+        password_hash: Blake2bHash,
+    },
+    Token {
+        token_hash: Blake2bHash,
+    },
+}
+
+impl Presented {
+    fn parse(authorization: &str) -> Option<Self> {
+        let (scheme, value) = authorization.trim().split_once(' ')?;
+        let value = value.trim();
+        if scheme.eq_ignore_ascii_case("Bearer") {
+            return Some(Presented::Token {
+                token_hash: value.hash(),
+            });
+        }
+        if scheme.eq_ignore_ascii_case("Basic") {
+            let decoded = String::from_utf8(STANDARD.decode(value).ok()?).ok()?;
+            let (username, password) = decoded.split_once(':')?;
+            return Some(Presented::Basic {
+                username: username.to_string(),
+                password_hash: password.hash(),
+            });
+        }
+        None
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AuthorizationError {
+    #[error("Missing or invalid credentials")]
+    Unauthenticated,
+
+    #[error("Role {role} is not allowed to call {method}")]
+    MethodNotAllowed { role: String, method: String },
+
+    #[error("Rate limit exceeded")]
+    RateLimited,
+}
+
+/// The clients allowed to use the RPC server.
+#[derive(Clone, Debug, Default)]
+pub struct Authorization {
+    grants: Vec<Grant>,
+}
+
+impl Authorization {
+    pub fn new(grants: Vec<Grant>) -> Self {
+        Self { grants }
+    }
+
+    pub fn grants(&self) -> &[Grant] {
+        &self.grants
+    }
+
+    /// Keeps the state of the clients of a previous authorization, e.g. of a server that is
+    /// restarted with new settings. Clients with the same secret keep their unlocked wallets and,
+    /// if their limit didn't change, their rate limit.
+    pub fn inherit(&mut self, previous: &Authorization) {
+        for grant in &mut self.grants {
+            if let Some(previous) = previous
+                .grants
+                .iter()
+                .find(|previous| previous.secret == grant.secret)
+            {
+                grant.inherit(previous);
+            }
+        }
+    }
+
+    /// Finds the client with the credentials of the given `Authorization` header.
+    pub fn authenticate(&self, authorization: Option<&str>) -> Result<&Grant, AuthorizationError> {
+        self.authenticate_index(authorization)
+            .map(|index| &self.grants[index])
+    }
+
+    /// Finds the index of the client with the credentials of the given `Authorization` header in
+    /// the [grants](Self::grants).
+    pub fn authenticate_index(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<usize, AuthorizationError> {
+        let presented = authorization
+            .and_then(Presented::parse)
+            .ok_or(AuthorizationError::Unauthenticated)?;
+        self.grants
+            .iter()
+            .position(|grant| grant.matches(&presented))
+            .ok_or(AuthorizationError::Unauthenticated)
+    }
+}
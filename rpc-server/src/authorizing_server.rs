@@ -0,0 +1,194 @@
+use std::{
+    collections::HashSet,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Incoming as IncomingBody, header, http::StatusCode, Method, Request, Response};
+use log::Instrument;
+use nimiq_jsonrpc_server::Dispatcher;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::{
+    authorization::{Authorization, AuthorizationError, Grant},
+    http::serve,
+};
+
+/// The JSON-RPC error code for calls to methods the role of the client doesn't allow.
+const METHOD_NOT_ALLOWED: i64 = -32001;
+
+/// An HTTP JSON-RPC server that authenticates every client by HTTP basic auth or a bearer token
+/// and only lets it call the methods of its role, within its rate limit. Every client has its own
+/// dispatcher, so state like unlocked wallets isn't shared between clients.
+pub struct AuthorizingServer<D: Dispatcher> {
+    bind_to: SocketAddr,
+    ip_whitelist: Option<HashSet<IpAddr>>,
+    inner: Arc<Inner<D>>,
+}
+
+struct Inner<D> {
+    authorization: Authorization,
+    /// The dispatchers of the clients, in the order of their grants.
+    dispatchers: Vec<Mutex<D>>,
+}
+
+impl<D: Dispatcher> AuthorizingServer<D> {
+    /// Creates the server, with a dispatcher for every client of the authorization.
+    pub fn new<F: Fn(&Grant) -> D>(
+        bind_to: SocketAddr,
+        ip_whitelist: Option<HashSet<IpAddr>>,
+        authorization: Authorization,
+        create_dispatcher: F,
+    ) -> Self {
+        let dispatchers = authorization
+            .grants()
+            .iter()
+            .map(|grant| Mutex::new(create_dispatcher(grant)))
+            .collect();
+        Self {
+            bind_to,
+            ip_whitelist,
+            inner: Arc::new(Inner {
+                authorization,
+                dispatchers,
+            }),
+        }
+    }
+
+    pub async fn run(&self) {
+        let inner = Arc::clone(&self.inner);
+        let handler = move |request: Request<IncomingBody>| {
+            let inner = Arc::clone(&inner);
+            async move { inner.handle(request).await }
+        };
+        serve(
+            "RPC server",
+            self.bind_to,
+            self.ip_whitelist.as_ref(),
+            Arc::new(handler),
+        )
+        .await
+    }
+}
+
+impl<D: Dispatcher> Inner<D> {
+    async fn handle(&self, request: Request<IncomingBody>) -> Response<Full<Bytes>> {
+        if request.method() != Method::POST {
+            return respond(StatusCode::METHOD_NOT_ALLOWED, Bytes::new());
+        }
+
+        let authorization = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        let index = match self.authorization.authenticate_index(authorization) {
+            Ok(index) => index,
+            Err(_) => {
+                let mut response = respond(StatusCode::UNAUTHORIZED, Bytes::new());
+                response.headers_mut().insert(
+                    header::WWW_AUTHENTICATE,
+                    header::HeaderValue::from_static("Basic realm=\"nimiq\", Bearer"),
+                );
+                return response;
+            }
+        };
+        let grant = &self.authorization.grants()[index];
+
+        let body = match request.into_body().collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(_) => return respond(StatusCode::BAD_REQUEST, Bytes::new()),
+        };
+        let (calls, batch) = match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Array(calls)) => (calls, true),
+            Ok(call) => (vec![call], false),
+            Err(error) => {
+                return respond_json(&error_response(
+                    Value::Null,
+                    -32700,
+                    format!("Parse error: {error}"),
+                ))
+            }
+        };
+
+        if let Err(error) = grant.check_rate_limit(calls.len() as u32) {
+            log::debug!(role = %grant.role().name, "RPC rate limit exceeded");
+            return respond(StatusCode::TOO_MANY_REQUESTS, error.to_string().into());
+        }
+
+        let mut responses = Vec::with_capacity(calls.len());
+        for call in calls {
+            let id = call.get("id").cloned().unwrap_or(Value::Null);
+            let method = call
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+
+            if let Err(error) = grant.authorize(&method) {
+                if let AuthorizationError::MethodNotAllowed { role, method } = &error {
+                    log::warn!(%role, %method, "Rejected RPC call that is not allowed");
+                }
+                responses.push(error_response(id, METHOD_NOT_ALLOWED, error.to_string()));
+                continue;
+            }
+
+            let call = match serde_json::from_value(call) {
+                Ok(call) => call,
+                Err(error) => {
+                    responses.push(error_response(
+                        id,
+                        -32600,
+                        format!("Invalid request: {error}"),
+                    ));
+                    continue;
+                }
+            };
+            let span = log::debug_span!("rpc_call", %method, role = %grant.role().name);
+            let response = self.dispatchers[index]
+                .lock()
+                .await
+                .dispatch(call, None, 0)
+                .instrument(span)
+                .await;
+            if let Some(response) = response {
+                match serde_json::to_value(response) {
+                    Ok(response) => responses.push(response),
+                    Err(error) => log::error!(%error, "Failed to serialize RPC response"),
+                }
+            }
+        }
+
+        match (batch, responses.len()) {
+            (_, 0) => respond(StatusCode::NO_CONTENT, Bytes::new()),
+            (false, _) => respond_json(&responses[0]),
+            (true, _) => respond_json(&Value::Array(responses)),
+        }
+    }
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": id,
+    })
+}
+
+fn respond(status: StatusCode, body: Bytes) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(body))
+        .unwrap()
+}
+
+fn respond_json(value: &Value) -> Response<Full<Bytes>> {
+    let mut response = respond(StatusCode::OK, serde_json::to_vec(value).unwrap().into());
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
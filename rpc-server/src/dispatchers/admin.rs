@@ -0,0 +1,230 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Instant};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use log::level_filters::LevelFilter;
+use nimiq_database::DatabaseProxy;
+use nimiq_keys::Address;
+use nimiq_network_interface::{
+    network::{CloseReason, Network as InterfaceNetwork},
+    peer_info::Services,
+    Multiaddr,
+};
+use nimiq_network_libp2p::{Network, PeerId};
+use nimiq_rpc_interface::{
+    admin::AdminInterface,
+    types::{ConfigReloadReport, DatabaseInfo, LogType, NodeInfo, RPCResult, Webhook},
+};
+use nimiq_utils::shutdown::{Shutdown, ShutdownReason};
+
+use crate::{
+    error::Error,
+    webhooks::{WebhookFilter, Webhooks},
+};
+
+/// Reloads the configuration of the node, returning the report or a description of the error.
+pub type ReloadConfig =
+    Arc<dyn Fn() -> BoxFuture<'static, Result<ConfigReloadReport, String>> + Send + Sync>;
+
+/// Sets the log level and the levels of specific targets, returning a description of the error
+/// if this fails.
+pub type SetLogLevel =
+    Arc<dyn Fn(LevelFilter, HashMap<String, LevelFilter>) -> Result<(), String> + Send + Sync>;
+
+/// Describes the node as it was configured on startup.
+#[derive(Clone, Debug)]
+pub struct NodeDescription {
+    pub version: String,
+    pub user_agent: String,
+    pub sync_mode: String,
+    pub services: Services,
+    pub started_at: Instant,
+}
+
+/// Operations of the node the RPC server can't perform by itself. They are not supported if not
+/// set.
+#[derive(Clone, Default)]
+pub struct AdminHooks {
+    pub reload_config: Option<ReloadConfig>,
+    pub set_log_level: Option<SetLogLevel>,
+}
+
+pub struct AdminDispatcher {
+    node: NodeDescription,
+    network: Arc<Network>,
+    database: Option<DatabaseProxy>,
+    shutdown: Shutdown,
+    hooks: AdminHooks,
+    webhooks: Option<Webhooks>,
+}
+
+impl AdminDispatcher {
+    pub fn new(
+        node: NodeDescription,
+        network: Arc<Network>,
+        database: Option<DatabaseProxy>,
+        shutdown: Shutdown,
+        hooks: AdminHooks,
+        webhooks: Option<Webhooks>,
+    ) -> Self {
+        AdminDispatcher {
+            node,
+            network,
+            database,
+            shutdown,
+            hooks,
+            webhooks,
+        }
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, Error> {
+    LevelFilter::from_str(level)
+        .map_err(|_| Error::InvalidArgument(format!("Invalid log level: {level}")))
+}
+
+fn parse_peer_id(peer_id: &str) -> Result<PeerId, Error> {
+    PeerId::from_str(peer_id)
+        .map_err(|_| Error::InvalidArgument(format!("Invalid peer ID: {peer_id}")))
+}
+
+#[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
+#[async_trait]
+impl AdminInterface for AdminDispatcher {
+    type Error = Error;
+
+    async fn get_node_info(&mut self) -> RPCResult<NodeInfo, (), Self::Error> {
+        let database = match &self.database {
+            Some(database) => {
+                let size = database.size()?;
+                Some(DatabaseInfo {
+                    size_used: size.used as u64,
+                    map_size: size.map_size as u64,
+                    need_resize: database.need_resize(0),
+                })
+            }
+            None => None,
+        };
+
+        Ok(NodeInfo {
+            version: self.node.version.clone(),
+            user_agent: self.node.user_agent.clone(),
+            peer_id: self.network.local_peer_id().to_string(),
+            sync_mode: self.node.sync_mode.clone(),
+            uptime: self.node.started_at.elapsed().as_secs(),
+            services: self
+                .node
+                .services
+                .iter_names()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            database,
+        }
+        .into())
+    }
+
+    async fn set_log_level(
+        &mut self,
+        level: String,
+        tags: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error> {
+        let set_log_level = self
+            .hooks
+            .set_log_level
+            .as_ref()
+            .ok_or(Error::NotImplemented)?;
+
+        let level = parse_level(&level)?;
+        let tags = tags
+            .iter()
+            .map(|tag| {
+                let (target, level) = tag.split_once(':').ok_or_else(|| {
+                    Error::InvalidArgument(format!("Log tag is missing separator: {tag}"))
+                })?;
+                Ok((target.to_string(), parse_level(level)?))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()?;
+
+        set_log_level(level, tags).map_err(Error::LogLevel)?;
+        Ok(().into())
+    }
+
+    async fn dial_peer(&mut self, peer: String) -> RPCResult<(), (), Self::Error> {
+        if peer.starts_with('/') {
+            let address = Multiaddr::from_str(&peer)
+                .map_err(|_| Error::InvalidArgument(format!("Invalid multiaddress: {peer}")))?;
+            self.network.dial_address(address).await?;
+        } else {
+            self.network.dial_peer(parse_peer_id(&peer)?).await?;
+        }
+        Ok(().into())
+    }
+
+    async fn disconnect_peer(&mut self, peer_id: String) -> RPCResult<(), (), Self::Error> {
+        let peer_id = parse_peer_id(&peer_id)?;
+        if !self.network.has_peer(peer_id) {
+            return Err(Error::PeerNotConnected(peer_id.to_string()));
+        }
+        self.network
+            .disconnect_peer(peer_id, CloseReason::Other)
+            .await;
+        Ok(().into())
+    }
+
+    async fn backup_database(&mut self, path: String) -> RPCResult<u64, (), Self::Error> {
+        let database = self.database.clone().ok_or(Error::NotImplemented)?;
+        // Mdbx transactions are bound to their thread and copying the tables takes a while, so
+        // the backup is taken on a blocking thread.
+        let copied = tokio::task::spawn_blocking(move || database.backup(path))
+            .await
+            .map_err(|error| Error::Backup(error.to_string()))??;
+        Ok(copied.into())
+    }
+
+    async fn shutdown(&mut self) -> RPCResult<(), (), Self::Error> {
+        if !self.shutdown.request(ShutdownReason::Rpc) {
+            return Err(Error::ShutdownInProgress);
+        }
+        Ok(().into())
+    }
+
+    async fn reload_config(&mut self) -> RPCResult<ConfigReloadReport, (), Self::Error> {
+        let reload_config = self
+            .hooks
+            .reload_config
+            .as_ref()
+            .ok_or(Error::ConfigReloadUnsupported)?;
+        let report = reload_config().await.map_err(Error::ConfigReload)?;
+        Ok(report.into())
+    }
+
+    async fn add_webhook(
+        &mut self,
+        url: String,
+        secret: String,
+        addresses: Vec<Address>,
+        log_types: Vec<LogType>,
+        validators: Vec<Address>,
+        finalized: bool,
+    ) -> RPCResult<String, (), Self::Error> {
+        let webhooks = self.webhooks.as_ref().ok_or(Error::NotImplemented)?;
+        let filter = WebhookFilter {
+            addresses,
+            log_types,
+            validators,
+            finalized,
+        };
+        Ok(webhooks.add(url, secret, filter).await?.into())
+    }
+
+    async fn remove_webhook(&mut self, id: String) -> RPCResult<(), (), Self::Error> {
+        let webhooks = self.webhooks.as_ref().ok_or(Error::NotImplemented)?;
+        webhooks.remove(&id).await?;
+        Ok(().into())
+    }
+
+    async fn get_webhooks(&mut self) -> RPCResult<Vec<Webhook>, (), Self::Error> {
+        let webhooks = self.webhooks.as_ref().ok_or(Error::NotImplemented)?;
+        Ok(webhooks.list().into())
+    }
+}
@@ -1,17 +1,19 @@
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
+use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
+use nimiq_primitives::{coin::Coin, key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
         is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        DisciplinaryEvent, ExecutedTransaction, HistoryRetention, Inherent, LogType,
+        PenalizedSlots, RPCData, RPCResult, RewardEstimate, SignalTally, Slot, Staker,
+        StakerReward, TransactionInclusionProof, Validator,
     },
 };
 use tokio_stream::wrappers::BroadcastStream;
@@ -28,6 +30,15 @@ impl BlockchainDispatcher {
     }
 }
 
+/// Returns the blockchain state of the macro block at the given block number. This is used as the
+/// state of archive queries.
+fn get_archive_state(blockchain: &Blockchain, block_number: u32) -> Result<BlockchainState, Error> {
+    let block = blockchain
+        .get_block_at(block_number, false)
+        .map_err(|_| Error::BlockNotFound(block_number))?;
+    Ok(BlockchainState::new(block_number, block.hash()))
+}
+
 /// Tries to fetch a block given its hash. It has an option to include the transactions in the
 /// block, which defaults to false.
 /// This function requires the read lock acquisition prior to its execution
@@ -179,6 +190,18 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_transaction_inclusion_proof(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionInclusionProof, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let proof = blockchain.prove_transaction_inclusion(&hash)?;
+            Ok(TransactionInclusionProof::from_proof(hash, &proof).into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_transactions_by_block_number(
         &mut self,
         block_number: u32,
@@ -365,6 +388,28 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_history_retention(
+        &mut self,
+    ) -> RPCResult<HistoryRetention, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let first_epoch_number = blockchain.first_retained_history_epoch();
+            let first_block_number = Policy::first_block_of(first_epoch_number)
+                .ok_or(Error::InvalidArgument("Invalid epoch number".to_string()))?;
+
+            Ok(RPCData::with_blockchain(
+                HistoryRetention {
+                    retention_epochs: blockchain.history_retention_epochs(),
+                    first_epoch_number,
+                    first_block_number,
+                },
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_account_by_address(
         &mut self,
         address: Address,
@@ -484,6 +529,24 @@ impl BlockchainInterface for BlockchainDispatcher {
         get_validator_by_address(&self.blockchain.read(), &address)
     }
 
+    async fn get_disciplinary_record_by_address(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+    ) -> RPCResult<Vec<DisciplinaryEvent>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .history_store
+                .get_punishments_by_address(&address, max.unwrap_or(500), None)
+                .into_iter()
+                .filter_map(DisciplinaryEvent::try_from)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_validators(&mut self) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
         let blockchain_proxy = self.blockchain.read();
 
@@ -552,6 +615,163 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_account_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Account, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let account = blockchain.get_account_at(&address, block_number)?;
+            Ok(Account::from_account_with_state(
+                address,
+                account,
+                get_archive_state(blockchain, block_number)?,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_validators_at(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let validators = blockchain.get_validators_at(block_number)?;
+            Ok(RPCData::new(
+                validators.iter().map(Validator::from_validator).collect(),
+                get_archive_state(blockchain, block_number)?,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_validator_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Validator, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let validator = blockchain
+                .get_validator_at(&address, block_number)?
+                .ok_or(Error::ValidatorNotFound(address))?;
+            Ok(RPCData::new(
+                Validator::from_validator(&validator),
+                get_archive_state(blockchain, block_number)?,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_stakers_by_validator_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let stakers = blockchain.get_stakers_for_validator_at(&address, block_number)?;
+            Ok(RPCData::new(
+                stakers.iter().map(Staker::from_staker).collect(),
+                get_archive_state(blockchain, block_number)?,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_staker_by_address_at(
+        &mut self,
+        address: Address,
+        block_number: u32,
+    ) -> RPCResult<Staker, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let staker = blockchain
+                .get_staker_at(&address, block_number)?
+                .ok_or(Error::StakerNotFound(address))?;
+            Ok(RPCData::new(
+                Staker::from_staker(&staker),
+                get_archive_state(blockchain, block_number)?,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_staker_rewards(
+        &mut self,
+        address: Address,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<StakerReward>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let rewards = blockchain.get_staker_rewards(&address, from_epoch, to_epoch)?;
+            Ok(rewards
+                .into_iter()
+                .map(StakerReward::from_staker_reward)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_staking_reward_estimate(
+        &mut self,
+        validator_address: Address,
+        stake: Coin,
+    ) -> RPCResult<RewardEstimate, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let estimate = blockchain
+                .estimate_staking_rewards(&validator_address, stake)
+                .ok_or_else(|| Error::ValidatorNotFound(validator_address.clone()))?;
+            Ok(RPCData::with_blockchain(
+                RewardEstimate::from_reward_estimate(validator_address, estimate),
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_signal_tally(
+        &mut self,
+        block_number: Option<u32>,
+    ) -> RPCResult<SignalTally, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let block_number =
+                block_number.unwrap_or_else(|| blockchain.election_head().block_number());
+            let tally = blockchain.get_signal_tally(block_number)?;
+            Ok(SignalTally::from_signal_tally(tally).into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_signal_tallies(
+        &mut self,
+        from_epoch: u32,
+        to_epoch: u32,
+    ) -> RPCResult<Vec<SignalTally>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            Ok(blockchain
+                .get_signal_tallies(from_epoch, to_epoch)
+                .into_iter()
+                .map(SignalTally::from_signal_tally)
+                .collect::<Vec<_>>()
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     #[stream]
     async fn subscribe_for_head_block(
         &mut self,
@@ -2,8 +2,11 @@ use std::sync::atomic::Ordering;
 
 use async_trait::async_trait;
 use nimiq_keys::Address;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
-use nimiq_serde::Serialize;
+use nimiq_rpc_interface::{
+    types::{EquivocationProof, MacroBlockTelemetry, RPCResult},
+    validator::ValidatorInterface,
+};
+use nimiq_serde::{Deserialize, Serialize};
 use nimiq_validator::validator::ValidatorProxy;
 
 use crate::error::Error;
@@ -64,4 +67,65 @@ impl ValidatorInterface for ValidatorDispatcher {
         let is_synced = state.consensus_established && state.validity_window_synced;
         Ok(is_synced.into())
     }
+
+    async fn submit_equivocation_proof(
+        &mut self,
+        serialized_proof: String,
+    ) -> RPCResult<(), (), Self::Error> {
+        let proof: nimiq_block::EquivocationProof =
+            Deserialize::deserialize_from_vec(&hex::decode(serialized_proof)?)?;
+
+        self.validator
+            .blockchain
+            .read()
+            .verify_equivocation_proof(&proof)?;
+
+        log::debug!(locator = ?proof.locator(), "Equivocation proof submitted.");
+        self.validator
+            .equivocation_proof_tx
+            .send(proof)
+            .map_err(|_| Error::ValidatorNotRunning)?;
+        Ok(().into())
+    }
+
+    async fn get_pending_equivocation_proofs(
+        &mut self,
+    ) -> RPCResult<Vec<EquivocationProof>, (), Self::Error> {
+        Ok(self
+            .validator
+            .consensus_state
+            .read()
+            .pending_equivocation_proofs()
+            .into_iter()
+            .map(EquivocationProof::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn get_macro_block_telemetry(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<MacroBlockTelemetry, (), Self::Error> {
+        let telemetry = self
+            .validator
+            .tendermint_telemetry
+            .get(block_number)
+            .ok_or(Error::MacroBlockTelemetryNotFound(block_number))?;
+        Ok(MacroBlockTelemetry::from_telemetry(telemetry.block_number, telemetry.telemetry).into())
+    }
+
+    async fn get_recent_macro_block_telemetry(
+        &mut self,
+    ) -> RPCResult<Vec<MacroBlockTelemetry>, (), Self::Error> {
+        Ok(self
+            .validator
+            .tendermint_telemetry
+            .recent()
+            .into_iter()
+            .map(|telemetry| {
+                MacroBlockTelemetry::from_telemetry(telemetry.block_number, telemetry.telemetry)
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
 }
@@ -34,6 +34,18 @@ impl WalletDispatcher {
             unlocked_wallets: Arc::new(RwLock::new(UnlockedWallets::default())),
         }
     }
+
+    /// Creates a dispatcher sharing the given unlocked wallets, e.g. with the dispatcher of a
+    /// previous RPC server.
+    pub fn with_unlocked_wallets(
+        wallet_store: Arc<WalletStore>,
+        unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
+    ) -> Self {
+        Self {
+            wallet_store,
+            unlocked_wallets,
+        }
+    }
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
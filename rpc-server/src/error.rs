@@ -9,6 +9,9 @@ pub enum Error {
     #[error("{0}")]
     Core(#[from] nimiq_rpc_interface::error::Error),
 
+    #[error("{0}")]
+    Blockchain(#[from] nimiq_blockchain_interface::BlockchainError),
+
     #[error("{0}")]
     NetworkError(#[from] nimiq_network_libp2p::NetworkError),
 
@@ -69,6 +72,9 @@ pub enum Error {
     #[error("Serialization error: {0}")]
     Serialization(#[from] nimiq_serde::DeserializeError),
 
+    #[error("{0}")]
+    Webhook(#[from] crate::webhooks::WebhookError),
+
     #[error("{0}")]
     Argon2(#[from] nimiq_hash::argon2kdf::Argon2Error),
 
@@ -84,6 +90,33 @@ pub enum Error {
     #[error("No consensus")]
     NoConsensus,
 
+    #[error("Validator is not running")]
+    ValidatorNotRunning,
+
+    #[error("Shutdown is already in progress")]
+    ShutdownInProgress,
+
+    #[error("Reloading the configuration is not supported by this node")]
+    ConfigReloadUnsupported,
+
+    #[error("Failed to reload configuration: {0}")]
+    ConfigReload(String),
+
+    #[error("Failed to set log level: {0}")]
+    LogLevel(String),
+
+    #[error("Peer is not connected: {0}")]
+    PeerNotConnected(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] nimiq_database::Error),
+
+    #[error("Failed to back up database: {0}")]
+    Backup(String),
+
+    #[error("No Tendermint telemetry for macro block: {0}")]
+    MacroBlockTelemetryNotFound(u32),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -0,0 +1,69 @@
+use std::{
+    collections::HashSet,
+    error::Error as StdError,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use hyper::{
+    body::{Body, Incoming as IncomingBody},
+    server::conn::http1,
+    service::service_fn,
+    Request, Response,
+};
+use hyper_util::rt::tokio::TokioIo;
+use tokio::net::TcpListener;
+
+/// Serves HTTP/1 requests with the given handler until the listener fails to bind. Connections
+/// from IP addresses that are not in the whitelist, if there is one, are closed right away.
+pub(crate) async fn serve<H, F, B>(
+    name: &str,
+    bind_to: SocketAddr,
+    ip_whitelist: Option<&HashSet<IpAddr>>,
+    handler: Arc<H>,
+) where
+    H: Fn(Request<IncomingBody>) -> F + Send + Sync + 'static,
+    F: Future<Output = Response<B>> + Send + 'static,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    let listener = match TcpListener::bind(&bind_to).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            log::error!(%error, %bind_to, "Failed to bind {}", name);
+            return;
+        }
+    };
+
+    loop {
+        let (stream, remote) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(error) => {
+                log::warn!(%error, "Failed to accept {} connection", name);
+                continue;
+            }
+        };
+        if let Some(ip_whitelist) = ip_whitelist {
+            if !ip_whitelist.contains(&remote.ip()) {
+                log::debug!(%remote, "Rejected {} connection from IP that is not allowed", name);
+                continue;
+            }
+        }
+
+        let handler = Arc::clone(&handler);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let response = handler(request);
+                async move { Ok::<_, hyper::Error>(response.await) }
+            });
+            if let Err(error) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                log::debug!(%error, %remote, "HTTP connection error");
+            }
+        });
+    }
+}
@@ -1,6 +1,11 @@
 pub use error::Error;
 pub use nimiq_jsonrpc_server::{Config, Server};
 
+pub mod authorization;
+pub mod authorizing_server;
 pub mod dispatchers;
 pub mod error;
+mod http;
+pub mod rest;
 pub mod wallets;
+pub mod webhooks;
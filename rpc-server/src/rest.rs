@@ -0,0 +1,313 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt};
+use http_body_util::{combinators::UnsyncBoxBody, BodyExt, Full, StreamBody};
+use hyper::{
+    body::{Frame, Incoming as IncomingBody},
+    header,
+    http::StatusCode,
+    Method, Request, Response,
+};
+use log::Instrument;
+use nimiq_rpc_interface::{
+    blockchain::BlockchainInterface,
+    mempool::MempoolInterface,
+    rest::match_route,
+    types::{RPCData, RPCResult},
+};
+use percent_encoding::percent_decode_str;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::{
+    authorization::{Authorization, Role},
+    dispatchers::{BlockchainDispatcher, MempoolDispatcher},
+    error::Error,
+    http::serve,
+};
+
+type Body = UnsyncBoxBody<Bytes, Infallible>;
+
+/// Serves the blockchain and the mempool as resources over HTTP, next to the JSON-RPC server.
+/// The routes are listed in [`nimiq_rpc_interface::rest::REST_ROUTES`]. Every route is
+/// authorized like a call to the RPC method serving it.
+pub struct RestServer {
+    bind_to: SocketAddr,
+    ip_whitelist: Option<HashSet<IpAddr>>,
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    blockchain: Mutex<BlockchainDispatcher>,
+    mempool: Option<Mutex<MempoolDispatcher>>,
+    authorization: Option<Authorization>,
+    allowed_methods: Option<Role>,
+}
+
+impl RestServer {
+    /// Creates the REST gateway. If an authorization is given, only its clients may use the
+    /// gateway. If allowed methods are given, only the routes served by these methods are
+    /// available.
+    pub fn new(
+        bind_to: SocketAddr,
+        ip_whitelist: Option<HashSet<IpAddr>>,
+        blockchain: BlockchainDispatcher,
+        mempool: Option<MempoolDispatcher>,
+        authorization: Option<Authorization>,
+        allowed_methods: Option<Role>,
+    ) -> Self {
+        Self {
+            bind_to,
+            ip_whitelist,
+            inner: Arc::new(Inner {
+                blockchain: Mutex::new(blockchain),
+                mempool: mempool.map(Mutex::new),
+                authorization,
+                allowed_methods,
+            }),
+        }
+    }
+
+    pub async fn run(&self) {
+        log::info!("Initializing REST gateway: {}", self.bind_to);
+        let inner = Arc::clone(&self.inner);
+        let handler = move |request: Request<IncomingBody>| {
+            let inner = Arc::clone(&inner);
+            async move { inner.handle(request).await }
+        };
+        serve(
+            "REST gateway",
+            self.bind_to,
+            self.ip_whitelist.as_ref(),
+            Arc::new(handler),
+        )
+        .await
+    }
+}
+
+impl Inner {
+    async fn handle(&self, request: Request<IncomingBody>) -> Response<Body> {
+        if request.method() != Method::GET {
+            return respond(StatusCode::METHOD_NOT_ALLOWED, String::new());
+        }
+        let Some((method, params)) = match_route(request.uri().path()) else {
+            return respond(StatusCode::NOT_FOUND, "No such resource".to_string());
+        };
+
+        if let Some(authorization) = &self.authorization {
+            let authorization_header = request
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok());
+            let grant = match authorization.authenticate(authorization_header) {
+                Ok(grant) => grant,
+                Err(error) => return respond(StatusCode::UNAUTHORIZED, error.to_string()),
+            };
+            if let Err(error) = grant.check_rate_limit(1) {
+                return respond(StatusCode::TOO_MANY_REQUESTS, error.to_string());
+            }
+            if let Err(error) = grant.authorize(method) {
+                return respond(StatusCode::FORBIDDEN, error.to_string());
+            }
+        }
+        if let Some(allowed_methods) = &self.allowed_methods {
+            if !allowed_methods.allows(method) {
+                return respond(StatusCode::FORBIDDEN, format!("{method} is not allowed"));
+            }
+        }
+
+        let mut params: HashMap<String, String> = params
+            .into_iter()
+            .map(|(name, value)| {
+                let value = percent_decode_str(&value).decode_utf8_lossy().into_owned();
+                (name.to_string(), value)
+            })
+            .collect();
+        let query = request.uri().query().unwrap_or_default();
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            params
+                .entry(name.into_owned())
+                .or_insert_with(|| value.into_owned());
+        }
+
+        let span = log::debug_span!("rest_call", method);
+        match self.call(method, &Params(params)).instrument(span).await {
+            Ok(response) => response,
+            Err(error) => respond(status(&error), error.to_string()),
+        }
+    }
+
+    async fn call(&self, method: &str, params: &Params) -> Result<Response<Body>, Error> {
+        let mut blockchain = self.blockchain.lock().await;
+        match method {
+            "getLatestBlock" => json(
+                blockchain
+                    .get_latest_block(params.optional("includeBody")?)
+                    .await,
+            ),
+            "getBlockByNumber" => json(
+                blockchain
+                    .get_block_by_number(
+                        params.required("blockNumber")?,
+                        params.optional("includeBody")?,
+                    )
+                    .await,
+            ),
+            "getTransactionByHash" => json(
+                blockchain
+                    .get_transaction_by_hash(params.required_json("hash")?)
+                    .await,
+            ),
+            "getAccountByAddress" => json(
+                blockchain
+                    .get_account_by_address(params.required_json("address")?)
+                    .await,
+            ),
+            "getActiveValidators" => json(blockchain.get_active_validators().await),
+            "subscribeForHeadBlock" => Ok(events(
+                blockchain
+                    .subscribe_for_head_block(params.optional("includeBody")?)
+                    .await?,
+            )),
+            "subscribeForLogsByAddressesAndTypes" => Ok(events(
+                blockchain
+                    .subscribe_for_logs_by_addresses_and_types(
+                        params.list("addresses")?,
+                        params.list("logTypes")?,
+                    )
+                    .await?,
+            )),
+            "mempool" => json(self.mempool()?.lock().await.mempool().await),
+            "mempoolContent" => json(
+                self.mempool()?
+                    .lock()
+                    .await
+                    .mempool_content(params.optional("includeTransactions")?.unwrap_or(false))
+                    .await,
+            ),
+            _ => Err(Error::NotImplemented),
+        }
+    }
+
+    fn mempool(&self) -> Result<&Mutex<MempoolDispatcher>, Error> {
+        self.mempool.as_ref().ok_or(Error::NotImplemented)
+    }
+}
+
+/// The path and query parameters of a request.
+struct Params(HashMap<String, String>);
+
+impl Params {
+    fn optional<T: FromStr>(&self, name: &str) -> Result<Option<T>, Error> {
+        self.0
+            .get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| Error::InvalidArgument(format!("Invalid {name}: {value}")))
+            })
+            .transpose()
+    }
+
+    fn required<T: FromStr>(&self, name: &str) -> Result<T, Error> {
+        self.optional(name)?
+            .ok_or_else(|| Error::InvalidArgument(format!("Missing {name}")))
+    }
+
+    /// Parses a parameter the way it would be parsed from a JSON string.
+    fn required_json<T: DeserializeOwned>(&self, name: &str) -> Result<T, Error> {
+        let value = self
+            .0
+            .get(name)
+            .ok_or_else(|| Error::InvalidArgument(format!("Missing {name}")))?;
+        parse_json(name, value)
+    }
+
+    /// Parses a comma-separated list of values the way they would be parsed from JSON strings.
+    fn list<T: DeserializeOwned>(&self, name: &str) -> Result<Vec<T>, Error> {
+        self.0
+            .get(name)
+            .map(|values| {
+                values
+                    .split(',')
+                    .filter(|value| !value.is_empty())
+                    .map(|value| parse_json(name, value))
+                    .collect()
+            })
+            .unwrap_or_else(|| Ok(vec![]))
+    }
+}
+
+fn parse_json<T: DeserializeOwned>(name: &str, value: &str) -> Result<T, Error> {
+    serde_json::from_value(Value::String(value.to_string()))
+        .map_err(|_| Error::InvalidArgument(format!("Invalid {name}: {value}")))
+}
+
+fn status(error: &Error) -> StatusCode {
+    match error {
+        Error::BlockNotFound(_)
+        | Error::BlockNotFoundByHash(_)
+        | Error::TransactionNotFound(_)
+        | Error::AccountNotFound(_)
+        | Error::ValidatorNotFound(_)
+        | Error::StakerNotFound(_) => StatusCode::NOT_FOUND,
+        Error::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+        Error::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn json<T: Serialize, S: Serialize>(
+    result: RPCResult<T, S, Error>,
+) -> Result<Response<Body>, Error> {
+    let body = match serde_json::to_vec(&result?) {
+        Ok(body) => body,
+        Err(error) => {
+            log::error!(%error, "Failed to serialize response");
+            return Ok(respond(StatusCode::INTERNAL_SERVER_ERROR, String::new()));
+        }
+    };
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(body)).boxed_unsync())
+        .unwrap())
+}
+
+/// Sends the items of the stream as Server-Sent Events.
+fn events<T: Serialize + Send + 'static, S: Serialize + Send + 'static>(
+    stream: BoxStream<'static, RPCData<T, S>>,
+) -> Response<Body> {
+    let events = stream.filter_map(|item| async move {
+        match serde_json::to_string(&item) {
+            Ok(data) => Some(Ok::<_, Infallible>(Frame::data(Bytes::from(format!(
+                "data: {data}\n\n"
+            ))))),
+            Err(error) => {
+                log::error!(%error, "Failed to serialize event");
+                None
+            }
+        }
+    });
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(StreamBody::new(events).boxed_unsync())
+        .unwrap()
+}
+
+fn respond(status: StatusCode, message: String) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::from(message)).boxed_unsync())
+        .unwrap()
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use futures::{stream, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::{header::CONTENT_TYPE, Client};
+use sha2::Sha256;
+
+use super::{store::Notification, unix_millis, Webhooks};
+
+/// The header containing the Unix timestamp, in seconds, at which a notification was sent.
+pub const TIMESTAMP_HEADER: &str = "X-Nimiq-Timestamp";
+
+/// The header containing the signature of a notification, as `sha256=` followed by the hex
+/// encoded signature.
+pub const SIGNATURE_HEADER: &str = "X-Nimiq-Signature";
+
+/// The time after which a delivery attempt fails if the webhook doesn't respond.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum number of notifications that are delivered at the same time.
+const MAX_CONCURRENT_DELIVERIES: usize = 16;
+
+/// The maximum number of due notifications that are loaded at once. The outcome of their delivery
+/// is stored together.
+const MAX_DUE_NOTIFICATIONS: usize = 256;
+
+/// The number of failed attempts after which a notification is dropped. With the backoff, this
+/// is about a day after the first attempt.
+const MAX_ATTEMPTS: u32 = 36;
+
+/// The maximum time between two attempts to deliver a notification.
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// Signs the body of a notification sent at the given timestamp, in seconds. The signature is
+/// the HMAC-SHA256 of `{timestamp}.{body}` with the secret of the webhook as key. Receivers
+/// should compute it the same way and reject notifications with old timestamps to prevent
+/// replays.
+pub fn sign(secret: &str, timestamp: u64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// The time to wait after the given number of failed attempts.
+fn backoff(attempts: u32) -> Duration {
+    Duration::from_secs(1u64 << attempts.min(12)).min(MAX_BACKOFF)
+}
+
+impl Webhooks {
+    /// Delivers the queued notifications, retrying failed deliveries with exponential backoff.
+    pub(super) async fn deliver(&self) {
+        let client = Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .expect("Failed to create HTTP client for webhooks");
+
+        loop {
+            let now = unix_millis();
+            let due = self.inner.store.due(now, MAX_DUE_NOTIFICATIONS);
+
+            if !due.is_empty() {
+                let attempts = stream::iter(due)
+                    .map(|(key, notification)| self.send(&client, key, notification))
+                    .buffer_unordered(MAX_CONCURRENT_DELIVERIES)
+                    .collect::<Vec<_>>()
+                    .await;
+                self.write_store(move |store| store.complete(attempts))
+                    .await;
+                continue;
+            }
+
+            let wait = self
+                .inner
+                .store
+                .next_attempt()
+                .map_or(MAX_BACKOFF, |next_attempt| {
+                    Duration::from_millis(next_attempt.saturating_sub(now))
+                });
+            tokio::select! {
+                _ = self.inner.queued.notified() => {},
+                _ = tokio::time::sleep(wait) => {},
+            }
+        }
+    }
+
+    /// Attempts to deliver a notification. Returns the notification with its next attempt if it
+    /// should be retried.
+    async fn send(
+        &self,
+        client: &Client,
+        key: u64,
+        mut notification: Notification,
+    ) -> (u64, Option<Notification>) {
+        let Some(secret) = self.secret(&notification) else {
+            // The webhook was removed or changed.
+            return (key, None);
+        };
+
+        let timestamp = unix_millis() / 1000;
+        let signature = sign(&secret, timestamp, &notification.body);
+        let result = client
+            .post(&notification.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+            .body(notification.body.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(_) => (key, None),
+            Err(error) => {
+                notification.attempts += 1;
+                if notification.attempts >= MAX_ATTEMPTS {
+                    log::warn!(
+                        %error,
+                        webhook = %notification.webhook,
+                        attempts = notification.attempts,
+                        "Dropping webhook notification that could not be delivered"
+                    );
+                    return (key, None);
+                }
+
+                log::debug!(
+                    %error,
+                    webhook = %notification.webhook,
+                    attempts = notification.attempts,
+                    "Failed to deliver webhook notification"
+                );
+                notification.next_attempt =
+                    unix_millis() + backoff(notification.attempts).as_millis() as u64;
+                (key, Some(notification))
+            }
+        }
+    }
+
+    /// Returns the secret of the webhook the notification is for, if it still exists with the
+    /// same URL.
+    fn secret(&self, notification: &Notification) -> Option<String> {
+        self.inner
+            .webhooks
+            .read()
+            .iter()
+            .find(|webhook| webhook.id == notification.webhook && webhook.url == notification.url)
+            .map(|webhook| webhook.secret.clone())
+    }
+}
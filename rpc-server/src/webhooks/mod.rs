@@ -0,0 +1,509 @@
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_account::{BlockLog as BBlockLog, Log, TransactionLog};
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
+use nimiq_database::DatabaseProxy;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_keys::Address;
+use nimiq_rpc_interface::types::{
+    is_of_log_type_and_related_to_addresses, BlockLog, LogType, Webhook,
+};
+use parking_lot::RwLock;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{sync::Notify, task::spawn_blocking};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+
+pub use self::delivery::{sign, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use self::store::{Notification, Registration, WebhookStore};
+
+mod delivery;
+mod store;
+
+/// What a webhook is notified of.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookFilter {
+    /// The addresses whose logs are sent.
+    pub addresses: Vec<Address>,
+    /// The types of the logs that are sent. All types are sent if empty.
+    pub log_types: Vec<LogType>,
+    /// The validators whose election, jailing and deactivation are sent.
+    pub validators: Vec<Address>,
+    /// Whether finalized macro blocks are sent.
+    pub finalized: bool,
+}
+
+impl WebhookFilter {
+    fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.validators.is_empty() && !self.finalized
+    }
+
+    fn retain_logs(&self, logs: &mut Vec<Log>) {
+        logs.retain(|log| {
+            is_of_log_type_and_related_to_addresses(log, &self.addresses, &self.log_types)
+        });
+    }
+
+    fn retain_tx_logs(&self, tx_logs: Vec<TransactionLog>) -> Vec<TransactionLog> {
+        tx_logs
+            .into_iter()
+            .filter_map(|mut tx_log| {
+                self.retain_logs(&mut tx_log.logs);
+                (!tx_log.logs.is_empty()).then_some(tx_log)
+            })
+            .collect()
+    }
+
+    /// Returns the events of an applied or reverted block this webhook is notified of.
+    pub fn block_log_events(&self, block_log: &BBlockLog) -> Vec<WebhookEvent> {
+        let mut events = vec![];
+
+        if !self.addresses.is_empty() {
+            let event = match block_log.clone() {
+                BBlockLog::AppliedBlock {
+                    mut inherent_logs,
+                    block_hash,
+                    block_number,
+                    timestamp,
+                    tx_logs,
+                    ..
+                } => {
+                    self.retain_logs(&mut inherent_logs);
+                    let tx_logs = self.retain_tx_logs(tx_logs);
+                    (!inherent_logs.is_empty() || !tx_logs.is_empty()).then_some(
+                        WebhookEvent::Logs {
+                            block_number,
+                            block_hash,
+                            logs: BlockLog::AppliedBlock {
+                                inherent_logs,
+                                timestamp,
+                                tx_logs,
+                            },
+                        },
+                    )
+                }
+                BBlockLog::RevertedBlock {
+                    mut inherent_logs,
+                    block_hash,
+                    block_number,
+                    tx_logs,
+                    ..
+                } => {
+                    self.retain_logs(&mut inherent_logs);
+                    let tx_logs = self.retain_tx_logs(tx_logs);
+                    (!inherent_logs.is_empty() || !tx_logs.is_empty()).then_some(
+                        WebhookEvent::Logs {
+                            block_number,
+                            block_hash,
+                            logs: BlockLog::RevertedBlock {
+                                inherent_logs,
+                                tx_logs,
+                            },
+                        },
+                    )
+                }
+            };
+            events.extend(event);
+        }
+
+        if let BBlockLog::AppliedBlock {
+            inherent_logs,
+            block_hash,
+            block_number,
+            tx_logs,
+            ..
+        } = block_log
+        {
+            let logs = inherent_logs
+                .iter()
+                .chain(tx_logs.iter().flat_map(|tx_log| tx_log.logs.iter()));
+            for log in logs {
+                match log {
+                    Log::JailValidator {
+                        validator_address,
+                        jailed_from,
+                    } if self.validators.contains(validator_address) => {
+                        events.push(WebhookEvent::ValidatorJailed {
+                            validator_address: validator_address.clone(),
+                            jailed_from: *jailed_from,
+                            block_number: *block_number,
+                            block_hash: block_hash.clone(),
+                        })
+                    }
+                    Log::DeactivateValidator {
+                        validator_address,
+                        inactive_from,
+                    } if self.validators.contains(validator_address) => {
+                        events.push(WebhookEvent::ValidatorDeactivated {
+                            validator_address: validator_address.clone(),
+                            inactive_from: *inactive_from,
+                            block_number: *block_number,
+                            block_hash: block_hash.clone(),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Returns the events of a finalized macro block this webhook is notified of.
+    pub fn finalized_block_events(&self, block: &Block) -> Vec<WebhookEvent> {
+        let mut events = vec![];
+        if self.finalized {
+            events.push(WebhookEvent::Finalized {
+                block_number: block.block_number(),
+                block_hash: block.hash(),
+                epoch: block.epoch_number(),
+                election: block.is_election(),
+            });
+        }
+        if let Some(validators) = block.validators() {
+            events.extend(
+                validators
+                    .iter()
+                    .filter(|validator| self.validators.contains(&validator.address))
+                    .map(|validator| WebhookEvent::ValidatorElected {
+                        validator_address: validator.address.clone(),
+                        epoch: block.epoch_number() + 1,
+                        block_number: block.block_number(),
+                        block_hash: block.hash(),
+                    }),
+            );
+        }
+        events
+    }
+}
+
+/// An event a webhook is notified of.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum WebhookEvent {
+    /// The logs of a block that are related to the addresses of the webhook.
+    #[serde(rename_all = "camelCase")]
+    Logs {
+        block_number: u32,
+        block_hash: Blake2bHash,
+        logs: BlockLog,
+    },
+    /// The validator was elected into the validator set of the given epoch.
+    #[serde(rename_all = "camelCase")]
+    ValidatorElected {
+        validator_address: Address,
+        epoch: u32,
+        block_number: u32,
+        block_hash: Blake2bHash,
+    },
+    #[serde(rename_all = "camelCase")]
+    ValidatorJailed {
+        validator_address: Address,
+        jailed_from: u32,
+        block_number: u32,
+        block_hash: Blake2bHash,
+    },
+    #[serde(rename_all = "camelCase")]
+    ValidatorDeactivated {
+        validator_address: Address,
+        inactive_from: u32,
+        block_number: u32,
+        block_hash: Blake2bHash,
+    },
+    /// A macro block was finalized.
+    #[serde(rename_all = "camelCase")]
+    Finalized {
+        block_number: u32,
+        block_hash: Blake2bHash,
+        epoch: u32,
+        election: bool,
+    },
+}
+
+/// The body of a notification.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload<'a> {
+    /// Identifies the event for the webhook, such that receivers can ignore duplicates.
+    id: String,
+    webhook: &'a str,
+    event: &'a WebhookEvent,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Invalid webhook URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("The secret of a webhook must not be empty")]
+    EmptySecret,
+
+    #[error("A webhook has to be notified of the logs of addresses, validator events or finalized blocks")]
+    NothingToNotify,
+
+    #[error("No webhook with ID: {0}")]
+    NotFound(String),
+
+    #[error("Webhook {0} is set in the config file and can't be removed")]
+    Configured(String),
+}
+
+/// Notifies URLs of the logs of addresses, of the election, jailing and deactivation of
+/// validators and of finalized macro blocks. Notifications are queued in the database and
+/// retried with exponential backoff until they are delivered, also across restarts.
+#[derive(Clone)]
+pub struct Webhooks {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    store: WebhookStore,
+    /// The webhooks set in the config file, followed by those added via RPC.
+    webhooks: RwLock<Vec<Registration>>,
+    /// Wakes up the delivery when notifications are queued.
+    queued: Notify,
+}
+
+impl Webhooks {
+    /// Creates the webhooks, loading the ones that were added via RPC from the database.
+    pub fn new(db: DatabaseProxy) -> Self {
+        let store = WebhookStore::new(db);
+        let webhooks = store.webhooks();
+        Self {
+            inner: Arc::new(Inner {
+                store,
+                webhooks: RwLock::new(webhooks),
+                queued: Notify::new(),
+            }),
+        }
+    }
+
+    fn validate(url: &str, secret: &str, filter: &WebhookFilter) -> Result<(), WebhookError> {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            _ => return Err(WebhookError::InvalidUrl(url.to_string())),
+        }
+        if secret.is_empty() {
+            return Err(WebhookError::EmptySecret);
+        }
+        if filter.is_empty() {
+            return Err(WebhookError::NothingToNotify);
+        }
+        Ok(())
+    }
+
+    /// Adds a webhook from the config file, which is not stored. Returns its ID.
+    pub fn configure(
+        &self,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<String, WebhookError> {
+        Self::validate(&url, &secret, &filter)?;
+
+        let mut webhooks = self.inner.webhooks.write();
+        let configured = webhooks.iter().filter(|webhook| webhook.configured).count();
+        let id = format!("config-{configured}");
+        webhooks.insert(
+            configured,
+            Registration {
+                id: id.clone(),
+                url,
+                secret,
+                filter,
+                configured: true,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Runs a write to the store on a blocking thread, since committing a database transaction
+    /// blocks until the write lock of the database is acquired.
+    async fn write_store<F: FnOnce(&WebhookStore) + Send + 'static>(&self, write: F) {
+        let inner = Arc::clone(&self.inner);
+        if let Err(error) = spawn_blocking(move || write(&inner.store)).await {
+            log::error!(%error, "Failed to write to the webhook store");
+        }
+    }
+
+    /// Adds a webhook and stores it, such that it is kept across restarts. Returns its ID.
+    pub async fn add(
+        &self,
+        url: String,
+        secret: String,
+        filter: WebhookFilter,
+    ) -> Result<String, WebhookError> {
+        Self::validate(&url, &secret, &filter)?;
+
+        let webhook = Registration {
+            id: hex::encode(rand::thread_rng().gen::<[u8; 8]>()),
+            url,
+            secret,
+            filter,
+            configured: false,
+        };
+        let stored = webhook.clone();
+        self.write_store(move |store| store.put_webhook(&stored))
+            .await;
+        let id = webhook.id.clone();
+        self.inner.webhooks.write().push(webhook);
+        Ok(id)
+    }
+
+    /// Removes a webhook that was added via RPC, dropping its queued notifications.
+    pub async fn remove(&self, id: &str) -> Result<(), WebhookError> {
+        {
+            let mut webhooks = self.inner.webhooks.write();
+            let index = webhooks
+                .iter()
+                .position(|webhook| webhook.id == id)
+                .ok_or_else(|| WebhookError::NotFound(id.to_string()))?;
+            if webhooks[index].configured {
+                return Err(WebhookError::Configured(id.to_string()));
+            }
+            webhooks.remove(index);
+        }
+        let id = id.to_string();
+        self.write_store(move |store| store.remove_webhook(&id))
+            .await;
+        Ok(())
+    }
+
+    /// Returns all webhooks, without their secrets.
+    pub fn list(&self) -> Vec<Webhook> {
+        let pending = self.inner.store.pending();
+        self.inner
+            .webhooks
+            .read()
+            .iter()
+            .map(|webhook| Webhook {
+                id: webhook.id.clone(),
+                url: webhook.url.clone(),
+                addresses: webhook.filter.addresses.clone(),
+                log_types: webhook.filter.log_types.clone(),
+                validators: webhook.filter.validators.clone(),
+                finalized: webhook.filter.finalized,
+                configured: webhook.configured,
+                pending: pending.get(&webhook.id).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Queues the notifications of the blockchain's events and delivers them. This never returns.
+    pub async fn run(self, blockchain: BlockchainProxy) {
+        future::join(self.process_events(blockchain), self.deliver()).await;
+    }
+
+    async fn process_events(&self, blockchain: BlockchainProxy) {
+        enum Event {
+            Block(BBlockLog),
+            Blockchain(BlockchainEvent),
+        }
+
+        let (block_logs, blockchain_events) = {
+            let blockchain = blockchain.read();
+            let block_logs: BoxStream<'static, _> = if let BlockchainReadProxy::Full(blockchain) =
+                &blockchain
+            {
+                BroadcastStream::new(blockchain.log_notifier.subscribe())
+                    .filter_map(|block_log| {
+                        future::ready(match block_log {
+                            Ok(block_log) => Some(Event::Block(block_log)),
+                            Err(BroadcastStreamRecvError::Lagged(count)) => {
+                                log::warn!(count, "Webhooks missed block logs");
+                                None
+                            }
+                        })
+                    })
+                    .boxed()
+            } else {
+                log::warn!("Webhooks are only notified of finalized blocks and elected validators by a light client");
+                stream::pending().boxed()
+            };
+            (
+                block_logs,
+                blockchain.notifier_as_stream().map(Event::Blockchain),
+            )
+        };
+
+        let mut events = stream::select(block_logs, blockchain_events);
+        while let Some(event) = events.next().await {
+            match event {
+                Event::Block(block_log) => {
+                    self.notify(|filter| filter.block_log_events(&block_log))
+                        .await;
+                }
+                Event::Blockchain(
+                    BlockchainEvent::Finalized(hash) | BlockchainEvent::EpochFinalized(hash),
+                ) => {
+                    let block = match blockchain.read().get_block(&hash, true) {
+                        Ok(block) => block,
+                        Err(error) => {
+                            log::warn!(%error, %hash, "Failed to get finalized block for webhooks");
+                            continue;
+                        }
+                    };
+                    self.notify(|filter| filter.finalized_block_events(&block))
+                        .await;
+                }
+                Event::Blockchain(_) => {}
+            }
+        }
+    }
+
+    /// Queues a notification for each event of each webhook.
+    async fn notify<F: Fn(&WebhookFilter) -> Vec<WebhookEvent>>(&self, events: F) {
+        let now = unix_millis();
+        let notifications: Vec<Notification> = self
+            .inner
+            .webhooks
+            .read()
+            .iter()
+            .flat_map(|webhook| {
+                events(&webhook.filter)
+                    .into_iter()
+                    .filter_map(|event| {
+                        let event_json = serde_json::to_string(&event).ok()?;
+                        let payload = Payload {
+                            id: Blake2bHasher::default()
+                                .digest(format!("{}:{}", webhook.id, event_json).as_bytes())
+                                .to_hex(),
+                            webhook: &webhook.id,
+                            event: &event,
+                        };
+                        Some(Notification {
+                            webhook: webhook.id.clone(),
+                            url: webhook.url.clone(),
+                            body: serde_json::to_string(&payload).ok()?,
+                            attempts: 0,
+                            next_attempt: now,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        if !notifications.is_empty() {
+            self.write_store(move |store| store.enqueue(&notifications))
+                .await;
+            self.inner.queued.notify_one();
+        }
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
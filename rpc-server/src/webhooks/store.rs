@@ -0,0 +1,323 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use nimiq_database::{
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableFlags, TableProxy,
+};
+use nimiq_database_value::{FromDatabaseValue, IntoDatabaseValue};
+use nimiq_serde::{Deserialize as _, Serialize as _};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use super::WebhookFilter;
+
+/// A webhook and the secret its notifications are signed with.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct Registration {
+    pub id: String,
+    pub url: String,
+    pub secret: String,
+    pub filter: WebhookFilter,
+    /// Whether the webhook is set in the config file. Only webhooks added via RPC are stored.
+    #[serde(skip)]
+    pub configured: bool,
+}
+
+/// A notification that has not been delivered yet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct Notification {
+    /// The ID of the webhook.
+    pub webhook: String,
+    /// The URL of the webhook when the notification was queued. If the URL changed, the
+    /// notification is dropped.
+    pub url: String,
+    /// The JSON body.
+    pub body: String,
+    /// The number of failed delivery attempts.
+    pub attempts: u32,
+    /// When to attempt the next delivery, as a Unix timestamp in milliseconds.
+    pub next_attempt: u64,
+}
+
+macro_rules! impl_database_value {
+    ($type:ty) => {
+        impl IntoDatabaseValue for $type {
+            fn database_byte_size(&self) -> usize {
+                self.serialized_size()
+            }
+
+            fn copy_into_database(&self, mut bytes: &mut [u8]) {
+                self.serialize_to_writer(&mut bytes).unwrap();
+            }
+        }
+
+        impl FromDatabaseValue for $type {
+            fn copy_from_database(bytes: &[u8]) -> io::Result<Self>
+            where
+                Self: Sized,
+            {
+                Self::deserialize_from_vec(bytes)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            }
+        }
+    };
+}
+
+impl_database_value!(Registration);
+impl_database_value!(Notification);
+
+/// The queued notifications by the time of their next delivery attempt, and the webhook of each
+/// of them. It is kept in memory, so that the queue doesn't have to be read from the database to
+/// find the due notifications.
+#[derive(Debug, Default)]
+struct QueueIndex {
+    /// The webhook and the next attempt of each queued notification, by its key.
+    queued: BTreeMap<u64, (String, u64)>,
+    /// The keys of the queued notifications, ordered by their next attempt.
+    schedule: BTreeSet<(u64, u64)>,
+}
+
+impl QueueIndex {
+    fn insert(&mut self, key: u64, notification: &Notification) {
+        self.remove(key);
+        self.queued.insert(
+            key,
+            (notification.webhook.clone(), notification.next_attempt),
+        );
+        self.schedule.insert((notification.next_attempt, key));
+    }
+
+    /// Removes the notification, returning whether it was queued.
+    fn remove(&mut self, key: u64) -> bool {
+        match self.queued.remove(&key) {
+            Some((_, next_attempt)) => {
+                self.schedule.remove(&(next_attempt, key));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Persists the webhooks added via RPC and the queue of notifications that have not been
+/// delivered yet.
+#[derive(Debug)]
+pub(super) struct WebhookStore {
+    db: DatabaseProxy,
+    webhook_table: TableProxy,
+    queue_table: TableProxy,
+    next_key: AtomicU64,
+    /// The index of the queue. Its lock is held while the queue is written, so that it matches the
+    /// database.
+    index: Mutex<QueueIndex>,
+}
+
+impl WebhookStore {
+    const WEBHOOK_DB_NAME: &'static str = "Webhooks";
+    const QUEUE_DB_NAME: &'static str = "WebhookQueue";
+
+    pub fn new(db: DatabaseProxy) -> Self {
+        let webhook_table = db.open_table(Self::WEBHOOK_DB_NAME.to_string());
+        let queue_table =
+            db.open_table_with_flags(Self::QUEUE_DB_NAME.to_string(), TableFlags::UINT_KEYS);
+
+        let mut index = QueueIndex::default();
+        {
+            let txn = db.read_transaction();
+            let cursor = txn.cursor(&queue_table);
+            for (key, notification) in cursor.into_iter_start::<u64, Notification>() {
+                index.insert(key, &notification);
+            }
+        }
+        let last_key = index.queued.keys().next_back().copied();
+        WebhookStore {
+            db,
+            webhook_table,
+            queue_table,
+            next_key: AtomicU64::new(last_key.map_or(0, |key| key + 1)),
+            index: Mutex::new(index),
+        }
+    }
+
+    pub fn webhooks(&self) -> Vec<Registration> {
+        let txn = self.db.read_transaction();
+        let cursor = txn.cursor(&self.webhook_table);
+        cursor
+            .into_iter_start::<String, Registration>()
+            .map(|(_, webhook)| webhook)
+            .collect()
+    }
+
+    pub fn put_webhook(&self, webhook: &Registration) {
+        let mut txn = self.db.write_transaction();
+        txn.put_reserve(&self.webhook_table, webhook.id.as_str(), webhook);
+        txn.commit();
+    }
+
+    /// Removes the webhook and the notifications queued for it.
+    pub fn remove_webhook(&self, id: &str) {
+        let mut index = self.index.lock();
+        let queued: Vec<u64> = index
+            .queued
+            .iter()
+            .filter(|(_, (webhook, _))| webhook == id)
+            .map(|(key, _)| *key)
+            .collect();
+
+        let mut txn = self.db.write_transaction();
+        txn.remove(&self.webhook_table, id);
+        for key in queued {
+            txn.remove(&self.queue_table, &key);
+            index.remove(key);
+        }
+        txn.commit();
+    }
+
+    /// Returns the number of queued notifications of each webhook.
+    pub fn pending(&self) -> HashMap<String, u64> {
+        let mut pending = HashMap::new();
+        for (webhook, _) in self.index.lock().queued.values() {
+            *pending.entry(webhook.clone()).or_default() += 1;
+        }
+        pending
+    }
+
+    /// Returns up to `max` notifications whose next attempt is at or before `now`, the earliest
+    /// first.
+    pub fn due(&self, now: u64, max: usize) -> Vec<(u64, Notification)> {
+        let keys: Vec<u64> = self
+            .index
+            .lock()
+            .schedule
+            .iter()
+            .take_while(|(next_attempt, _)| *next_attempt <= now)
+            .take(max)
+            .map(|(_, key)| *key)
+            .collect();
+
+        let txn = self.db.read_transaction();
+        keys.into_iter()
+            .filter_map(|key| Some((key, txn.get::<u64, Notification>(&self.queue_table, &key)?)))
+            .collect()
+    }
+
+    /// Returns the time of the earliest next attempt of the queued notifications.
+    pub fn next_attempt(&self) -> Option<u64> {
+        self.index
+            .lock()
+            .schedule
+            .first()
+            .map(|(next_attempt, _)| *next_attempt)
+    }
+
+    pub fn enqueue(&self, notifications: &[Notification]) {
+        let mut index = self.index.lock();
+        let mut txn = self.db.write_transaction();
+        for notification in notifications {
+            let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+            txn.put_reserve(&self.queue_table, &key, notification);
+            index.insert(key, notification);
+        }
+        txn.commit();
+    }
+
+    /// Stores the outcome of delivery attempts in one transaction. Notifications given with a next
+    /// attempt are updated, the others are removed. Notifications that were removed in the
+    /// meantime, e.g. together with their webhook, are not queued again.
+    pub fn complete(&self, attempts: Vec<(u64, Option<Notification>)>) {
+        let mut index = self.index.lock();
+        let mut txn = self.db.write_transaction();
+        for (key, retry) in attempts {
+            if !index.remove(key) {
+                continue;
+            }
+            match retry {
+                Some(notification) => {
+                    txn.put_reserve(&self.queue_table, &key, &notification);
+                    index.insert(key, &notification);
+                }
+                None => txn.remove(&self.queue_table, &key),
+            }
+        }
+        txn.commit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_database::volatile::VolatileDatabase;
+
+    use super::*;
+
+    fn notification(webhook: &str, next_attempt: u64) -> Notification {
+        Notification {
+            webhook: webhook.to_string(),
+            url: format!("https://example.com/{webhook}"),
+            body: "{}".to_string(),
+            attempts: 0,
+            next_attempt,
+        }
+    }
+
+    fn keys(notifications: Vec<(u64, Notification)>) -> Vec<u64> {
+        notifications.into_iter().map(|(key, _)| key).collect()
+    }
+
+    #[test]
+    fn it_returns_due_notifications_by_next_attempt() {
+        let db = VolatileDatabase::new(20).unwrap();
+        let store = WebhookStore::new(db.clone());
+        store.enqueue(&[
+            notification("a", 30),
+            notification("b", 10),
+            notification("a", 20),
+        ]);
+
+        assert_eq!(store.next_attempt(), Some(10));
+        assert_eq!(keys(store.due(5, 10)), Vec::<u64>::new());
+        assert_eq!(keys(store.due(20, 10)), vec![1, 2]);
+        assert_eq!(keys(store.due(30, 2)), vec![1, 2]);
+        assert_eq!(store.pending().get("a"), Some(&2));
+
+        // One notification is delivered, the other one is retried later.
+        let mut retry = notification("a", 40);
+        retry.attempts = 1;
+        store.complete(vec![(1, None), (2, Some(retry))]);
+        assert_eq!(keys(store.due(40, 10)), vec![0, 2]);
+        assert_eq!(store.due(40, 10)[1].1.attempts, 1);
+
+        // The index is restored from the database.
+        let store = WebhookStore::new(db);
+        assert_eq!(store.next_attempt(), Some(30));
+        assert_eq!(keys(store.due(40, 10)), vec![0, 2]);
+        store.enqueue(&[notification("b", 0)]);
+        assert_eq!(keys(store.due(0, 10)), vec![3]);
+    }
+
+    #[test]
+    fn it_removes_the_notifications_of_removed_webhooks() {
+        let db = VolatileDatabase::new(20).unwrap();
+        let store = WebhookStore::new(db);
+        store.enqueue(&[notification("a", 10), notification("b", 10)]);
+        let due = store.due(10, 10);
+
+        store.remove_webhook("a");
+        assert_eq!(keys(store.due(10, 10)), vec![1]);
+        assert_eq!(store.pending().get("a"), None);
+
+        // A failed attempt doesn't queue the notification of the removed webhook again.
+        let retries = due
+            .into_iter()
+            .map(|(key, mut notification)| {
+                notification.next_attempt = 20;
+                (key, Some(notification))
+            })
+            .collect();
+        store.complete(retries);
+        assert_eq!(keys(store.due(20, 10)), vec![1]);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{sync::Arc, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use nimiq_hash::Hash;
+use nimiq_rpc_server::authorization::{
+    Authorization, AuthorizationError, Grant, MethodPattern, RateLimit, Role, Secret,
+};
+
+fn role(name: &str, methods: &[&str]) -> Arc<Role> {
+    Arc::new(Role {
+        name: name.to_string(),
+        methods: methods
+            .iter()
+            .map(|method| MethodPattern::new(method))
+            .collect(),
+    })
+}
+
+#[test]
+fn method_patterns() {
+    assert!(MethodPattern::new("get_*").matches("getBlockNumber"));
+    assert!(MethodPattern::new("unlock_account").matches("unlockAccount"));
+    assert!(!MethodPattern::new("unlock_account").matches("unlockAccounts"));
+    assert!(MethodPattern::new("*_account").matches("lockAccount"));
+    assert!(MethodPattern::new("get*by*").matches("getBlockByNumber"));
+    assert!(!MethodPattern::new("get*by*hash").matches("getBlockByNumber"));
+    assert!(MethodPattern::new("*").matches("sendBasicTransaction"));
+    assert!(!MethodPattern::new("send_*").matches("getAccount"));
+}
+
+#[test]
+fn authenticate_and_authorize() {
+    let read_only = role("read-only", &["get_*"]);
+    let wallet = role("wallet", &["get_*", "unlock_account"]);
+    let authorization = Authorization::new(vec![
+        Grant::new(
+            Secret::Token {
+                token_hash: "reader-token".hash(),
+            },
+            read_only,
+            None,
+        ),
+        Grant::new(
+            Secret::Basic {
+                username: "wallet".to_string(),
+                password_hash: "secret".hash(),
+            },
+            wallet,
+            None,
+        ),
+    ]);
+
+    let reader = authorization
+        .authenticate(Some("Bearer reader-token"))
+        .unwrap();
+    assert!(reader.authorize("getBlockNumber").is_ok());
+    assert_eq!(
+        reader.authorize("unlockAccount"),
+        Err(AuthorizationError::MethodNotAllowed {
+            role: "read-only".to_string(),
+            method: "unlockAccount".to_string(),
+        })
+    );
+
+    let basic = format!("Basic {}", STANDARD.encode("wallet:secret"));
+    let wallet = authorization.authenticate(Some(&basic)).unwrap();
+    assert!(wallet.authorize("unlockAccount").is_ok());
+
+    let wrong_password = format!("Basic {}", STANDARD.encode("wallet:wrong"));
+    assert!(authorization.authenticate(Some(&wrong_password)).is_err());
+    assert!(authorization.authenticate(Some("Bearer other")).is_err());
+    assert!(authorization.authenticate(None).is_err());
+}
+
+#[test]
+fn rate_limit() {
+    let grant = Grant::new(
+        Secret::Token {
+            token_hash: "token".hash(),
+        },
+        role("all", &["*"]),
+        Some(RateLimit {
+            requests: 3,
+            per: Duration::from_secs(3600),
+        }),
+    );
+
+    assert!(grant.check_rate_limit(2).is_ok());
+    assert!(grant.check_rate_limit(1).is_ok());
+    assert_eq!(
+        grant.check_rate_limit(1),
+        Err(AuthorizationError::RateLimited)
+    );
+}
+
+#[test]
+fn inherit_client_state() {
+    let grant = |token: &str, requests| {
+        Grant::new(
+            Secret::Token {
+                token_hash: token.hash(),
+            },
+            role("all", &["*"]),
+            Some(RateLimit {
+                requests,
+                per: Duration::from_secs(3600),
+            }),
+        )
+    };
+    let previous = Authorization::new(vec![grant("a", 1), grant("b", 1)]);
+    let a = previous.authenticate(Some("Bearer a")).unwrap();
+    assert!(a.check_rate_limit(1).is_ok());
+    let b = previous.authenticate(Some("Bearer b")).unwrap();
+    assert!(b.check_rate_limit(1).is_ok());
+
+    // The limit of `b` changed and `c` is a new client.
+    let mut authorization = Authorization::new(vec![grant("a", 1), grant("b", 2), grant("c", 1)]);
+    authorization.inherit(&previous);
+
+    let a = authorization.authenticate(Some("Bearer a")).unwrap();
+    assert_eq!(a.check_rate_limit(1), Err(AuthorizationError::RateLimited));
+    assert!(Arc::ptr_eq(
+        &a.unlocked_wallets(),
+        &previous.grants()[0].unlocked_wallets()
+    ));
+
+    let b = authorization.authenticate(Some("Bearer b")).unwrap();
+    assert!(b.check_rate_limit(2).is_ok());
+    assert!(Arc::ptr_eq(
+        &b.unlocked_wallets(),
+        &previous.grants()[1].unlocked_wallets()
+    ));
+
+    // Clients don't share their unlocked wallets.
+    let c = authorization.authenticate(Some("Bearer c")).unwrap();
+    assert!(!Arc::ptr_eq(&c.unlocked_wallets(), &a.unlocked_wallets()));
+}
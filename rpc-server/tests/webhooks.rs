@@ -0,0 +1,172 @@
+use nimiq_account::{BlockLog, Log, TransactionLog};
+use nimiq_database::volatile::VolatileDatabase;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::types::LogType;
+use nimiq_rpc_server::webhooks::{sign, WebhookError, WebhookEvent, WebhookFilter, Webhooks};
+
+fn filter(addresses: Vec<Address>) -> WebhookFilter {
+    WebhookFilter {
+        addresses,
+        ..Default::default()
+    }
+}
+
+fn transfer(from: u8, to: u8) -> Log {
+    Log::Transfer {
+        from: Address::from([from; 20]),
+        to: Address::from([to; 20]),
+        amount: Coin::from_u64_unchecked(100),
+        data: None,
+    }
+}
+
+#[test]
+fn sign_body() {
+    assert_eq!(
+        sign("secret", 1_700_000_000, r#"{"id":"1"}"#),
+        "086f6aff7bd084c98679825129c5a64dbad88c760016d6d2c0fb123f27951d54",
+    );
+    assert_ne!(
+        sign("secret", 1_700_000_001, r#"{"id":"1"}"#),
+        sign("secret", 1_700_000_000, r#"{"id":"1"}"#),
+    );
+}
+
+#[tokio::test]
+async fn add_list_and_remove() {
+    let db = VolatileDatabase::new(20).unwrap();
+    let webhooks = Webhooks::new(db.clone());
+    let address = Address::from([1u8; 20]);
+
+    let configured = webhooks
+        .configure(
+            "https://example.com/config".to_string(),
+            "secret".to_string(),
+            filter(vec![address.clone()]),
+        )
+        .unwrap();
+    let added = webhooks
+        .add(
+            "https://example.com/rpc".to_string(),
+            "secret".to_string(),
+            filter(vec![address]),
+        )
+        .await
+        .unwrap();
+
+    let listed = webhooks.list();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].id, configured);
+    assert!(listed[0].configured);
+    assert_eq!(listed[1].id, added);
+    assert!(!listed[1].configured);
+
+    // Only the webhooks added via RPC are stored.
+    let reloaded = Webhooks::new(db);
+    let listed = reloaded.list();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, added);
+
+    assert!(matches!(
+        webhooks.remove(&configured).await,
+        Err(WebhookError::Configured(_))
+    ));
+    webhooks.remove(&added).await.unwrap();
+    assert!(matches!(
+        webhooks.remove(&added).await,
+        Err(WebhookError::NotFound(_))
+    ));
+    assert_eq!(webhooks.list().len(), 1);
+}
+
+#[tokio::test]
+async fn invalid_webhooks_are_rejected() {
+    let db = VolatileDatabase::new(20).unwrap();
+    let webhooks = Webhooks::new(db);
+    let address = Address::from([1u8; 20]);
+
+    assert!(matches!(
+        webhooks
+            .add(
+                "ftp://example.com".to_string(),
+                "secret".to_string(),
+                filter(vec![address.clone()]),
+            )
+            .await,
+        Err(WebhookError::InvalidUrl(_))
+    ));
+    assert!(matches!(
+        webhooks
+            .add(
+                "https://example.com".to_string(),
+                String::new(),
+                filter(vec![address]),
+            )
+            .await,
+        Err(WebhookError::EmptySecret)
+    ));
+    assert!(matches!(
+        webhooks
+            .add(
+                "https://example.com".to_string(),
+                "secret".to_string(),
+                WebhookFilter {
+                    log_types: vec![LogType::Transfer],
+                    ..Default::default()
+                },
+            )
+            .await,
+        Err(WebhookError::NothingToNotify)
+    ));
+}
+
+#[test]
+fn block_log_events_are_filtered() {
+    let validator = Address::from([9u8; 20]);
+    let block_log = BlockLog::AppliedBlock {
+        inherent_logs: vec![Log::JailValidator {
+            validator_address: validator.clone(),
+            jailed_from: 100,
+        }],
+        block_hash: Blake2bHash::default(),
+        block_number: 42,
+        timestamp: 0,
+        tx_logs: vec![
+            TransactionLog::new(Blake2bHash::default(), vec![transfer(1, 2)]),
+            TransactionLog::new(Blake2bHash::default(), vec![transfer(3, 4)]),
+        ],
+        total_tx_size: 0,
+    };
+
+    let events = filter(vec![Address::from([2u8; 20])]).block_log_events(&block_log);
+    assert_eq!(events.len(), 1);
+    let WebhookEvent::Logs {
+        block_number,
+        logs: nimiq_rpc_interface::types::BlockLog::AppliedBlock { tx_logs, .. },
+        ..
+    } = &events[0]
+    else {
+        panic!("Unexpected event {:?}", events[0]);
+    };
+    assert_eq!(*block_number, 42);
+    assert_eq!(tx_logs.len(), 1);
+    assert_eq!(tx_logs[0].logs, vec![transfer(1, 2)]);
+
+    let events = filter(vec![Address::from([5u8; 20])]).block_log_events(&block_log);
+    assert!(events.is_empty());
+
+    let events = WebhookFilter {
+        validators: vec![validator],
+        ..Default::default()
+    }
+    .block_log_events(&block_log);
+    assert!(matches!(
+        events[..],
+        [WebhookEvent::ValidatorJailed {
+            jailed_from: 100,
+            ..
+        }]
+    ));
+}
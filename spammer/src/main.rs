@@ -230,6 +230,8 @@ async fn main_inner() -> Result<(), Error> {
             client.mempool(),
             client.consensus_proxy(),
             client.network(),
+            None,
+            None,
             &[],
         )
     }
@@ -3,10 +3,12 @@ extern crate log;
 pub(crate) mod protocol;
 pub(crate) mod state;
 mod states;
+pub(crate) mod telemetry;
 pub(crate) mod tendermint;
 pub(crate) mod utils;
 
 pub use protocol::*;
 pub use state::*;
+pub use telemetry::{RoundTelemetry, StepTelemetry, Telemetry};
 pub use tendermint::*;
 pub use utils::{Return, Step};
@@ -2,7 +2,7 @@ use futures::{future::BoxFuture, stream::BoxStream};
 use nimiq_collections::BitSet;
 use serde::{Deserialize, Serialize};
 
-use crate::utils::Step;
+use crate::{telemetry::Telemetry, utils::Step};
 
 /// Error for proposal verification. Currently not really used, but in place to allow for potential
 /// punishment of misbehaving contributors to the tendermint protocol.
@@ -163,4 +163,8 @@ pub trait Protocol: Clone + Send + Sync + Unpin + Sized + 'static {
         step: Step,
         message: Self::AggregationMessage,
     ) -> BoxFuture<'static, Result<(), ()>>;
+
+    /// Reports the `telemetry` of the Tendermint instance once it ends, either because a decision
+    /// was reached or because the instance is dropped.
+    fn report_telemetry(&self, _telemetry: Telemetry) {}
 }
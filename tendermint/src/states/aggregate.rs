@@ -121,6 +121,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
         // Check if the timeout elapsed. If so the result must be returned, even though it might still improve.
         if self.timeout.as_mut().unwrap().poll_unpin(cx).is_ready() {
             log::debug!("Aggregation timed out without final result.");
+            self.telemetry.on_timeout_elapsed();
             self.on_none_polka();
 
             // Reset timeout.
@@ -130,6 +130,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
         // Check if the timeout elapsed.
         if self.timeout.as_mut().unwrap().poll_unpin(cx).is_ready() {
             // The timeout elapsed, vote nil as the proposal did not arrive in time.
+            self.telemetry.on_timeout_elapsed();
             self.state
                 .votes
                 .insert((self.state.current_round, Step::Prevote), None);
@@ -122,6 +122,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
 
             // Store the proposal for the current round.
             proposals.insert(proposal_hash.clone(), (Some(*valid_round), signature));
+            self.telemetry.on_proposal(self.state.current_round);
 
             // Yield the state as it has changed.
             Ok(Return::Update(self.state.clone()))
@@ -154,6 +155,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
 
             // Store the proposal for the current round.
             proposals.insert(proposal_hash, (None, signature));
+            self.telemetry.on_proposal(self.state.current_round);
 
             // Yield the state as it has changed.
             Ok(Return::Update(self.state.clone()))
@@ -0,0 +1,258 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::utils::Step;
+
+/// Statistics about a single step of a Tendermint round.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StepTelemetry {
+    /// The time spent in this step.
+    pub duration: Duration,
+    /// The timeout that was started in this step, if any.
+    pub timeout: Option<Duration>,
+    /// Whether this step was ended by its timeout elapsing.
+    pub timed_out: bool,
+    /// The highest number of contributors seen in the aggregation of this step. This is always
+    /// zero for the propose step.
+    pub contributors: usize,
+    /// The time after the start of this step at which its aggregation reached 2f+1 contributors.
+    pub two_f_plus_one_after: Option<Duration>,
+}
+
+/// Statistics about a single Tendermint round.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundTelemetry {
+    /// The round these statistics are about.
+    pub round: u32,
+    /// The time after the start of the round at which the first valid proposal for it was known.
+    pub proposal_after: Option<Duration>,
+    pub propose: StepTelemetry,
+    pub prevote: StepTelemetry,
+    pub precommit: StepTelemetry,
+}
+
+impl RoundTelemetry {
+    fn step_mut(&mut self, step: Step) -> &mut StepTelemetry {
+        match step {
+            Step::Propose => &mut self.propose,
+            Step::Prevote => &mut self.prevote,
+            Step::Precommit => &mut self.precommit,
+        }
+    }
+
+    /// Returns the number of timeouts that elapsed in this round.
+    pub fn num_timeouts(&self) -> usize {
+        [&self.propose, &self.prevote, &self.precommit]
+            .iter()
+            .filter(|step| step.timed_out)
+            .count()
+    }
+}
+
+/// Statistics about how a Tendermint instance went, i.e. how agreement on a macro block was
+/// reached.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Telemetry {
+    /// Whether this instance reached a decision. Instances can also end without one, e.g. when
+    /// the macro block is received from the network first.
+    pub decided: bool,
+    /// The total time this instance was running for.
+    pub duration: Duration,
+    /// The statistics of the rounds this instance went through, in order. Rounds that were
+    /// skipped are not included.
+    pub rounds: Vec<RoundTelemetry>,
+}
+
+impl Telemetry {
+    /// Returns the number of rounds this instance went through.
+    pub fn num_rounds(&self) -> usize {
+        self.rounds.len()
+    }
+
+    /// Returns the number of timeouts that elapsed in all rounds.
+    pub fn num_timeouts(&self) -> usize {
+        self.rounds.iter().map(RoundTelemetry::num_timeouts).sum()
+    }
+}
+
+/// Records the telemetry of a Tendermint instance as its state machine progresses.
+pub(crate) struct TelemetryRecorder {
+    started: Instant,
+    current: (u32, Step),
+    round_starts: BTreeMap<u32, Instant>,
+    step_starts: BTreeMap<(u32, Step), Instant>,
+    finished: bool,
+    telemetry: Telemetry,
+}
+
+impl TelemetryRecorder {
+    pub fn new(round: u32, step: Step) -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            current: (round, step),
+            round_starts: BTreeMap::from([(round, now)]),
+            step_starts: BTreeMap::from([((round, step), now)]),
+            finished: false,
+            telemetry: Telemetry {
+                rounds: vec![RoundTelemetry {
+                    round,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        }
+    }
+
+    fn round_mut(&mut self, round: u32) -> Option<&mut RoundTelemetry> {
+        self.telemetry
+            .rounds
+            .iter_mut()
+            .rev()
+            .find(|telemetry| telemetry.round == round)
+    }
+
+    fn close_current_step(&mut self, now: Instant) {
+        let (round, step) = self.current;
+        let started = self.step_starts[&(round, step)];
+        if let Some(telemetry) = self.round_mut(round) {
+            telemetry.step_mut(step).duration = now - started;
+        }
+    }
+
+    /// Records the state machine being in the given round and step.
+    pub fn on_state(&mut self, round: u32, step: Step) {
+        if self.finished || self.current == (round, step) {
+            return;
+        }
+
+        let now = Instant::now();
+        self.close_current_step(now);
+
+        if round != self.current.0 {
+            self.round_starts.insert(round, now);
+            self.telemetry.rounds.push(RoundTelemetry {
+                round,
+                ..Default::default()
+            });
+        }
+        self.step_starts.insert((round, step), now);
+        self.current = (round, step);
+    }
+
+    /// Records the timeout of the current step being started.
+    pub fn on_timeout_started(&mut self, timeout: Duration) {
+        let (round, step) = self.current;
+        if let Some(telemetry) = self.round_mut(round) {
+            telemetry.step_mut(step).timeout = Some(timeout);
+        }
+    }
+
+    /// Records the timeout of the current step having elapsed.
+    pub fn on_timeout_elapsed(&mut self) {
+        let (round, step) = self.current;
+        if let Some(telemetry) = self.round_mut(round) {
+            telemetry.step_mut(step).timed_out = true;
+        }
+    }
+
+    /// Records a valid proposal for the given round becoming known.
+    pub fn on_proposal(&mut self, round: u32) {
+        let Some(started) = self.round_starts.get(&round).copied() else {
+            return;
+        };
+        if let Some(telemetry) = self.round_mut(round) {
+            telemetry.proposal_after.get_or_insert(started.elapsed());
+        }
+    }
+
+    /// Records an aggregate for the given round and step with the given number of contributors.
+    pub fn on_aggregate(
+        &mut self,
+        round: u32,
+        step: Step,
+        contributors: usize,
+        two_f_plus_one: usize,
+    ) {
+        let Some(started) = self.step_starts.get(&(round, step)).copied() else {
+            return;
+        };
+        if let Some(telemetry) = self.round_mut(round) {
+            let telemetry = telemetry.step_mut(step);
+            telemetry.contributors = telemetry.contributors.max(contributors);
+            if contributors >= two_f_plus_one {
+                telemetry
+                    .two_f_plus_one_after
+                    .get_or_insert(started.elapsed());
+            }
+        }
+    }
+
+    /// Finishes the recording. Subsequent state changes are not recorded anymore.
+    pub fn finish(&mut self, decided: bool) {
+        if self.finished {
+            return;
+        }
+
+        let now = Instant::now();
+        self.close_current_step(now);
+        self.telemetry.decided = decided;
+        self.telemetry.duration = now - self.started;
+        self.finished = true;
+    }
+
+    /// Returns the telemetry recorded so far.
+    pub fn telemetry(&self) -> &Telemetry {
+        &self.telemetry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_records_rounds_and_steps() {
+        let mut recorder = TelemetryRecorder::new(0, Step::Propose);
+
+        // Round 0: The proposal times out and the prevotes and precommits are for None.
+        recorder.on_timeout_started(Duration::from_millis(1000));
+        recorder.on_timeout_elapsed();
+        recorder.on_state(0, Step::Prevote);
+        recorder.on_aggregate(0, Step::Prevote, 5, 7);
+        recorder.on_aggregate(0, Step::Prevote, 8, 7);
+        recorder.on_state(0, Step::Precommit);
+        recorder.on_aggregate(0, Step::Precommit, 7, 7);
+
+        // Round 1: The proposal arrives in time.
+        recorder.on_state(1, Step::Propose);
+        recorder.on_proposal(1);
+        recorder.on_state(1, Step::Prevote);
+        recorder.finish(true);
+
+        // Changes after the recording finished are ignored.
+        recorder.on_state(2, Step::Propose);
+
+        let telemetry = recorder.telemetry();
+        assert!(telemetry.decided);
+        assert_eq!(telemetry.num_rounds(), 2);
+        assert_eq!(telemetry.num_timeouts(), 1);
+
+        let round = &telemetry.rounds[0];
+        assert_eq!(round.round, 0);
+        assert_eq!(round.proposal_after, None);
+        assert_eq!(round.propose.timeout, Some(Duration::from_millis(1000)));
+        assert!(round.propose.timed_out);
+        assert_eq!(round.prevote.contributors, 8);
+        assert!(round.prevote.two_f_plus_one_after.is_some());
+        assert_eq!(round.precommit.contributors, 7);
+
+        let round = &telemetry.rounds[1];
+        assert_eq!(round.round, 1);
+        assert!(round.proposal_after.is_some());
+        assert!(!round.propose.timed_out);
+        assert_eq!(round.prevote.two_f_plus_one_after, None);
+    }
+}
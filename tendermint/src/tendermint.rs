@@ -21,6 +21,7 @@ use tokio_stream::wrappers::ReceiverStream;
 use crate::{
     protocol::{Aggregation, Protocol, SignedProposalMessage, TaggedAggregationMessage},
     state::State,
+    telemetry::TelemetryRecorder,
     utils::{Return, Step},
     AggregationMessage, Proposal,
 };
@@ -90,6 +91,9 @@ pub struct Tendermint<TProtocol: Protocol> {
 
     /// Waker used for the poll next function
     pub(crate) waker: Option<Waker>,
+
+    /// Records how agreement is reached, reported to the protocol once this instance ends.
+    pub(crate) telemetry: TelemetryRecorder,
 }
 
 impl<TProtocol: Protocol> Tendermint<TProtocol> {
@@ -105,9 +109,12 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             TaggedAggregationMessage<TProtocol::AggregationMessage>,
         >,
     ) -> Self {
+        let state = state_opt.unwrap_or_default();
+        let telemetry = TelemetryRecorder::new(state.current_round, state.current_step);
+
         let mut this = Self {
             protocol: dependencies,
-            state: state_opt.unwrap_or_default(),
+            state,
             proposal_stream,
             requested_proposals: FuturesUnordered::default(),
             pending_proposal_requests: BTreeSet::default(),
@@ -121,6 +128,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             decision: false,
             state_return_pending: false,
             waker: None,
+            telemetry,
         };
 
         this.init();
@@ -167,6 +175,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
                 TProtocol::TIMEOUT_INIT
                     + self.state.current_round as u64 * TProtocol::TIMEOUT_DELTA,
             );
+            self.telemetry.on_timeout_started(duration);
             self.timeout = Some(sleep(duration).boxed());
         }
     }
@@ -349,6 +358,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
         };
 
         // The proposal with `proposal_hash` was successfully verified. Add it to the set of proposals for its round.
+        self.telemetry.on_proposal(proposal.message.round);
         self.state
             .round_proposals
             .entry(proposal.message.round)
@@ -520,6 +530,12 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
 
             // Get the total weight for the aggregate. It will be used for more comparisons later.
             let total_contributor_count = best_vote.all_contributors().len();
+            self.telemetry.on_aggregate(
+                round_and_step.0,
+                round_and_step.1,
+                total_contributor_count,
+                TProtocol::TWO_F_PLUS_ONE,
+            );
 
             for (proposal_hash, contributor_count) in best_vote.proposals() {
                 // First check for the aggregate yielding a result like a decision, or a precommit block vote.
@@ -623,10 +639,9 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
     }
 }
 
-impl<TProtocol: Protocol> Stream for Tendermint<TProtocol> {
-    type Item = Return<TProtocol>;
-
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+impl<TProtocol: Protocol> Tendermint<TProtocol> {
+    /// Polls the received messages and runs the state machine. See [Stream::poll_next].
+    fn poll_state_machine(&mut self, cx: &mut Context<'_>) -> Poll<Option<Return<TProtocol>>> {
         self.waker.store_waker(cx);
 
         // If a decision was returned previously this stream is terminated.
@@ -754,3 +769,28 @@ impl<TProtocol: Protocol> Stream for Tendermint<TProtocol> {
         Poll::Pending
     }
 }
+
+impl<TProtocol: Protocol> Stream for Tendermint<TProtocol> {
+    type Item = Return<TProtocol>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let result = self.poll_state_machine(cx);
+
+        // Record the state the state machine ended up in.
+        let (round, step) = (self.state.current_round, self.state.current_step);
+        self.telemetry.on_state(round, step);
+        if let Poll::Ready(Some(Return::Decision(_))) = &result {
+            self.telemetry.finish(true);
+        }
+
+        result
+    }
+}
+
+impl<TProtocol: Protocol> Drop for Tendermint<TProtocol> {
+    fn drop(&mut self) {
+        self.telemetry.finish(false);
+        self.protocol
+            .report_telemetry(self.telemetry.telemetry().clone());
+    }
+}
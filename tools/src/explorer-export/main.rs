@@ -0,0 +1,172 @@
+mod records;
+mod writer;
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Error};
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, Command};
+use nimiq_blockchain::{interface::HistoryInterface, ChainStore, HistoryStore};
+use nimiq_database::{mdbx::MdbxDatabase, traits::Database, DEFAULT_MAX_TABLES};
+use nimiq_genesis::NetworkInfo;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+
+use crate::{
+    records::Records,
+    writer::{Checkpoint, Format, Writer},
+};
+
+/// The number of blocks that are read in one database transaction. The progress is recorded
+/// after each of them.
+const BLOCKS_PER_CHUNK: u32 = 1000;
+
+fn main() -> Result<(), Error> {
+    let matches = Command::new("nimiq-explorer-export")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Exports blocks, transactions, inherents, logs and staking changes from the database of a history node")
+        .long_about("Exports blocks, transactions, inherents, logs and staking changes of a block range from the database of a history node, one file per kind of record. The database is opened read-only, so the node may keep running. Logs are derived from the history, since they are not stored.")
+        .arg(
+            Arg::new("database")
+                .short('d')
+                .long("database")
+                .value_name("DIR")
+                .required(true)
+                .value_parser(value_parser!(PathBuf))
+                .help("The database directory of the node, e.g. `mainalbatross-history-consensus`."),
+        )
+        .arg(
+            Arg::new("network")
+                .short('n')
+                .long("network")
+                .value_name("NETWORK")
+                .default_value("main-albatross")
+                .value_parser(value_parser!(NetworkId))
+                .help("The network of the database."),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("DIR")
+                .default_value("explorer-export")
+                .value_parser(value_parser!(PathBuf))
+                .help("The directory to write the files to."),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("ndjson")
+                .value_parser(["ndjson", "csv"])
+                .help("Write newline-delimited JSON or CSV."),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_name("BLOCK_NUMBER")
+                .value_parser(value_parser!(u32))
+                .help("The first block to export. Defaults to the genesis block."),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("BLOCK_NUMBER")
+                .value_parser(value_parser!(u32))
+                .help("The last block to export. Defaults to the head of the chain."),
+        )
+        .arg(
+            Arg::new("max-tables")
+                .long("max-tables")
+                .value_name("COUNT")
+                .value_parser(value_parser!(u32))
+                .help("The maximum number of tables of the database, as set by `max_dbs` in the node's config. Defaults to the node's default."),
+        )
+        .arg(
+            Arg::new("resume")
+                .short('r')
+                .long("resume")
+                .action(ArgAction::SetTrue)
+                .help("Continue a previous export to the output directory after its last checkpoint, ignoring `--from`."),
+        )
+        .get_matches();
+
+    let network_id = *matches.get_one::<NetworkId>("network").unwrap();
+    let network_info = NetworkInfo::from_network_id(network_id);
+    let _ = Policy::get_or_init(Policy {
+        genesis_block_number: network_info.genesis_block().block_number(),
+        ..Default::default()
+    });
+
+    let format = match matches.get_one::<String>("format").unwrap().as_str() {
+        "csv" => Format::Csv,
+        _ => Format::Ndjson,
+    };
+    let output = matches.get_one::<PathBuf>("output").unwrap();
+    let database = matches.get_one::<PathBuf>("database").unwrap();
+    let max_tables = matches
+        .get_one::<u32>("max-tables")
+        .copied()
+        .unwrap_or(DEFAULT_MAX_TABLES);
+
+    let db = MdbxDatabase::new_read_only(database, max_tables)
+        .with_context(|| format!("Failed to open database {}", database.display()))?;
+    let chain_store = ChainStore::new(db.clone());
+    let history_store = HistoryStore::new(db.clone());
+
+    let checkpoint = if matches.get_flag("resume") {
+        Checkpoint::load(output)?
+    } else {
+        None
+    };
+    let from = match &checkpoint {
+        Some(checkpoint) => checkpoint.block_number + 1,
+        None => matches
+            .get_one::<u32>("from")
+            .copied()
+            .unwrap_or_else(Policy::genesis_block_number),
+    };
+    let to = match matches.get_one::<u32>("to") {
+        Some(to) => *to,
+        None => {
+            let Some(head_hash) = chain_store.get_head(None) else {
+                bail!("The database has no chain");
+            };
+            chain_store
+                .get_block(&head_hash, false, None)?
+                .block_number()
+        }
+    };
+
+    let mut writer = Writer::new(output, format, checkpoint.as_ref())?;
+    let mut chunk_start = from;
+    while chunk_start <= to {
+        let chunk_end = chunk_start.saturating_add(BLOCKS_PER_CHUNK - 1).min(to);
+
+        // Keep read transactions short, so that a running node can reclaim space.
+        let mut records = Records::default();
+        {
+            let txn = db.read_transaction();
+            for block_number in chunk_start..=chunk_end {
+                let block = chain_store
+                    .get_block_at(block_number, false, Some(&txn))
+                    .with_context(|| {
+                        format!("Block {block_number} is missing, is this the database of a history node?")
+                    })?;
+                let history = history_store.get_block_transactions(block_number, Some(&txn));
+                records.add_block(&block, history);
+            }
+        }
+
+        writer.write(&records)?;
+        writer.checkpoint(chunk_end)?;
+        eprintln!("Exported blocks up to #{chunk_end} of #{to}");
+
+        chunk_start = match chunk_end.checked_add(1) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    Ok(())
+}
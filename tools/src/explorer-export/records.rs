@@ -0,0 +1,540 @@
+use nimiq_block::Block;
+use nimiq_hash::Blake2bHash;
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountType, policy::Policy};
+use nimiq_serde::Deserialize;
+use nimiq_transaction::{
+    account::staking_contract::{IncomingStakingTransactionData, OutgoingStakingTransactionData},
+    historic_transaction::{HistoricTransaction, HistoricTransactionData},
+    ExecutedTransaction, SignatureProof, Transaction,
+};
+use serde::Serialize;
+
+/// A block, without its body.
+#[derive(Serialize)]
+pub struct BlockRecord {
+    pub number: u32,
+    pub hash: String,
+    pub parent_hash: String,
+    pub timestamp: u64,
+    pub batch: u32,
+    pub epoch: u32,
+    pub block_type: &'static str,
+    pub is_election_block: bool,
+    pub transaction_count: usize,
+    pub extra_data: String,
+}
+
+/// A transaction included in a micro block.
+#[derive(Serialize)]
+pub struct TransactionRecord {
+    pub hash: String,
+    pub block_number: u32,
+    pub timestamp: u64,
+    pub index: usize,
+    pub sender: String,
+    pub sender_type: u8,
+    pub recipient: String,
+    pub recipient_type: u8,
+    /// In Luna.
+    pub value: u64,
+    /// In Luna.
+    pub fee: u64,
+    pub validity_start_height: u32,
+    pub succeeded: bool,
+    pub sender_data: String,
+    pub recipient_data: String,
+}
+
+/// A reward, penalty, jail or equivocation recorded in the history.
+#[derive(Serialize)]
+pub struct InherentRecord {
+    pub hash: String,
+    pub block_number: u32,
+    pub timestamp: u64,
+    pub inherent_type: &'static str,
+    pub validator_address: Option<String>,
+    pub reward_address: Option<String>,
+    /// In Luna.
+    pub value: Option<u64>,
+    pub offense_event_block: Option<u32>,
+}
+
+/// A log event of a transaction or an inherent. Log types are named like in the RPC, e.g.
+/// `pay-fee` or `create-staker`.
+#[derive(Serialize)]
+pub struct LogRecord {
+    pub block_number: u32,
+    pub hash: String,
+    pub log_type: &'static str,
+    pub address: String,
+    pub counterparty: Option<String>,
+    /// In Luna.
+    pub value: Option<u64>,
+}
+
+/// A change to a validator or a staker.
+#[derive(Serialize)]
+pub struct StakingRecord {
+    pub block_number: u32,
+    pub hash: String,
+    pub event: &'static str,
+    pub validator_address: Option<String>,
+    pub staker_address: Option<String>,
+    /// In Luna.
+    pub value: Option<u64>,
+}
+
+/// The records of a block range.
+#[derive(Default)]
+pub struct Records {
+    pub blocks: Vec<BlockRecord>,
+    pub transactions: Vec<TransactionRecord>,
+    pub inherents: Vec<InherentRecord>,
+    pub logs: Vec<LogRecord>,
+    pub staking: Vec<StakingRecord>,
+}
+
+fn address(address: &Address) -> String {
+    address.to_user_friendly_address()
+}
+
+impl Records {
+    /// Adds a block and its history, in the order it appears in the block.
+    pub fn add_block(&mut self, block: &Block, history: Vec<HistoricTransaction>) {
+        let block_number = block.block_number();
+        let hash: Blake2bHash = block.hash();
+        let transaction_count = history
+            .iter()
+            .filter(|hist_tx| matches!(hist_tx.data, HistoricTransactionData::Basic(_)))
+            .count();
+
+        self.blocks.push(BlockRecord {
+            number: block_number,
+            hash: hash.to_hex(),
+            parent_hash: block.parent_hash().to_hex(),
+            timestamp: block.timestamp(),
+            batch: Policy::batch_at(block_number),
+            epoch: Policy::epoch_at(block_number),
+            block_type: if block.is_macro() { "macro" } else { "micro" },
+            is_election_block: Policy::is_election_block_at(block_number),
+            transaction_count,
+            extra_data: hex::encode(block.extra_data()),
+        });
+
+        let mut index = 0;
+        for hist_tx in history {
+            let hash = Blake2bHash::from(hist_tx.tx_hash()).to_hex();
+            match hist_tx.data {
+                HistoricTransactionData::Basic(executed) => {
+                    self.add_transaction(&executed, hash, block_number, hist_tx.block_time, index);
+                    index += 1;
+                }
+                HistoricTransactionData::Reward(event) => {
+                    self.logs.push(LogRecord {
+                        block_number,
+                        hash: hash.clone(),
+                        log_type: "payout-reward",
+                        address: address(&event.reward_address),
+                        counterparty: Some(address(&event.validator_address)),
+                        value: Some(event.value.into()),
+                    });
+                    self.inherents.push(InherentRecord {
+                        hash,
+                        block_number,
+                        timestamp: hist_tx.block_time,
+                        inherent_type: "reward",
+                        validator_address: Some(address(&event.validator_address)),
+                        reward_address: Some(address(&event.reward_address)),
+                        value: Some(event.value.into()),
+                        offense_event_block: None,
+                    });
+                }
+                HistoricTransactionData::Penalize(event) => {
+                    self.add_punishment(
+                        "penalize",
+                        &event.validator_address,
+                        event.offense_event_block,
+                        hash,
+                        block_number,
+                        hist_tx.block_time,
+                    );
+                }
+                HistoricTransactionData::Jail(event) => {
+                    self.add_punishment(
+                        "jail",
+                        &event.validator_address,
+                        event.offense_event_block,
+                        hash,
+                        block_number,
+                        hist_tx.block_time,
+                    );
+                }
+                HistoricTransactionData::Equivocation(event) => {
+                    self.inherents.push(InherentRecord {
+                        hash,
+                        block_number,
+                        timestamp: hist_tx.block_time,
+                        inherent_type: "equivocation",
+                        validator_address: Some(address(event.locator.validator_address())),
+                        reward_address: None,
+                        value: None,
+                        offense_event_block: Some(event.locator.block_number()),
+                    });
+                }
+            }
+        }
+    }
+
+    fn add_punishment(
+        &mut self,
+        inherent_type: &'static str,
+        validator_address: &Address,
+        offense_event_block: u32,
+        hash: String,
+        block_number: u32,
+        timestamp: u64,
+    ) {
+        self.logs.push(LogRecord {
+            block_number,
+            hash: hash.clone(),
+            log_type: inherent_type,
+            address: address(validator_address),
+            counterparty: None,
+            value: None,
+        });
+        self.staking.push(StakingRecord {
+            block_number,
+            hash: hash.clone(),
+            event: inherent_type,
+            validator_address: Some(address(validator_address)),
+            staker_address: None,
+            value: None,
+        });
+        self.inherents.push(InherentRecord {
+            hash,
+            block_number,
+            timestamp,
+            inherent_type,
+            validator_address: Some(address(validator_address)),
+            reward_address: None,
+            value: None,
+            offense_event_block: Some(offense_event_block),
+        });
+    }
+
+    fn add_transaction(
+        &mut self,
+        executed: &ExecutedTransaction,
+        hash: String,
+        block_number: u32,
+        timestamp: u64,
+        index: usize,
+    ) {
+        let tx = executed.get_raw_transaction();
+        self.transactions.push(TransactionRecord {
+            hash: hash.clone(),
+            block_number,
+            timestamp,
+            index,
+            sender: address(&tx.sender),
+            sender_type: tx.sender_type as u8,
+            recipient: address(&tx.recipient),
+            recipient_type: tx.recipient_type as u8,
+            value: tx.value.into(),
+            fee: tx.fee.into(),
+            validity_start_height: tx.validity_start_height,
+            succeeded: executed.succeeded(),
+            sender_data: hex::encode(&tx.sender_data),
+            recipient_data: hex::encode(&tx.recipient_data),
+        });
+
+        let log = |log_type, address: String, counterparty: Option<String>, value: Option<u64>| {
+            LogRecord {
+                block_number,
+                hash: hash.clone(),
+                log_type,
+                address,
+                counterparty,
+                value,
+            }
+        };
+        if !tx.fee.is_zero() {
+            self.logs.push(log(
+                "pay-fee",
+                address(&tx.sender),
+                None,
+                Some(tx.fee.into()),
+            ));
+        }
+        if executed.failed() {
+            self.logs
+                .push(log("failed-transaction", address(&tx.sender), None, None));
+            return;
+        }
+        if !tx.value.is_zero() {
+            self.logs.push(log(
+                "transfer",
+                address(&tx.sender),
+                Some(address(&tx.recipient)),
+                Some(tx.value.into()),
+            ));
+        }
+
+        if let Some(staking) = StakingChange::from_transaction(tx) {
+            self.logs.push(log(
+                staking.event,
+                staking
+                    .staker_address
+                    .clone()
+                    .or_else(|| staking.validator_address.clone())
+                    .unwrap_or_else(|| address(&tx.sender)),
+                None,
+                staking.value,
+            ));
+            self.staking.push(StakingRecord {
+                block_number,
+                hash,
+                event: staking.event,
+                validator_address: staking.validator_address,
+                staker_address: staking.staker_address,
+                value: staking.value,
+            });
+        }
+    }
+}
+
+/// The change to a validator or staker a transaction makes.
+struct StakingChange {
+    event: &'static str,
+    validator_address: Option<String>,
+    staker_address: Option<String>,
+    value: Option<u64>,
+}
+
+impl StakingChange {
+    fn from_transaction(tx: &Transaction) -> Option<Self> {
+        if tx.recipient_type == AccountType::Staking {
+            return Self::from_incoming(tx);
+        }
+        if tx.sender_type == AccountType::Staking {
+            return Self::from_outgoing(tx);
+        }
+        None
+    }
+
+    fn from_incoming(tx: &Transaction) -> Option<Self> {
+        let signer = |proof: &SignatureProof| Some(address(&proof.compute_signer()));
+        let value = Some(tx.value.into());
+        let change = match IncomingStakingTransactionData::parse(tx).ok()? {
+            IncomingStakingTransactionData::CreateValidator { proof, .. } => Self {
+                event: "create-validator",
+                validator_address: signer(&proof),
+                staker_address: None,
+                value,
+            },
+            IncomingStakingTransactionData::UpdateValidator { proof, .. } => Self {
+                event: "update-validator",
+                validator_address: signer(&proof),
+                staker_address: None,
+                value: None,
+            },
+            IncomingStakingTransactionData::DeactivateValidator {
+                validator_address, ..
+            } => Self {
+                event: "deactivate-validator",
+                validator_address: Some(address(&validator_address)),
+                staker_address: None,
+                value: None,
+            },
+            IncomingStakingTransactionData::ReactivateValidator {
+                validator_address, ..
+            } => Self {
+                event: "reactivate-validator",
+                validator_address: Some(address(&validator_address)),
+                staker_address: None,
+                value: None,
+            },
+            IncomingStakingTransactionData::RetireValidator { proof } => Self {
+                event: "retire-validator",
+                validator_address: signer(&proof),
+                staker_address: None,
+                value: None,
+            },
+            IncomingStakingTransactionData::CreateStaker { delegation, proof } => Self {
+                event: "create-staker",
+                validator_address: delegation.as_ref().map(address),
+                staker_address: signer(&proof),
+                value,
+            },
+            IncomingStakingTransactionData::AddStake { staker_address } => Self {
+                event: "stake",
+                validator_address: None,
+                staker_address: Some(address(&staker_address)),
+                value,
+            },
+            IncomingStakingTransactionData::UpdateStaker {
+                new_delegation,
+                proof,
+                ..
+            } => Self {
+                event: "update-staker",
+                validator_address: new_delegation.as_ref().map(address),
+                staker_address: signer(&proof),
+                value: None,
+            },
+            IncomingStakingTransactionData::SetActiveStake {
+                new_active_balance,
+                proof,
+            } => Self {
+                event: "set-active-stake",
+                validator_address: None,
+                staker_address: signer(&proof),
+                value: Some(new_active_balance.into()),
+            },
+            IncomingStakingTransactionData::RetireStake {
+                retire_stake,
+                proof,
+            } => Self {
+                event: "retire-stake",
+                validator_address: None,
+                staker_address: signer(&proof),
+                value: Some(retire_stake.into()),
+            },
+        };
+        Some(change)
+    }
+
+    fn from_outgoing(tx: &Transaction) -> Option<Self> {
+        let signer = SignatureProof::deserialize_from_vec(&tx.proof)
+            .ok()
+            .map(|proof| address(&proof.compute_signer()));
+        let change = match OutgoingStakingTransactionData::parse(tx).ok()? {
+            OutgoingStakingTransactionData::DeleteValidator => Self {
+                event: "delete-validator",
+                validator_address: signer,
+                staker_address: None,
+                value: Some(tx.value.into()),
+            },
+            OutgoingStakingTransactionData::RemoveStake => Self {
+                event: "remove-stake",
+                validator_address: None,
+                staker_address: signer,
+                value: Some(tx.value.into()),
+            },
+        };
+        Some(change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_genesis::NetworkInfo;
+    use nimiq_primitives::{coin::Coin, networks::NetworkId};
+    use nimiq_transaction::{
+        historic_transaction::EquivocationEvent, EquivocationLocator, ForkLocator,
+    };
+
+    use super::*;
+
+    #[test]
+    fn it_records_blocks_and_transactions() {
+        let network_id = NetworkId::UnitAlbatross;
+        let block = NetworkInfo::from_network_id(network_id).genesis_block();
+        let block_number = block.block_number();
+        let historic_transaction = |value, fee, succeeded| {
+            let tx = Transaction::new_basic(
+                Address::from([1u8; 20]),
+                Address::from([2u8; 20]),
+                Coin::from_u64_unchecked(value),
+                Coin::from_u64_unchecked(fee),
+                block_number,
+                network_id,
+            );
+            HistoricTransaction {
+                network_id,
+                block_number,
+                block_time: block.timestamp(),
+                data: HistoricTransactionData::Basic(if succeeded {
+                    ExecutedTransaction::Ok(tx)
+                } else {
+                    ExecutedTransaction::Err(tx)
+                }),
+            }
+        };
+
+        let mut records = Records::default();
+        records.add_block(
+            &block,
+            vec![
+                historic_transaction(100, 1, true),
+                historic_transaction(200, 2, false),
+            ],
+        );
+
+        let hash: Blake2bHash = block.hash();
+        assert_eq!(records.blocks.len(), 1);
+        assert_eq!(records.blocks[0].number, block_number);
+        assert_eq!(records.blocks[0].hash, hash.to_hex());
+        assert_eq!(records.blocks[0].block_type, "macro");
+        assert_eq!(records.blocks[0].transaction_count, 2);
+
+        let transactions: Vec<_> = records
+            .transactions
+            .iter()
+            .map(|tx| (tx.index, tx.value, tx.succeeded))
+            .collect();
+        assert_eq!(transactions, vec![(0, 100, true), (1, 200, false)]);
+
+        // A failed transaction only pays its fee.
+        let logs: Vec<_> = records
+            .logs
+            .iter()
+            .map(|log| (log.log_type, log.value))
+            .collect();
+        assert_eq!(
+            logs,
+            vec![
+                ("pay-fee", Some(1)),
+                ("transfer", Some(100)),
+                ("pay-fee", Some(2)),
+                ("failed-transaction", None),
+            ]
+        );
+        assert!(records.inherents.is_empty());
+        assert!(records.staking.is_empty());
+    }
+
+    #[test]
+    fn it_records_the_offender_of_equivocations() {
+        let network_id = NetworkId::UnitAlbatross;
+        let block = NetworkInfo::from_network_id(network_id).genesis_block();
+        let validator_address = Address::from([3u8; 20]);
+        let offense_event_block = block.block_number();
+
+        let mut records = Records::default();
+        records.add_block(
+            &block,
+            vec![HistoricTransaction {
+                network_id,
+                block_number: block.block_number(),
+                block_time: block.timestamp(),
+                data: HistoricTransactionData::Equivocation(EquivocationEvent {
+                    locator: EquivocationLocator::Fork(ForkLocator {
+                        validator_address: validator_address.clone(),
+                        block_number: offense_event_block,
+                    }),
+                }),
+            }],
+        );
+
+        assert_eq!(records.inherents.len(), 1);
+        let inherent = &records.inherents[0];
+        assert_eq!(inherent.inherent_type, "equivocation");
+        assert_eq!(
+            inherent.validator_address,
+            Some(validator_address.to_user_friendly_address())
+        );
+        assert_eq!(inherent.offense_event_block, Some(offense_event_block));
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::records::Records;
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+/// The format of the exported files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Newline-delimited JSON, one record per line.
+    Ndjson,
+    /// CSV with a header line. Absent values are empty.
+    Csv,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+        }
+    }
+}
+
+/// The last block that was exported completely and the length of every file after it, so that
+/// records written after it can be discarded when resuming.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Checkpoint {
+    pub block_number: u32,
+    pub file_lengths: BTreeMap<String, u64>,
+}
+
+impl Checkpoint {
+    pub fn load(output: &Path) -> Result<Option<Self>, Error> {
+        let path = output.join(CHECKPOINT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Writes the checkpoint atomically, so that it is never read partially.
+    fn store(&self, output: &Path) -> Result<(), Error> {
+        let path = output.join(CHECKPOINT_FILE);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+enum Sink {
+    Ndjson(BufWriter<File>),
+    Csv(csv::Writer<File>),
+}
+
+/// One exported file, e.g. `blocks.csv`.
+struct Table {
+    name: String,
+    sink: Sink,
+}
+
+impl Table {
+    fn open(
+        output: &Path,
+        name: &str,
+        format: Format,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<Self, Error> {
+        let name = format!("{name}.{}", format.extension());
+        let path = output.join(&name);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(checkpoint.is_none())
+            .open(&path)?;
+
+        // Discard everything written after the checkpoint.
+        let length = match checkpoint {
+            Some(checkpoint) => {
+                let Some(length) = checkpoint.file_lengths.get(&name) else {
+                    bail!(
+                        "{name} is not part of the checkpoint, was it exported in another format?"
+                    );
+                };
+                file.set_len(*length)?;
+                *length
+            }
+            None => 0,
+        };
+        file.seek(SeekFrom::End(0))?;
+
+        let sink = match format {
+            Format::Ndjson => Sink::Ndjson(BufWriter::new(file)),
+            Format::Csv => Sink::Csv(
+                csv::WriterBuilder::new()
+                    .has_headers(length == 0)
+                    .from_writer(file),
+            ),
+        };
+        Ok(Self { name, sink })
+    }
+
+    fn write<T: Serialize>(&mut self, records: &[T]) -> Result<(), Error> {
+        for record in records {
+            match &mut self.sink {
+                Sink::Ndjson(writer) => {
+                    serde_json::to_writer(&mut *writer, record)?;
+                    writer.write_all(b"\n")?;
+                }
+                Sink::Csv(writer) => writer.serialize(record)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the file to disk and returns its length.
+    fn sync(&mut self) -> Result<u64, Error> {
+        let file = match &mut self.sink {
+            Sink::Ndjson(writer) => {
+                writer.flush()?;
+                writer.get_ref()
+            }
+            Sink::Csv(writer) => {
+                writer.flush()?;
+                writer.get_ref()
+            }
+        };
+        file.sync_data()?;
+        Ok(file.metadata()?.len())
+    }
+}
+
+/// Writes the records of blocks, transactions, inherents, logs and staking changes to one file
+/// each and keeps track of the progress in a checkpoint.
+pub struct Writer {
+    output: PathBuf,
+    blocks: Table,
+    transactions: Table,
+    inherents: Table,
+    logs: Table,
+    staking: Table,
+}
+
+impl Writer {
+    /// Creates the files in the output directory. If a checkpoint is given, the files are
+    /// continued from it, otherwise they are overwritten.
+    pub fn new(
+        output: &Path,
+        format: Format,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<Self, Error> {
+        fs::create_dir_all(output)?;
+        Ok(Self {
+            output: output.to_path_buf(),
+            blocks: Table::open(output, "blocks", format, checkpoint)?,
+            transactions: Table::open(output, "transactions", format, checkpoint)?,
+            inherents: Table::open(output, "inherents", format, checkpoint)?,
+            logs: Table::open(output, "logs", format, checkpoint)?,
+            staking: Table::open(output, "staking", format, checkpoint)?,
+        })
+    }
+
+    pub fn write(&mut self, records: &Records) -> Result<(), Error> {
+        self.blocks.write(&records.blocks)?;
+        self.transactions.write(&records.transactions)?;
+        self.inherents.write(&records.inherents)?;
+        self.logs.write(&records.logs)?;
+        self.staking.write(&records.staking)?;
+        Ok(())
+    }
+
+    /// Flushes all files and records that everything up to the given block has been exported.
+    pub fn checkpoint(&mut self, block_number: u32) -> Result<(), Error> {
+        let mut file_lengths = BTreeMap::new();
+        for table in [
+            &mut self.blocks,
+            &mut self.transactions,
+            &mut self.inherents,
+            &mut self.logs,
+            &mut self.staking,
+        ] {
+            file_lengths.insert(table.name.clone(), table.sync()?);
+        }
+        Checkpoint {
+            block_number,
+            file_lengths,
+        }
+        .store(&self.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::records::BlockRecord;
+
+    fn records(block_numbers: RangeInclusive<u32>) -> Records {
+        let mut records = Records::default();
+        for number in block_numbers {
+            records.blocks.push(BlockRecord {
+                number,
+                hash: format!("{number:064x}"),
+                parent_hash: format!("{:064x}", number - 1),
+                timestamp: number.into(),
+                batch: 0,
+                epoch: 0,
+                block_type: "micro",
+                is_election_block: false,
+                transaction_count: 0,
+                extra_data: String::new(),
+            });
+        }
+        records
+    }
+
+    /// Returns the block numbers in the blocks file, checking that a CSV file has one header.
+    fn exported_blocks(output: &Path, format: Format) -> Vec<u32> {
+        let content =
+            fs::read_to_string(output.join(format!("blocks.{}", format.extension()))).unwrap();
+        let mut lines = content.lines();
+        if format == Format::Csv {
+            assert!(lines.next().unwrap().starts_with("number,"));
+        }
+        lines
+            .map(|line| match format {
+                Format::Ndjson => {
+                    serde_json::from_str::<serde_json::Value>(line).unwrap()["number"]
+                        .as_u64()
+                        .unwrap() as u32
+                }
+                Format::Csv => line.split(',').next().unwrap().parse().unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_discards_records_after_the_checkpoint_when_resuming() {
+        for format in [Format::Ndjson, Format::Csv] {
+            let dir = tempdir().unwrap();
+            let output = dir.path();
+
+            let mut writer = Writer::new(output, format, None).unwrap();
+            writer.write(&records(1..=2)).unwrap();
+            writer.checkpoint(2).unwrap();
+            // These records are written to the file, but the export stops before the checkpoint.
+            writer.write(&records(3..=3)).unwrap();
+            drop(writer);
+            assert_eq!(exported_blocks(output, format), vec![1, 2, 3]);
+
+            let checkpoint = Checkpoint::load(output).unwrap().unwrap();
+            assert_eq!(checkpoint.block_number, 2);
+            let mut writer = Writer::new(output, format, Some(&checkpoint)).unwrap();
+            writer.write(&records(3..=4)).unwrap();
+            writer.checkpoint(4).unwrap();
+
+            assert_eq!(exported_blocks(output, format), vec![1, 2, 3, 4]);
+            assert_eq!(Checkpoint::load(output).unwrap().unwrap().block_number, 4);
+        }
+    }
+
+    #[test]
+    fn it_overwrites_files_without_a_checkpoint() {
+        let dir = tempdir().unwrap();
+        let output = dir.path();
+
+        let mut writer = Writer::new(output, Format::Csv, None).unwrap();
+        writer.write(&records(1..=2)).unwrap();
+        writer.checkpoint(2).unwrap();
+        drop(writer);
+
+        let mut writer = Writer::new(output, Format::Csv, None).unwrap();
+        writer.write(&records(5..=5)).unwrap();
+        writer.checkpoint(5).unwrap();
+        drop(writer);
+        assert_eq!(exported_blocks(output, Format::Csv), vec![5]);
+
+        // A checkpoint of a CSV export can't be resumed as NDJSON.
+        let checkpoint = Checkpoint::load(output).unwrap().unwrap();
+        assert!(Writer::new(output, Format::Ndjson, Some(&checkpoint)).is_err());
+    }
+}
@@ -1,14 +1,15 @@
+mod openapi;
 mod openrpc;
 mod parser;
 
 use std::{env, fs};
 
 use anyhow::Error;
-use clap::{crate_authors, crate_description, crate_version, Arg, Command};
+use clap::{crate_authors, crate_description, crate_version, Arg, ArgAction, Command};
 use syn::parse_file;
 use thiserror::Error;
 
-use crate::openrpc::OpenRpcBuilder;
+use crate::{openapi::OpenApiBuilder, openrpc::OpenRpcBuilder};
 
 fn main() -> Result<(), Error> {
     let matches = Command::new("RPC schema generator")
@@ -38,21 +39,28 @@ fn main() -> Result<(), Error> {
             .default_value("rpc-interface/src")
             .help("The folder that contains the source of the RPC interface traits and structs.")
         )
+        .arg(
+            Arg::new("openapi")
+            .long("openapi")
+            .action(ArgAction::SetTrue)
+            .help("Generate the OpenAPI document of the REST gateway instead of the OpenRPC document.")
+        )
         .get_matches();
 
-    let mut builder = OpenRpcBuilder::builder()
-        .version(
-            matches
-                .get_one::<String>("openrpc_version")
-                .ok_or(AppError::OpenRpcArgumentMissing("version".to_string()))?
-                .to_string(),
-        )
-        .title(
-            matches
-                .get_one::<String>("openrpc_title")
-                .ok_or(AppError::OpenRpcArgumentMissing("title".to_string()))?
-                .to_string(),
-        );
+    let version = matches
+        .get_one::<String>("openrpc_version")
+        .ok_or(AppError::OpenRpcArgumentMissing("version".to_string()))?
+        .to_string();
+
+    let mut openapi_builder = OpenApiBuilder::builder()
+        .version(version.clone())
+        .title("Nimiq REST Gateway Specification".to_string());
+    let mut builder = OpenRpcBuilder::builder().version(version).title(
+        matches
+            .get_one::<String>("openrpc_title")
+            .ok_or(AppError::OpenRpcArgumentMissing("title".to_string()))?
+            .to_string(),
+    );
 
     match fs::read_dir(
         matches
@@ -68,17 +76,24 @@ fn main() -> Result<(), Error> {
                             Ok(ast) => {
                                 let structs = parser::extract_structs_from_ast(&ast);
                                 let fns = parser::extract_fns_from_ast(&ast);
+                                let module = path
+                                    .file_name()
+                                    .unwrap()
+                                    .to_string_lossy()
+                                    .replace(".rs", "");
                                 for index in 0..structs.len() {
                                     builder = builder.with_schema(structs.get(index).unwrap());
+                                    openapi_builder =
+                                        openapi_builder.with_schema(structs.get(index).unwrap());
                                 }
                                 for index in 0..fns.len() {
-                                    builder = builder.with_method(
-                                        fns.get(index).unwrap(),
-                                        path.file_name()
-                                            .unwrap()
-                                            .to_string_lossy()
-                                            .replace(".rs", ""),
-                                    );
+                                    builder = builder
+                                        .with_method(fns.get(index).unwrap(), module.clone());
+                                    openapi_builder = openapi_builder
+                                        .with_method(fns.get(index).unwrap(), module.clone());
+                                }
+                                for (route, method) in parser::extract_rest_routes_from_ast(&ast) {
+                                    openapi_builder = openapi_builder.with_route(route, method);
                                 }
                             }
                             Err(err) => {
@@ -92,10 +107,19 @@ fn main() -> Result<(), Error> {
         Err(err) => return Err(AppError::LoadDirectoryError(err).into()),
     }
 
-    print!(
-        "{}",
-        serde_json::to_string_pretty(&builder.build()).expect("Failed to serialize OpenRPC spec")
-    );
+    if matches.get_flag("openapi") {
+        print!(
+            "{}",
+            serde_json::to_string_pretty(&openapi_builder.build())
+                .expect("Failed to serialize OpenAPI spec")
+        );
+    } else {
+        print!(
+            "{}",
+            serde_json::to_string_pretty(&builder.build())
+                .expect("Failed to serialize OpenRPC spec")
+        );
+    }
 
     Ok(())
 }
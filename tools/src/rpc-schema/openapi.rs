@@ -0,0 +1,172 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    openrpc::document::{ContentDescriptorOrReference, JSONSchema},
+    parser::{ParsedItemStruct, ParsedTraitItemFn},
+};
+
+/// A builder for constructing an OpenAPI document of the REST gateway. Every route is described
+/// by the RPC method serving it.
+#[derive(Clone, Default)]
+pub struct OpenApiBuilder {
+    title: String,
+    version: String,
+    structs: Vec<ParsedItemStruct>,
+    methods: Vec<(ParsedTraitItemFn, String)>,
+    routes: Vec<(String, String)>,
+}
+
+impl OpenApiBuilder {
+    /// Adds a schema based on a Rust struct to the OpenAPI document.
+    pub fn with_schema(mut self, item_struct: &ParsedItemStruct) -> OpenApiBuilder {
+        self.structs.push(item_struct.clone());
+        self
+    }
+
+    /// Adds a Rust trait method, which is only described if it serves a route.
+    pub fn with_method(mut self, item_fn: &ParsedTraitItemFn, module: String) -> OpenApiBuilder {
+        self.methods.push((item_fn.clone(), module));
+        self
+    }
+
+    /// Adds a route of the REST gateway, given by its path and the name of the RPC method
+    /// serving it.
+    pub fn with_route(mut self, path: String, method: String) -> OpenApiBuilder {
+        self.routes.push((path, method));
+        self
+    }
+
+    /// Sets the title of the OpenAPI document.
+    pub fn title(mut self, title: String) -> OpenApiBuilder {
+        self.title = title;
+        self
+    }
+
+    /// Sets the version of the OpenAPI document.
+    pub fn version(mut self, version: String) -> OpenApiBuilder {
+        self.version = version;
+        self
+    }
+
+    /// Creates a new instance of OpenApiBuilder with default settings and returns it.
+    pub fn builder() -> OpenApiBuilder {
+        OpenApiBuilder::default()
+    }
+
+    /// Builds the final OpenAPI document.
+    pub fn build(self) -> Value {
+        let schemas: Map<String, Value> = self
+            .structs
+            .iter()
+            .map(|s| (s.title(), s.schema(&self.structs)))
+            .collect();
+
+        let mut paths = Map::new();
+        for (path, method) in &self.routes {
+            let Some((item_fn, module)) = self.methods.iter().find(|(m, _)| &m.title() == method)
+            else {
+                panic!("No RPC method {method} for route {path}");
+            };
+            paths.insert(
+                path.clone(),
+                json!({ "get": self.operation(path, item_fn, module) }),
+            );
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": self.title,
+                "version": self.version,
+                "description": "The REST gateway of a Nimiq node serves blocks, transactions, accounts, validators and the mempool as resources. Every route is served by the JSON-RPC method of the same name as its operation ID and requires the same authorization. Streams are served as Server-Sent Events.",
+                "contact": { "name": env!("CARGO_PKG_AUTHORS"), "url": env!("CARGO_PKG_HOMEPAGE") },
+                "license": { "name": env!("CARGO_PKG_LICENSE") },
+            },
+            "paths": paths,
+            "components": { "schemas": schemas },
+        })
+    }
+
+    fn operation(&self, path: &str, item_fn: &ParsedTraitItemFn, module: &str) -> Value {
+        let parameters: Vec<Value> = item_fn
+            .params(&self.structs)
+            .into_iter()
+            .filter_map(|param| match param {
+                ContentDescriptorOrReference::ContentDescriptorObject(param) => Some(param),
+                ContentDescriptorOrReference::ReferenceObject(_) => None,
+            })
+            .map(|param| {
+                // Path parameters are always required, the gateway defaults all others.
+                let in_path = path.contains(&format!("{{{}}}", param.name));
+                let schema = match param.schema {
+                    JSONSchema::JsonSchemaObject(schema) => serde_json::to_value(schema.schema),
+                    JSONSchema::JSONSchemaBoolean(schema) => serde_json::to_value(schema),
+                }
+                .expect("Failed to serialize parameter schema");
+                json!({
+                    "name": param.name,
+                    "in": if in_path { "path" } else { "query" },
+                    "required": in_path,
+                    "schema": schema,
+                })
+            })
+            .collect();
+
+        let response = if item_fn.is_stream() {
+            let item = item_fn
+                .stream_item_schema(&self.structs)
+                .map(|schema| serde_json::to_value(schema).expect("Failed to serialize schema"))
+                .unwrap_or_else(|| json!({ "type": "object" }));
+            json!({
+                "description": "A stream of Server-Sent Events, each with the JSON of an item as data.",
+                "content": {
+                    "text/event-stream": { "schema": Self::rpc_data(item) },
+                },
+            })
+        } else {
+            let result = match item_fn.return_type(&self.structs) {
+                ContentDescriptorOrReference::ContentDescriptorObject(result) => {
+                    match result.schema {
+                        JSONSchema::JsonSchemaObject(schema) => serde_json::to_value(schema.schema),
+                        JSONSchema::JSONSchemaBoolean(schema) => serde_json::to_value(schema),
+                    }
+                    .expect("Failed to serialize result schema")
+                }
+                ContentDescriptorOrReference::ReferenceObject(_) => json!({ "type": "object" }),
+            };
+            json!({
+                "description": "The result of the RPC method.",
+                "content": {
+                    "application/json": { "schema": Self::rpc_data(result) },
+                },
+            })
+        };
+
+        json!({
+            "operationId": item_fn.title(),
+            "description": item_fn.description(),
+            "tags": [module],
+            "parameters": parameters,
+            "responses": {
+                "200": response,
+                "400": { "description": "A parameter is invalid." },
+                "401": { "description": "The credentials are missing or invalid." },
+                "403": { "description": "The RPC method serving the route is not allowed." },
+                "404": { "description": "The resource doesn't exist." },
+                "429": { "description": "The rate limit is exceeded." },
+            },
+        })
+    }
+
+    /// Wraps the schema of a result like `RPCData`, which adds metadata to it.
+    fn rpc_data(data: Value) -> Value {
+        json!({
+            "type": "object",
+            "required": ["data", "metadata"],
+            "properties": {
+                "data": data,
+                "metadata": {},
+            },
+        })
+    }
+}
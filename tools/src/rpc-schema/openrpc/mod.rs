@@ -1,7 +1,5 @@
 use std::collections::BTreeMap;
 
-use serde_json::{Map, Value};
-
 use self::document::{
     Components, ContactObject, InfoObject, LicenseObject, MethodObject, Openrpc, OpenrpcDocument,
     TagObject, TagOrReference,
@@ -87,12 +85,7 @@ impl OpenRpcBuilder {
             .expect("Component schema not initialized.");
 
         self.structs.iter().for_each(|s| {
-            let mut schema = Map::new();
-            schema.insert("title".into(), Value::String(s.title()));
-            schema.insert("description".into(), Value::String(s.description()));
-            schema.insert("required".into(), Value::Array(s.required_fields()));
-            schema.insert("properties".into(), s.properties(&self.structs));
-            schemas.insert(s.title(), Some(Value::Object(schema)));
+            schemas.insert(s.title(), Some(s.schema(&self.structs)));
         });
 
         self.methods.iter().for_each(|m| {
@@ -5,8 +5,8 @@ use schemars::schema::{
 };
 use serde_json::{Map, Value};
 use syn::{
-    Field, File, GenericArgument, Ident, ItemStruct, Pat, PatIdent, Path, PathArguments,
-    PathSegment, ReturnType, TraitItem, TraitItemFn, Type,
+    Expr, ExprLit, Field, File, GenericArgument, Ident, ItemStruct, Lit, Pat, PatIdent, Path,
+    PathArguments, PathSegment, ReturnType, TraitItem, TraitItemFn, Type,
 };
 
 use crate::openrpc::document::{ContentDescriptorObject, ContentDescriptorOrReference, JSONSchema};
@@ -31,6 +31,16 @@ impl ParsedItemStruct {
         "".into()
     }
 
+    /// Generates the JSON schema of the Rust struct.
+    pub fn schema(&self, structs: &[ParsedItemStruct]) -> Value {
+        let mut schema = Map::new();
+        schema.insert("title".into(), Value::String(self.title()));
+        schema.insert("description".into(), Value::String(self.description()));
+        schema.insert("required".into(), Value::Array(self.required_fields()));
+        schema.insert("properties".into(), self.properties(structs));
+        Value::Object(schema)
+    }
+
     /// Generates the properties for the Rust struct in the form of a JSON Object.
     pub fn properties(&self, structs: &[ParsedItemStruct]) -> Value {
         let props: Map<String, Value> = self
@@ -122,6 +132,7 @@ impl ParsedItemStruct {
             | "Blake2sHash"
             | "CompressedPublicKey"
             | "Ed25519PublicKey"
+            | "LogType"
             | "NetworkId"
             | "PrivateKey"
             | "Ed25519Signature"
@@ -220,6 +231,43 @@ impl ParsedTraitItemFn {
             .into()
     }
 
+    /// Determines whether the Rust trait method returns a stream of notifications.
+    pub fn is_stream(&self) -> bool {
+        self.0
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("stream"))
+    }
+
+    /// Generates the schema of the items of the stream returned by the Rust trait method, i.e.
+    /// of `T` in `Result<BoxStream<'static, RPCData<T, S>>, Self::Error>`.
+    pub fn stream_item_schema(&self, structs: &[ParsedItemStruct]) -> Option<SchemaObject> {
+        fn first_type_arg(segment: &PathSegment) -> Option<&PathSegment> {
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                    GenericArgument::Type(Type::Path(path)) => path.path.segments.first(),
+                    _ => None,
+                }),
+                _ => None,
+            }
+        }
+
+        let result = match &self.0.sig.output {
+            ReturnType::Type(_, ty) => match ty.as_ref() {
+                Type::Path(path) => path.path.segments.first()?,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let stream = first_type_arg(result)?;
+        let data = first_type_arg(stream)?;
+        let item = first_type_arg(data)?;
+
+        let inner_type = Self::unwrap_type(item);
+        let schema_ref = structs.iter().find(|s| inner_type.1 == s.title());
+        Some(Self::return_type_schema(item, schema_ref))
+    }
+
     /// Generates a list of parameters that accepted by the Rust trait method.
     pub fn params(&self, structs: &[ParsedItemStruct]) -> Vec<ContentDescriptorOrReference> {
         self.0
@@ -460,6 +508,41 @@ pub fn extract_structs_from_ast(file: &File) -> Vec<ParsedItemStruct> {
         .collect()
 }
 
+/// Filter a syntax tree and only extract the routes of the REST gateway, which are defined as
+/// `REST_ROUTES`, a slice of pairs of the path and the name of the RPC method serving it.
+pub fn extract_rest_routes_from_ast(file: &File) -> Vec<(String, String)> {
+    let string = |expr: &Expr| match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(string),
+            ..
+        }) => Some(string.value()),
+        _ => None,
+    };
+
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Const(item_const) if item_const.ident == "REST_ROUTES" => {
+                match item_const.expr.as_ref() {
+                    Expr::Reference(reference) => match reference.expr.as_ref() {
+                        Expr::Array(array) => Some(array.elems.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|route| match route {
+            Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                Some((string(&tuple.elems[0])?, string(&tuple.elems[1])?))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 /// Filter a syntrax tree and only extract the Rust methods within a trait definition.
 pub fn extract_fns_from_ast(file: &File) -> Vec<ParsedTraitItemFn> {
     file.items
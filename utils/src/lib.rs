@@ -10,6 +10,8 @@ pub mod math;
 pub mod merkle;
 #[cfg(feature = "otp")]
 pub mod otp;
+#[cfg(feature = "shutdown")]
+pub mod shutdown;
 #[cfg(feature = "spawn")]
 pub mod spawn;
 #[cfg(feature = "tagged-signing")]
@@ -0,0 +1,359 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Poll, Waker},
+    time::Duration,
+};
+
+use futures::{
+    future::{self, AbortHandle, Abortable, BoxFuture, Either},
+    FutureExt,
+};
+use parking_lot::Mutex;
+
+use crate::spawn::spawn;
+
+/// The stages of a coordinated shutdown. The stages are stopped one after the other, in the order
+/// in which they are declared here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShutdownStage {
+    /// The RPC server, such that no new requests are accepted.
+    Rpc,
+    /// The validator, such that it stops producing blocks.
+    Validator,
+    /// The consensus, such that no more blocks are pushed.
+    Consensus,
+    /// The mempool executors.
+    Mempool,
+    /// The network, closing the connections to all peers.
+    Network,
+    /// The ZKP component, including the prover process.
+    ZkpProver,
+    /// The database, which is flushed to disk.
+    Database,
+}
+
+impl ShutdownStage {
+    /// All stages in the order in which they are stopped.
+    pub const ALL: [ShutdownStage; 7] = [
+        ShutdownStage::Rpc,
+        ShutdownStage::Validator,
+        ShutdownStage::Consensus,
+        ShutdownStage::Mempool,
+        ShutdownStage::Network,
+        ShutdownStage::ZkpProver,
+        ShutdownStage::Database,
+    ];
+}
+
+impl fmt::Display for ShutdownStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ShutdownStage::Rpc => "rpc",
+            ShutdownStage::Validator => "validator",
+            ShutdownStage::Consensus => "consensus",
+            ShutdownStage::Mempool => "mempool",
+            ShutdownStage::Network => "network",
+            ShutdownStage::ZkpProver => "zkp-prover",
+            ShutdownStage::Database => "database",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a shutdown was requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// SIGINT was received, e.g. because of Ctrl+C.
+    Interrupt,
+    /// SIGTERM was received, e.g. because the container is being stopped.
+    Terminate,
+    /// The shutdown was requested via RPC.
+    Rpc,
+    /// The shutdown was requested by the application embedding the client.
+    Requested,
+}
+
+impl fmt::Display for ShutdownReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            ShutdownReason::Interrupt => "interrupt signal",
+            ShutdownReason::Terminate => "terminate signal",
+            ShutdownReason::Rpc => "RPC request",
+            ShutdownReason::Requested => "request",
+        };
+        f.write_str(reason)
+    }
+}
+
+enum StageEntry {
+    /// A task that is aborted. Resolves once the task was dropped.
+    Task {
+        name: String,
+        abort_handle: AbortHandle,
+        done: Arc<Mutex<TaskDone>>,
+    },
+    /// A hook that is run, e.g. to flush some state.
+    Hook {
+        name: String,
+        hook: Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>,
+    },
+}
+
+impl StageEntry {
+    fn name(&self) -> &str {
+        match self {
+            StageEntry::Task { name, .. } | StageEntry::Hook { name, .. } => name,
+        }
+    }
+
+    fn abort_handle(&self) -> Option<AbortHandle> {
+        match self {
+            StageEntry::Task { abort_handle, .. } => Some(abort_handle.clone()),
+            StageEntry::Hook { .. } => None,
+        }
+    }
+
+    fn stop(self) -> BoxFuture<'static, ()> {
+        match self {
+            StageEntry::Task {
+                abort_handle, done, ..
+            } => {
+                abort_handle.abort();
+                future::poll_fn(move |cx| {
+                    let mut done = done.lock();
+                    if done.done {
+                        Poll::Ready(())
+                    } else {
+                        done.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                })
+                .boxed()
+            }
+            StageEntry::Hook { hook, .. } => hook(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TaskDone {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+#[derive(Default)]
+struct ShutdownState {
+    reason: Option<ShutdownReason>,
+    listeners: Vec<Waker>,
+    stages: BTreeMap<ShutdownStage, Vec<StageEntry>>,
+}
+
+struct ShutdownInner {
+    stage_timeout: Duration,
+    running: AtomicBool,
+    state: Mutex<ShutdownState>,
+}
+
+/// Coordinates the shutdown of the components of a node.
+///
+/// Components register the tasks they spawn and the hooks they need to run with the stage they
+/// belong to. Once the shutdown is run, the stages are stopped in order: the hooks of a stage are
+/// run and its tasks are aborted afterwards, and the next stage is only stopped once they all
+/// finished or the stage timeout elapsed.
+///
+/// Requesting a shutdown only notifies the listeners; whoever drives the node is expected to
+/// listen for requests and [`run`](Shutdown::run) the shutdown.
+#[derive(Clone)]
+pub struct Shutdown {
+    inner: Arc<ShutdownInner>,
+}
+
+impl Shutdown {
+    /// The default time a single stage is given to stop.
+    pub const DEFAULT_STAGE_TIMEOUT: Duration = Duration::from_secs(3);
+
+    pub fn new(stage_timeout: Duration) -> Self {
+        Self {
+            inner: Arc::new(ShutdownInner {
+                stage_timeout,
+                running: AtomicBool::new(false),
+                state: Mutex::new(ShutdownState::default()),
+            }),
+        }
+    }
+
+    /// Spawns the given task, which is aborted when the given stage is stopped. If the shutdown is
+    /// running already, the task is not spawned at all.
+    pub fn spawn<F>(&self, stage: ShutdownStage, name: &str, future: F)
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        if self.is_running() {
+            log::debug!(name, "Not spawning task during shutdown");
+            return;
+        }
+
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let done = Arc::new(Mutex::new(TaskDone::default()));
+        let task = Abortable::new(future, abort_registration);
+        let task_done = Arc::clone(&done);
+        spawn(async move {
+            let _ = task.await;
+            let mut task_done = task_done.lock();
+            task_done.done = true;
+            if let Some(waker) = task_done.waker.take() {
+                waker.wake();
+            }
+        });
+
+        self.add_entry(
+            stage,
+            StageEntry::Task {
+                name: name.to_string(),
+                abort_handle,
+                done,
+            },
+        );
+    }
+
+    /// Registers a hook that is run when the given stage is stopped.
+    pub fn on_shutdown<F, Fut>(&self, stage: ShutdownStage, name: &str, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.add_entry(
+            stage,
+            StageEntry::Hook {
+                name: name.to_string(),
+                hook: Box::new(move || hook().boxed()),
+            },
+        );
+    }
+
+    fn add_entry(&self, stage: ShutdownStage, entry: StageEntry) {
+        self.inner
+            .state
+            .lock()
+            .stages
+            .entry(stage)
+            .or_default()
+            .push(entry);
+    }
+
+    /// Requests a shutdown. Returns `false` if a shutdown has been requested before, in which case
+    /// the given reason is ignored.
+    pub fn request(&self, reason: ShutdownReason) -> bool {
+        let mut state = self.inner.state.lock();
+        if state.reason.is_some() {
+            return false;
+        }
+
+        log::info!(%reason, "Shutdown requested");
+        state.reason = Some(reason);
+        for listener in state.listeners.drain(..) {
+            listener.wake();
+        }
+        true
+    }
+
+    /// Returns the reason of the shutdown request, if a shutdown has been requested.
+    pub fn requested_reason(&self) -> Option<ShutdownReason> {
+        self.inner.state.lock().reason
+    }
+
+    /// Resolves once a shutdown has been requested.
+    pub fn requested(&self) -> impl Future<Output = ShutdownReason> + Send + 'static {
+        let inner = Arc::clone(&self.inner);
+        future::poll_fn(move |cx| {
+            let mut state = inner.state.lock();
+            match state.reason {
+                Some(reason) => Poll::Ready(reason),
+                None => {
+                    if !state.listeners.iter().any(|w| w.will_wake(cx.waker())) {
+                        state.listeners.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+    }
+
+    /// Returns whether the shutdown is running or has finished.
+    pub fn is_running(&self) -> bool {
+        self.inner.running.load(Ordering::Acquire)
+    }
+
+    /// Stops all stages in order. This requests a shutdown if it hasn't been requested yet. The
+    /// shutdown is only run once, subsequent calls return immediately.
+    pub async fn run(&self) {
+        self.request(ShutdownReason::Requested);
+        if self.inner.running.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let mut stages = std::mem::take(&mut self.inner.state.lock().stages);
+        for stage in ShutdownStage::ALL {
+            let entries = match stages.remove(&stage) {
+                Some(entries) if !entries.is_empty() => entries,
+                _ => continue,
+            };
+
+            let names: Vec<String> = entries
+                .iter()
+                .map(|entry| entry.name().to_string())
+                .collect();
+            log::info!(%stage, components = ?names, "Stopping");
+
+            // The hooks of a stage are run before its tasks are aborted, since they might still rely
+            // on the tasks, e.g. to let the prover finish a running proof.
+            let (hooks, tasks): (Vec<_>, Vec<_>) = entries
+                .into_iter()
+                .partition(|entry| matches!(entry, StageEntry::Hook { .. }));
+            let abort_handles: Vec<AbortHandle> =
+                tasks.iter().filter_map(StageEntry::abort_handle).collect();
+            let stop = async move {
+                future::join_all(hooks.into_iter().map(StageEntry::stop)).await;
+                future::join_all(tasks.into_iter().map(StageEntry::stop)).await;
+            };
+
+            let timeout = nimiq_time::sleep(self.inner.stage_timeout);
+            if let Either::Right(_) = future::select(Box::pin(stop), Box::pin(timeout)).await {
+                // The tasks are aborted even if the hooks didn't finish in time.
+                for abort_handle in abort_handles {
+                    abort_handle.abort();
+                }
+                log::warn!(
+                    %stage,
+                    components = ?names,
+                    timeout = ?self.inner.stage_timeout,
+                    "Stage did not stop in time, continuing shutdown"
+                );
+            }
+        }
+
+        log::info!("Shutdown complete");
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_STAGE_TIMEOUT)
+    }
+}
+
+impl fmt::Debug for Shutdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shutdown")
+            .field("reason", &self.requested_reason())
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
@@ -4,3 +4,5 @@ pub mod crc;
 pub mod merkle;
 #[cfg(feature = "otp")]
 pub mod otp;
+#[cfg(feature = "shutdown")]
+pub mod shutdown;
@@ -0,0 +1,125 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::future;
+use nimiq_test_log::test;
+use nimiq_utils::shutdown::{Shutdown, ShutdownReason, ShutdownStage};
+use parking_lot::Mutex;
+
+/// Records its name once dropped, i.e. once the task holding it was aborted.
+struct DropGuard {
+    name: &'static str,
+    stopped: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.stopped.lock().push(self.name);
+    }
+}
+
+#[test(tokio::test)]
+async fn it_stops_stages_in_order() {
+    let shutdown = Shutdown::default();
+    let stopped = Arc::new(Mutex::new(vec![]));
+
+    // Register in reverse order to make sure the order of the stages is what counts.
+    let stopped1 = Arc::clone(&stopped);
+    shutdown.on_shutdown(ShutdownStage::Database, "database", move || async move {
+        stopped1.lock().push("database");
+    });
+    for (stage, name) in [
+        (ShutdownStage::Consensus, "consensus"),
+        (ShutdownStage::Validator, "validator"),
+        (ShutdownStage::Rpc, "rpc"),
+    ] {
+        let guard = DropGuard {
+            name,
+            stopped: Arc::clone(&stopped),
+        };
+        shutdown.spawn(stage, name, async move {
+            let _guard = guard;
+            future::pending::<()>().await
+        });
+    }
+
+    // Let the tasks start.
+    tokio::task::yield_now().await;
+    assert!(stopped.lock().is_empty());
+
+    shutdown.run().await;
+    assert!(shutdown.is_running());
+    assert_eq!(shutdown.requested_reason(), Some(ShutdownReason::Requested));
+    assert_eq!(
+        *stopped.lock(),
+        vec!["rpc", "validator", "consensus", "database"]
+    );
+
+    // Running it again does nothing.
+    shutdown.run().await;
+    assert_eq!(stopped.lock().len(), 4);
+}
+
+#[test(tokio::test)]
+async fn it_continues_after_stage_timeout() {
+    let shutdown = Shutdown::new(Duration::from_millis(10));
+    let stopped = Arc::new(Mutex::new(vec![]));
+
+    shutdown.on_shutdown(ShutdownStage::Network, "stuck", || future::pending());
+    let stopped1 = Arc::clone(&stopped);
+    shutdown.on_shutdown(ShutdownStage::Database, "database", move || async move {
+        stopped1.lock().push("database");
+    });
+
+    shutdown.run().await;
+    assert_eq!(*stopped.lock(), vec!["database"]);
+}
+
+#[test(tokio::test)]
+async fn it_runs_hooks_before_aborting_tasks() {
+    let shutdown = Shutdown::new(Duration::from_millis(10));
+    let stopped = Arc::new(Mutex::new(vec![]));
+
+    for (stage, name) in [
+        (ShutdownStage::ZkpProver, "prover"),
+        (ShutdownStage::Database, "stuck"),
+    ] {
+        let guard = DropGuard {
+            name,
+            stopped: Arc::clone(&stopped),
+        };
+        shutdown.spawn(stage, name, async move {
+            let _guard = guard;
+            future::pending::<()>().await
+        });
+    }
+
+    // The hook still sees the task of its stage running.
+    let stopped1 = Arc::clone(&stopped);
+    shutdown.on_shutdown(ShutdownStage::ZkpProver, "hook", move || async move {
+        tokio::task::yield_now().await;
+        stopped1.lock().push("hook");
+    });
+    // The task of a stage is aborted even if its hook doesn't finish in time.
+    shutdown.on_shutdown(ShutdownStage::Database, "stuck hook", || future::pending());
+
+    tokio::task::yield_now().await;
+    shutdown.run().await;
+
+    // Let the aborted task be dropped.
+    tokio::task::yield_now().await;
+    assert_eq!(*stopped.lock(), vec!["hook", "prover", "stuck"]);
+}
+
+#[test(tokio::test)]
+async fn it_notifies_about_requests() {
+    let shutdown = Shutdown::default();
+    let requested = shutdown.requested();
+
+    assert_eq!(shutdown.requested_reason(), None);
+    assert!(shutdown.request(ShutdownReason::Terminate));
+    assert!(!shutdown.request(ShutdownReason::Rpc));
+
+    assert_eq!(requested.await, ShutdownReason::Terminate);
+    assert_eq!(shutdown.requested().await, ShutdownReason::Terminate);
+    assert!(!shutdown.is_running());
+}
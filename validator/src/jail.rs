@@ -57,6 +57,11 @@ impl EquivocationProofPool {
         }
     }
 
+    /// Returns all equivocation proofs in the pool.
+    pub fn get_equivocation_proofs(&self) -> Vec<EquivocationProof> {
+        self.equivocation_proofs.iter().cloned().collect()
+    }
+
     /// Returns a list of current equivocation proofs.
     pub fn get_equivocation_proofs_for_block(&self, max_size: usize) -> Vec<EquivocationProof> {
         let mut proofs = Vec::new();
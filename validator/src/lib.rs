@@ -6,5 +6,11 @@ mod jail;
 mod r#macro;
 mod micro;
 mod proposal_buffer;
+pub mod telemetry;
 pub mod tendermint;
+#[cfg(feature = "metrics")]
+pub mod tendermint_metrics;
 pub mod validator;
+pub mod watchtower;
+#[cfg(feature = "metrics")]
+pub mod watchtower_metrics;
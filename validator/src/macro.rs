@@ -24,6 +24,7 @@ use crate::{
         state::MacroState,
         update_message::TendermintUpdate,
     },
+    telemetry::TendermintTelemetry,
     tendermint::TendermintProtocol,
 };
 
@@ -80,6 +81,7 @@ where
         network_id: NetworkId,
         block_height: u32,
         state_opt: Option<MacroState>,
+        telemetry: Arc<TendermintTelemetry>,
         proposal_stream: BoxStream<
             'static,
             SignedProposalMessage<Header<PubsubId<TValidatorNetwork>>, (SchnorrSignature, u16)>,
@@ -108,6 +110,7 @@ where
             validator_slot_band,
             network_id,
             block_height,
+            telemetry,
         );
 
         // create the Tendermint instance, which implements Stream
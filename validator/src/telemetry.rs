@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use nimiq_tendermint::Telemetry;
+use parking_lot::RwLock;
+
+#[cfg(feature = "metrics")]
+use crate::tendermint_metrics::TendermintMetrics;
+
+/// The Tendermint telemetry of a single macro block.
+#[derive(Clone, Debug)]
+pub struct MacroBlockTelemetry {
+    pub block_number: u32,
+    pub telemetry: Telemetry,
+}
+
+/// Keeps the Tendermint telemetry of the most recent macro blocks this validator took part in.
+#[derive(Default)]
+pub struct TendermintTelemetry {
+    recent: RwLock<VecDeque<MacroBlockTelemetry>>,
+    #[cfg(feature = "metrics")]
+    metrics: TendermintMetrics,
+}
+
+impl TendermintTelemetry {
+    /// The maximum number of macro blocks for which the telemetry is kept.
+    const MAX_RECENT: usize = 64;
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &TendermintMetrics {
+        &self.metrics
+    }
+
+    /// Records the telemetry of the Tendermint instance for the given macro block. A previous
+    /// record for the same block is replaced, which happens if the instance was restarted.
+    pub fn record(&self, block_number: u32, telemetry: Telemetry) {
+        #[cfg(feature = "metrics")]
+        self.metrics.note_telemetry(&telemetry);
+
+        let mut recent = self.recent.write();
+        recent.retain(|entry| entry.block_number != block_number);
+        recent.push_back(MacroBlockTelemetry {
+            block_number,
+            telemetry,
+        });
+        while recent.len() > Self::MAX_RECENT {
+            recent.pop_front();
+        }
+    }
+
+    /// Returns the telemetry for the given macro block, if it is still kept.
+    pub fn get(&self, block_number: u32) -> Option<MacroBlockTelemetry> {
+        self.recent
+            .read()
+            .iter()
+            .find(|entry| entry.block_number == block_number)
+            .cloned()
+    }
+
+    /// Returns the telemetry of the most recent macro blocks, oldest first.
+    pub fn recent(&self) -> Vec<MacroBlockTelemetry> {
+        self.recent.read().iter().cloned().collect()
+    }
+}
@@ -21,7 +21,7 @@ use nimiq_primitives::{
 use nimiq_serde::Serialize;
 use nimiq_tendermint::{
     Proposal, ProposalError, ProposalMessage, Protocol, ProtocolError, SignedProposalMessage, Step,
-    TaggedAggregationMessage,
+    TaggedAggregationMessage, Telemetry,
 };
 use nimiq_utils::spawn::spawn;
 use nimiq_validator_network::{
@@ -40,6 +40,7 @@ use crate::{
         },
     },
     r#macro::ProposalTopic,
+    telemetry::TendermintTelemetry,
 };
 
 // A note for the signing of the proposal:
@@ -116,6 +117,8 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     pub blockchain: Arc<RwLock<Blockchain>>,
     // Validator registry on the heap for easy cloning into handel protocol.
     validator_registry: Arc<ValidatorRegistry>,
+    // Collects the telemetry reported by the Tendermint instance.
+    telemetry: Arc<TendermintTelemetry>,
 }
 
 impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidatorNetwork> {
@@ -129,6 +132,7 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
+            telemetry: Arc::clone(&self.telemetry),
         }
     }
 }
@@ -145,6 +149,7 @@ where
         validator_slot_band: u16,
         network_id: NetworkId,
         block_height: u32,
+        telemetry: Arc<TendermintTelemetry>,
     ) -> Self {
         Self {
             block_producer,
@@ -155,6 +160,7 @@ where
             validator_registry: Arc::new(ValidatorRegistry::new(current_validators.clone())),
             current_validators,
             network,
+            telemetry,
         }
     }
 }
@@ -485,4 +491,8 @@ where
             }
         }
     }
+
+    fn report_telemetry(&self, telemetry: Telemetry) {
+        self.telemetry.record(self.block_height, telemetry);
+    }
 }
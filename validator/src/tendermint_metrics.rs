@@ -0,0 +1,147 @@
+use nimiq_tendermint::{Step, Telemetry};
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeLabelValue},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::Registry,
+};
+
+type HistogramConstructor = fn() -> Histogram;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct StepLabels {
+    step: StepLabel,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum StepLabel {
+    Propose,
+    Prevote,
+    Precommit,
+}
+
+impl From<Step> for StepLabels {
+    fn from(step: Step) -> Self {
+        let step = match step {
+            Step::Propose => StepLabel::Propose,
+            Step::Prevote => StepLabel::Prevote,
+            Step::Precommit => StepLabel::Precommit,
+        };
+        StepLabels { step }
+    }
+}
+
+fn duration_histogram() -> Histogram {
+    // 10ms up to ~80s.
+    Histogram::new(exponential_buckets(0.01, 2.0, 14))
+}
+
+pub struct TendermintMetrics {
+    macro_block_duration: Histogram,
+    macro_block_rounds: Histogram,
+    macro_block_undecided: Counter,
+    proposal_arrival: Histogram,
+    step_duration: Family<StepLabels, Histogram, HistogramConstructor>,
+    step_timeouts: Family<StepLabels, Counter>,
+    two_f_plus_one_after: Family<StepLabels, Histogram, HistogramConstructor>,
+}
+
+impl Default for TendermintMetrics {
+    fn default() -> Self {
+        Self {
+            macro_block_duration: duration_histogram(),
+            macro_block_rounds: Histogram::new(exponential_buckets(1.0, 2.0, 6)),
+            macro_block_undecided: Counter::default(),
+            proposal_arrival: duration_histogram(),
+            step_duration: Family::new_with_constructor(duration_histogram),
+            step_timeouts: Family::default(),
+            two_f_plus_one_after: Family::new_with_constructor(duration_histogram),
+        }
+    }
+}
+
+impl TendermintMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "macro_block_duration_seconds",
+            "Time it took to agree on a macro block",
+            self.macro_block_duration.clone(),
+        );
+
+        registry.register(
+            "macro_block_rounds",
+            "Number of Tendermint rounds needed to agree on a macro block",
+            self.macro_block_rounds.clone(),
+        );
+
+        registry.register(
+            "macro_block_undecided",
+            "Count of Tendermint instances that ended without a decision",
+            self.macro_block_undecided.clone(),
+        );
+
+        registry.register(
+            "proposal_arrival_seconds",
+            "Time after the start of a round at which its proposal was known",
+            self.proposal_arrival.clone(),
+        );
+
+        registry.register(
+            "step_duration_seconds",
+            "Time spent in each Tendermint step",
+            self.step_duration.clone(),
+        );
+
+        registry.register(
+            "step_timeouts",
+            "Count of Tendermint steps ended by their timeout",
+            self.step_timeouts.clone(),
+        );
+
+        registry.register(
+            "two_f_plus_one_seconds",
+            "Time after the start of a step at which its aggregation reached 2f+1 contributors",
+            self.two_f_plus_one_after.clone(),
+        );
+    }
+
+    pub(crate) fn note_telemetry(&self, telemetry: &Telemetry) {
+        if !telemetry.decided {
+            self.macro_block_undecided.inc();
+            return;
+        }
+
+        self.macro_block_duration
+            .observe(telemetry.duration.as_secs_f64());
+        self.macro_block_rounds
+            .observe(telemetry.num_rounds() as f64);
+
+        for round in &telemetry.rounds {
+            if let Some(proposal_after) = round.proposal_after {
+                self.proposal_arrival.observe(proposal_after.as_secs_f64());
+            }
+
+            for (step, step_telemetry) in [
+                (Step::Propose, &round.propose),
+                (Step::Prevote, &round.prevote),
+                (Step::Precommit, &round.precommit),
+            ] {
+                let labels = StepLabels::from(step);
+                self.step_duration
+                    .get_or_create(&labels)
+                    .observe(step_telemetry.duration.as_secs_f64());
+                if step_telemetry.timed_out {
+                    self.step_timeouts.get_or_create(&labels).inc();
+                }
+                if let Some(two_f_plus_one_after) = step_telemetry.two_f_plus_one_after {
+                    self.two_f_plus_one_after
+                        .get_or_create(&labels)
+                        .observe(two_f_plus_one_after.as_secs_f64());
+                }
+            }
+        }
+    }
+}
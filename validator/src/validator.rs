@@ -11,9 +11,13 @@ use std::{
 };
 
 use futures::stream::{BoxStream, StreamExt};
-use nimiq_block::{Block, BlockHeaderTopic, BlockTopic, BlockType, EquivocationProof};
+use nimiq_block::{
+    Block, BlockHeaderTopic, BlockTopic, BlockType, EquivocationProof, EquivocationProofTopic,
+};
 use nimiq_blockchain::{BlockProducer, Blockchain};
-use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, ForkEvent, PushResult};
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, BlockchainError, BlockchainEvent, ForkEvent, PushResult,
+};
 use nimiq_bls::{lazy::LazyPublicKey, KeyPair as BlsKeyPair};
 use nimiq_consensus::{Consensus, ConsensusEvent, ConsensusProxy};
 use nimiq_database::{
@@ -32,6 +36,7 @@ use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::spawn::spawn;
 use nimiq_validator_network::{PubsubId, ValidatorNetwork};
 use parking_lot::RwLock;
+use tokio::sync::mpsc;
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::BroadcastStream;
@@ -42,6 +47,7 @@ use crate::{
     micro::{ProduceMicroBlock, ProduceMicroBlockEvent},
     proposal_buffer::{ProposalBuffer, ProposalReceiver},
     r#macro::{MappedReturn, ProduceMacroBlock, ProposalTopic},
+    telemetry::TendermintTelemetry,
 };
 
 #[derive(PartialEq)]
@@ -58,6 +64,13 @@ pub struct ConsensusState {
     pub validity_window_synced: bool,
 }
 
+impl ConsensusState {
+    /// Returns the equivocation proofs that are waiting to be included in a block.
+    pub fn pending_equivocation_proofs(&self) -> Vec<EquivocationProof> {
+        self.equivocation_proofs.get_equivocation_proofs()
+    }
+}
+
 /// Validator inactivity
 struct InactivityState {
     inactive_tx_hash: Blake2bHash,
@@ -72,6 +85,11 @@ pub struct ValidatorProxy {
     pub automatic_reactivate: Arc<AtomicBool>,
     pub slot_band: Arc<RwLock<Option<u16>>>,
     pub consensus_state: Arc<RwLock<ConsensusState>>,
+    pub blockchain: Arc<RwLock<Blockchain>>,
+    /// Equivocation proofs sent here are added to the pool and gossiped to the other validators.
+    /// They must have been verified already.
+    pub equivocation_proof_tx: mpsc::UnboundedSender<EquivocationProof>,
+    pub tendermint_telemetry: Arc<TendermintTelemetry>,
 }
 
 impl Clone for ValidatorProxy {
@@ -84,6 +102,9 @@ impl Clone for ValidatorProxy {
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            blockchain: Arc::clone(&self.blockchain),
+            equivocation_proof_tx: self.equivocation_proof_tx.clone(),
+            tendermint_telemetry: Arc::clone(&self.tendermint_telemetry),
         }
     }
 }
@@ -110,6 +131,10 @@ where
     blockchain_event_rx: BoxStream<'static, BlockchainEvent>,
     network_event_rx: SubscribeEvents<<TValidatorNetwork::NetworkType as Network>::PeerId>,
     fork_event_rx: BroadcastStream<ForkEvent>,
+    equivocation_proof_rx:
+        mpsc::UnboundedReceiver<(EquivocationProof, PubsubId<TValidatorNetwork>)>,
+    submitted_equivocation_proof_tx: mpsc::UnboundedSender<EquivocationProof>,
+    submitted_equivocation_proof_rx: mpsc::UnboundedReceiver<EquivocationProof>,
 
     slot_band: Arc<RwLock<Option<u16>>>,
     consensus_state: Arc<RwLock<ConsensusState>>,
@@ -118,6 +143,7 @@ where
 
     macro_producer: Option<ProduceMacroBlock<TValidatorNetwork>>,
     macro_state: Arc<RwLock<Option<MacroState>>>,
+    tendermint_telemetry: Arc<TendermintTelemetry>,
 
     micro_producer: Option<ProduceMicroBlock<TValidatorNetwork>>,
 
@@ -197,6 +223,21 @@ where
                 .await
         });
 
+        let (equivocation_proof_tx, equivocation_proof_rx) = mpsc::unbounded_channel();
+        let network2 = Arc::clone(&network);
+        spawn(async move {
+            network2
+                .subscribe::<EquivocationProofTopic>()
+                .await
+                .expect("Failed to subscribe to equivocation proof topic")
+                .for_each(|proof| async {
+                    equivocation_proof_tx.send(proof).ok();
+                })
+                .await
+        });
+        let (submitted_equivocation_proof_tx, submitted_equivocation_proof_rx) =
+            mpsc::unbounded_channel();
+
         Self {
             consensus: consensus.proxy(),
             blockchain,
@@ -216,6 +257,9 @@ where
             blockchain_event_rx,
             network_event_rx,
             fork_event_rx,
+            equivocation_proof_rx,
+            submitted_equivocation_proof_tx,
+            submitted_equivocation_proof_rx,
 
             slot_band: Arc::new(RwLock::new(None)),
             consensus_state: Arc::new(RwLock::new(blockchain_state)),
@@ -224,6 +268,7 @@ where
 
             macro_producer: None,
             macro_state: Arc::clone(&macro_state),
+            tendermint_telemetry: Arc::new(TendermintTelemetry::default()),
 
             micro_producer: None,
 
@@ -357,6 +402,7 @@ where
                     network_id,
                     next_block_number,
                     self.macro_state.read().clone(),
+                    Arc::clone(&self.tendermint_telemetry),
                     proposal_stream,
                 ));
             }
@@ -548,6 +594,47 @@ where
         }
     }
 
+    /// Handles an equivocation proof received via gossip. Valid proofs are added to the pool and
+    /// relayed to the other validators.
+    fn on_equivocation_proof_message(
+        &mut self,
+        proof: EquivocationProof,
+        pubsub_id: PubsubId<TValidatorNetwork>,
+    ) {
+        let result = self.blockchain.read().verify_equivocation_proof(&proof);
+        let acceptance = match result {
+            Ok(()) => {
+                self.on_equivocation_proof(proof);
+                MsgAcceptance::Accept
+            }
+            // The proof might have been valid when it was sent.
+            Err(BlockchainError::EquivocationAlreadyIncluded(_))
+            | Err(BlockchainError::EquivocationProofExpired) => MsgAcceptance::Ignore,
+            Err(error) => {
+                debug!(%error, ?proof, "Received invalid equivocation proof");
+                MsgAcceptance::Reject
+            }
+        };
+        self.network
+            .validate_message::<EquivocationProofTopic>(pubsub_id, acceptance);
+    }
+
+    /// Handles an equivocation proof that was submitted locally, e.g. via RPC. The proof is added
+    /// to the pool and gossiped to the other validators.
+    fn on_submitted_equivocation_proof(&mut self, proof: EquivocationProof) {
+        self.on_equivocation_proof(proof.clone());
+
+        let network = Arc::clone(&self.network);
+        spawn(async move {
+            if let Err(e) = network.publish::<EquivocationProofTopic>(proof).await {
+                debug!(
+                    error = &e as &dyn Error,
+                    "Failed to publish equivocation proof"
+                );
+            }
+        });
+    }
+
     fn on_equivocation_proof(&mut self, proof: EquivocationProof) {
         // Keep the lock until the proof is added to the proof pool.
         let blockchain = self.blockchain.read();
@@ -820,9 +907,16 @@ where
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
+            blockchain: Arc::clone(&self.blockchain),
+            equivocation_proof_tx: self.submitted_equivocation_proof_tx.clone(),
+            tendermint_telemetry: Arc::clone(&self.tendermint_telemetry),
         }
     }
 
+    pub fn tendermint_telemetry(&self) -> Arc<TendermintTelemetry> {
+        Arc::clone(&self.tendermint_telemetry)
+    }
+
     #[cfg(feature = "metrics")]
     pub fn get_mempool_monitor(&self) -> TaskMonitor {
         self.mempool_monitor.clone()
@@ -865,6 +959,19 @@ where
             }
         }
 
+        // Process equivocation proofs received from other nodes or submitted locally.
+        while let Poll::Ready(Some((proof, pubsub_id))) = self.equivocation_proof_rx.poll_recv(cx) {
+            if self.consensus.is_established() {
+                self.on_equivocation_proof_message(proof, pubsub_id);
+            } else {
+                self.network
+                    .validate_message::<EquivocationProofTopic>(pubsub_id, MsgAcceptance::Ignore);
+            }
+        }
+        while let Poll::Ready(Some(proof)) = self.submitted_equivocation_proof_rx.poll_recv(cx) {
+            self.on_submitted_equivocation_proof(proof);
+        }
+
         // If we are an active validator, participate in block production.
         if self.is_synced() && self.is_elected() {
             if self.macro_producer.is_some() {
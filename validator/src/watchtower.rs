@@ -0,0 +1,645 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    error::Error,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::stream::StreamExt;
+use nimiq_block::{EquivocationProof, EquivocationProofTopic};
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, ForkEvent};
+use nimiq_consensus::ConsensusProxy;
+use nimiq_database::{
+    traits::{Database, ReadTransaction, WriteTransaction},
+    DatabaseProxy, TableProxy,
+};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_network_interface::network::MsgAcceptance;
+use nimiq_primitives::policy::Policy;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{DoubleProposalLocator, EquivocationLocator};
+use nimiq_utils::spawn::spawn;
+use nimiq_validator_network::{PubsubId, ValidatorNetwork};
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::BroadcastStream;
+
+#[cfg(feature = "metrics")]
+use crate::watchtower_metrics::WatchtowerMetrics;
+use crate::{aggregation::tendermint::proposal::SignedProposal, r#macro::ProposalTopic};
+
+/// The watchtower observes the network for equivocations without taking part in consensus, thus
+/// it doesn't need any validator keys.
+///
+/// * Forks are detected by the blockchain while pushing the blocks received via gossip. The
+///   resulting fork proofs are published on the equivocation proof topic, such that the
+///   validators include them in a block.
+/// * Tendermint proposals are observed on the proposal topic. A validator signing two different
+///   proposals for the same height and round is alerted on and both signed proposals are kept as
+///   evidence, see [`Watchtower::get_conflicting_proposals`]. Proposals are signed over the
+///   proposal message rather than the macro header, so the evidence doesn't verify as a
+///   `DoubleProposalProof` and isn't published.
+/// * Tendermint votes are only aggregated among the validators and are never gossiped, so double
+///   votes can't be observed. Double vote and other equivocation proofs published by the
+///   validators are verified and recorded instead.
+///
+/// Every equivocation is reported only once. The reported equivocation locators are persisted,
+/// such that they are not reported again after a restart.
+pub struct Watchtower<TValidatorNetwork: ValidatorNetwork + 'static>
+where
+    PubsubId<TValidatorNetwork>: std::fmt::Debug + Unpin,
+{
+    consensus: ConsensusProxy<TValidatorNetwork::NetworkType>,
+    blockchain: Arc<RwLock<Blockchain>>,
+    network: Arc<TValidatorNetwork>,
+
+    env: DatabaseProxy,
+    reported: TableProxy,
+    evidence: TableProxy,
+
+    fork_event_rx: BroadcastStream<ForkEvent>,
+    proposal_rx: mpsc::UnboundedReceiver<(SignedProposal, PubsubId<TValidatorNetwork>)>,
+    equivocation_proof_rx:
+        mpsc::UnboundedReceiver<(EquivocationProof, PubsubId<TValidatorNetwork>)>,
+
+    /// The verified proposals by block number, round and proposer slot band.
+    proposals: BTreeMap<(u32, u32, u16), SignedProposal>,
+
+    #[cfg(feature = "metrics")]
+    metrics: Arc<WatchtowerMetrics>,
+}
+
+impl<TValidatorNetwork: ValidatorNetwork + 'static> Watchtower<TValidatorNetwork>
+where
+    PubsubId<TValidatorNetwork>: std::fmt::Debug + Unpin,
+{
+    const REPORTED_DB_NAME: &'static str = "WatchtowerReported";
+    const EVIDENCE_DB_NAME: &'static str = "WatchtowerEvidence";
+
+    pub fn new(
+        env: DatabaseProxy,
+        consensus: ConsensusProxy<TValidatorNetwork::NetworkType>,
+        blockchain: Arc<RwLock<Blockchain>>,
+        network: Arc<TValidatorNetwork>,
+    ) -> Self {
+        let fork_event_rx = BroadcastStream::new(blockchain.read().fork_notifier.subscribe());
+        let reported = env.open_table(Self::REPORTED_DB_NAME.to_string());
+        let evidence = env.open_table(Self::EVIDENCE_DB_NAME.to_string());
+
+        let (proposal_tx, proposal_rx) = mpsc::unbounded_channel();
+        let network1 = Arc::clone(&network);
+        spawn(async move {
+            network1
+                .subscribe::<ProposalTopic<TValidatorNetwork>>()
+                .await
+                .expect("Failed to subscribe to proposal topic")
+                .for_each(|proposal| async {
+                    proposal_tx.send(proposal).ok();
+                })
+                .await
+        });
+
+        let (equivocation_proof_tx, equivocation_proof_rx) = mpsc::unbounded_channel();
+        let network2 = Arc::clone(&network);
+        spawn(async move {
+            network2
+                .subscribe::<EquivocationProofTopic>()
+                .await
+                .expect("Failed to subscribe to equivocation proof topic")
+                .for_each(|proof| async {
+                    equivocation_proof_tx.send(proof).ok();
+                })
+                .await
+        });
+
+        Self {
+            consensus,
+            blockchain,
+            network,
+            env,
+            reported,
+            evidence,
+            fork_event_rx,
+            proposal_rx,
+            equivocation_proof_rx,
+            proposals: BTreeMap::new(),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(WatchtowerMetrics::default()),
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Arc<WatchtowerMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Returns whether the equivocation at the given locator has been reported already.
+    pub fn has_reported(&self, locator: &EquivocationLocator) -> bool {
+        let txn = self.env.read_transaction();
+        txn.get::<Blake2bHash, Vec<u8>>(&self.reported, &locator.hash())
+            .is_some()
+    }
+
+    /// Returns the conflicting proposals observed for the double proposal at the given locator.
+    pub fn get_conflicting_proposals(
+        &self,
+        locator: &EquivocationLocator,
+    ) -> Option<ConflictingProposals> {
+        let txn = self.env.read_transaction();
+        let evidence = txn.get::<Blake2bHash, Vec<u8>>(&self.evidence, &locator.hash())?;
+        ConflictingProposals::deserialize_from_vec(&evidence).ok()
+    }
+
+    fn mark_reported(
+        &self,
+        locator: &EquivocationLocator,
+        evidence: Option<&ConflictingProposals>,
+    ) {
+        let mut txn = self.env.write_transaction();
+        txn.put::<Blake2bHash, Vec<u8>>(
+            &self.reported,
+            &locator.hash(),
+            &locator.serialize_to_vec(),
+        );
+        if let Some(evidence) = evidence {
+            txn.put::<Blake2bHash, Vec<u8>>(
+                &self.evidence,
+                &locator.hash(),
+                &evidence.serialize_to_vec(),
+            );
+        }
+        txn.commit();
+    }
+
+    fn on_fork_event(&mut self, event: ForkEvent) {
+        match event {
+            ForkEvent::Detected(fork_proof) => self.report_equivocation_proof(fork_proof.into()),
+        }
+    }
+
+    /// Reports an equivocation proof detected by this node by publishing it to the validators.
+    fn report_equivocation_proof(&mut self, proof: EquivocationProof) {
+        let locator = proof.locator();
+        if self.has_reported(&locator) {
+            return;
+        }
+        if let Err(error) = self.blockchain.read().verify_equivocation_proof(&proof) {
+            debug!(%error, ?proof, "Not reporting equivocation proof");
+            return;
+        }
+
+        warn!(
+            validator_address = %proof.validator_address(),
+            block_number = proof.block_number(),
+            ?locator,
+            "Detected equivocation, reporting proof"
+        );
+        #[cfg(feature = "metrics")]
+        self.metrics.note_equivocation(&proof, true);
+        self.mark_reported(&locator, None);
+
+        let network = Arc::clone(&self.network);
+        spawn(async move {
+            if let Err(e) = network.publish::<EquivocationProofTopic>(proof).await {
+                debug!(
+                    error = &e as &dyn Error,
+                    "Failed to publish equivocation proof"
+                );
+            }
+        });
+    }
+
+    /// Handles an equivocation proof received via gossip. Valid proofs are recorded and relayed.
+    fn on_equivocation_proof_message(
+        &mut self,
+        proof: EquivocationProof,
+        pubsub_id: PubsubId<TValidatorNetwork>,
+    ) {
+        let result = self.blockchain.read().verify_equivocation_proof(&proof);
+        let acceptance = match result {
+            Ok(()) => {
+                let locator = proof.locator();
+                if !self.has_reported(&locator) {
+                    warn!(
+                        validator_address = %proof.validator_address(),
+                        block_number = proof.block_number(),
+                        ?locator,
+                        "Received equivocation proof, relaying it"
+                    );
+                    #[cfg(feature = "metrics")]
+                    self.metrics.note_equivocation(&proof, false);
+                    self.mark_reported(&locator, None);
+                }
+                MsgAcceptance::Accept
+            }
+            // The proof might have been valid when it was sent.
+            Err(BlockchainError::EquivocationAlreadyIncluded(_))
+            | Err(BlockchainError::EquivocationProofExpired) => MsgAcceptance::Ignore,
+            Err(error) => {
+                debug!(%error, ?proof, "Received invalid equivocation proof");
+                MsgAcceptance::Reject
+            }
+        };
+        self.network
+            .validate_message::<EquivocationProofTopic>(pubsub_id, acceptance);
+    }
+
+    /// Handles a Tendermint proposal received via gossip. The watchtower doesn't take part in
+    /// Tendermint, so proposals are never relayed.
+    fn on_proposal_message(
+        &mut self,
+        proposal: SignedProposal,
+        pubsub_id: PubsubId<TValidatorNetwork>,
+    ) {
+        self.network
+            .validate_message::<ProposalTopic<TValidatorNetwork>>(pubsub_id, MsgAcceptance::Ignore);
+
+        let blockchain = self.blockchain.read();
+        let head_block_number = blockchain.block_number();
+        let block_number = proposal.proposal.block_number;
+
+        // Only proposals for the current epoch can be attributed to a validator.
+        if block_number <= head_block_number
+            || block_number > Policy::election_block_after(head_block_number)
+        {
+            return;
+        }
+        let validators = match blockchain.current_validators() {
+            Some(validators) => validators,
+            None => return,
+        };
+        drop(blockchain);
+
+        // Forget about the proposals for heights that have been finalized.
+        self.proposals = self.proposals.split_off(&(head_block_number + 1, 0, 0));
+
+        if proposal.signer as usize >= validators.num_validators() {
+            return;
+        }
+        let proposer = validators.get_validator_by_slot_band(proposal.signer);
+        let data = SignedProposal::hash(&proposal.proposal, proposal.round, proposal.valid_round)
+            .serialize_to_vec();
+        if !proposer.signing_key.verify(&proposal.signature, &data) {
+            return;
+        }
+
+        let header_hash: Blake2bHash = proposal.proposal.hash();
+        match self
+            .proposals
+            .entry((block_number, proposal.round, proposal.signer))
+        {
+            Entry::Vacant(entry) => {
+                entry.insert(proposal);
+            }
+            Entry::Occupied(entry) if entry.get().proposal.hash::<Blake2bHash>() != header_hash => {
+                let proposal1 = entry.get().clone();
+                let locator = EquivocationLocator::from(DoubleProposalLocator {
+                    validator_address: proposer.address.clone(),
+                    block_number,
+                    round: proposal.round,
+                });
+                if self.has_reported(&locator) {
+                    return;
+                }
+
+                warn!(
+                    validator_address = %proposer.address,
+                    block_number,
+                    round = proposal.round,
+                    "Validator signed conflicting Tendermint proposals"
+                );
+                #[cfg(feature = "metrics")]
+                self.metrics.note_conflicting_proposal();
+                let evidence = ConflictingProposals {
+                    validator_address: proposer.address.clone(),
+                    proposal1,
+                    proposal2: proposal,
+                };
+                self.mark_reported(&locator, Some(&evidence));
+            }
+            Entry::Occupied(_) => {}
+        }
+    }
+}
+
+/// Two different Tendermint proposals signed by the same validator for the same height and round.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConflictingProposals {
+    /// Address of the offending validator.
+    pub validator_address: Address,
+    /// The proposal that was observed first.
+    pub proposal1: SignedProposal,
+    /// The conflicting proposal.
+    pub proposal2: SignedProposal,
+}
+
+impl<TValidatorNetwork: ValidatorNetwork + 'static> Future for Watchtower<TValidatorNetwork>
+where
+    PubsubId<TValidatorNetwork>: std::fmt::Debug + Unpin,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Process fork events.
+        while let Poll::Ready(Some(Ok(event))) = self.fork_event_rx.poll_next_unpin(cx) {
+            let consensus_established = self.consensus.is_established();
+            trace!(?event, consensus_established, "fork event");
+            if consensus_established {
+                self.on_fork_event(event);
+            }
+        }
+
+        // Process equivocation proofs received from other nodes.
+        while let Poll::Ready(Some((proof, pubsub_id))) = self.equivocation_proof_rx.poll_recv(cx) {
+            if self.consensus.is_established() {
+                self.on_equivocation_proof_message(proof, pubsub_id);
+            } else {
+                self.network
+                    .validate_message::<EquivocationProofTopic>(pubsub_id, MsgAcceptance::Ignore);
+            }
+        }
+
+        // Process Tendermint proposals.
+        while let Poll::Ready(Some((proposal, pubsub_id))) = self.proposal_rx.poll_recv(cx) {
+            if self.consensus.is_established() {
+                self.on_proposal_message(proposal, pubsub_id);
+            } else {
+                self.network
+                    .validate_message::<ProposalTopic<TValidatorNetwork>>(
+                        pubsub_id,
+                        MsgAcceptance::Ignore,
+                    );
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Arc, time::Duration};
+
+    use futures::{poll, select, FutureExt, StreamExt};
+    use nimiq_block::MacroHeader;
+    use nimiq_blockchain::Blockchain;
+    use nimiq_blockchain_interface::AbstractBlockchain;
+    use nimiq_blockchain_proxy::BlockchainProxy;
+    use nimiq_bls::cache::PublicKeyCache;
+    use nimiq_consensus::{
+        sync::syncer_proxy::SyncerProxy, Consensus, ConsensusEvent, ConsensusProxy,
+    };
+    use nimiq_database::volatile::VolatileDatabase;
+    use nimiq_keys::{Address, KeyPair as SchnorrKeyPair, PrivateKey as SchnorrPrivateKey};
+    use nimiq_network_interface::network::Network as NetworkInterface;
+    use nimiq_network_mock::{MockHub, MockNetwork};
+    use nimiq_primitives::policy::Policy;
+    use nimiq_serde::{Deserialize, Serialize};
+    use nimiq_tendermint::{ProposalMessage, SignedProposalMessage};
+    use nimiq_test_log::test;
+    use nimiq_test_utils::{
+        block_production::{TemporaryBlockProducer, SIGNING_KEY},
+        test_network::TestNetwork,
+    };
+    use nimiq_time::{sleep, timeout};
+    use nimiq_transaction::{DoubleProposalLocator, EquivocationLocator, ForkLocator};
+    use nimiq_utils::spawn::spawn;
+    use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
+    use nimiq_zkp_component::ZKPComponent;
+    use parking_lot::{Mutex, RwLock};
+
+    use super::Watchtower;
+    use crate::{
+        aggregation::tendermint::proposal::{Header, SignedProposal},
+        r#macro::ProposalTopic,
+    };
+
+    type TestWatchtower = Watchtower<ValidatorNetworkImpl<MockNetwork>>;
+
+    /// Given a blockchain and a network creates an instance of Consensus.
+    async fn consensus<N: NetworkInterface + TestNetwork>(
+        blockchain: Arc<RwLock<Blockchain>>,
+        net: Arc<N>,
+    ) -> Consensus<N> {
+        let blockchain_proxy = BlockchainProxy::from(&blockchain);
+
+        let zkp_proxy = ZKPComponent::new(blockchain_proxy.clone(), Arc::clone(&net), None)
+            .await
+            .proxy();
+
+        let syncer = SyncerProxy::new_history(
+            blockchain_proxy.clone(),
+            Arc::clone(&net),
+            Arc::new(Mutex::new(PublicKeyCache::new(10))),
+            net.subscribe_events(),
+        )
+        .await;
+
+        Consensus::new(blockchain_proxy, net, syncer, 0, zkp_proxy)
+    }
+
+    /// Creates two nodes with established consensus. The watchtower observes the second one.
+    async fn setup() -> (
+        ConsensusProxy<MockNetwork>,
+        TemporaryBlockProducer,
+        TemporaryBlockProducer,
+        Arc<MockNetwork>,
+        Arc<MockNetwork>,
+    ) {
+        let mut hub = MockHub::default();
+        let nw1 = Arc::new(hub.new_network_with_address(0));
+        let nw2 = Arc::new(hub.new_network_with_address(1));
+
+        let producer1 = TemporaryBlockProducer::new();
+        let producer2 = TemporaryBlockProducer::new();
+
+        let consensus1 = consensus(Arc::clone(&producer1.blockchain), Arc::clone(&nw1)).await;
+        let consensus2 = consensus(Arc::clone(&producer2.blockchain), Arc::clone(&nw2)).await;
+        let consensus_proxy = consensus2.proxy();
+
+        let mut consensus_events1 = consensus1.subscribe_events().boxed().fuse();
+        let mut consensus_events2 = consensus2.subscribe_events().boxed().fuse();
+        let mut deadline = sleep(Duration::from_millis(200)).boxed().fuse();
+
+        spawn(consensus1);
+        spawn(consensus2);
+
+        nw1.dial_mock(&nw2);
+        let mut established = (false, false);
+        loop {
+            select! {
+                event = consensus_events1.next() => {
+                    match event {
+                        Some(Ok(ConsensusEvent::Established)) => {
+                            if established.1 {
+                                break
+                            }
+                            established.0 = true;
+                        }
+                        _ => established.0 = false,
+                    }
+                }
+                event = consensus_events2.next() => {
+                    match event {
+                        Some(Ok(ConsensusEvent::Established)) => {
+                            if established.0 {
+                                break;
+                            }
+                            established.1 = true;
+                        }
+                        _ => established.1 = false,
+                    }
+                }
+                _elapsed = deadline => panic!("Failed to establish consensus!"),
+            }
+        }
+
+        (consensus_proxy, producer1, producer2, nw1, nw2)
+    }
+
+    /// Creates a watchtower for the given node and waits until it is subscribed to gossip.
+    async fn watchtower(
+        consensus_proxy: ConsensusProxy<MockNetwork>,
+        producer: &TemporaryBlockProducer,
+        network: &Arc<MockNetwork>,
+    ) -> TestWatchtower {
+        let watchtower = Watchtower::new(
+            VolatileDatabase::new(2).unwrap(),
+            consensus_proxy,
+            Arc::clone(&producer.blockchain),
+            Arc::new(ValidatorNetworkImpl::new(Arc::clone(network))),
+        );
+        sleep(Duration::from_millis(100)).await;
+        watchtower
+    }
+
+    /// Polls the watchtower until it has reported the equivocation at the given locator.
+    async fn wait_for_report(watchtower: &mut TestWatchtower, locator: &EquivocationLocator) {
+        timeout(Duration::from_millis(400), async {
+            while !watchtower.has_reported(locator) {
+                assert!(poll!(&mut *watchtower).is_pending());
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("watchtower should have reported the equivocation before the timeout");
+    }
+
+    fn validator_address(producer: &TemporaryBlockProducer) -> Address {
+        producer
+            .blockchain
+            .read()
+            .current_validators()
+            .unwrap()
+            .get_validator_by_slot_band(0)
+            .address
+            .clone()
+    }
+
+    async fn publish_proposal(nw: &Arc<MockNetwork>, macro_header: MacroHeader) {
+        let signing_key = SchnorrKeyPair::from(
+            SchnorrPrivateKey::deserialize_from_vec(&hex::decode(SIGNING_KEY).unwrap()).unwrap(),
+        );
+
+        let proposal_message: ProposalMessage<Header<MacroHeader>> = ProposalMessage {
+            proposal: Header(macro_header, None),
+            round: 0,
+            valid_round: None,
+        };
+
+        let data = SignedProposal::hash(
+            &proposal_message.proposal.0,
+            proposal_message.round,
+            proposal_message.valid_round,
+        )
+        .serialize_to_vec();
+
+        let signed_message = SignedProposalMessage {
+            message: proposal_message,
+            signature: (signing_key.sign(&data), 0),
+        };
+
+        nw.publish::<ProposalTopic<ValidatorNetworkImpl<MockNetwork>>>(signed_message.into())
+            .await
+            .expect("Publishing on the proposal topic must succeed.");
+    }
+
+    /// Lets the validator produce two different micro blocks at the same height and pushes both
+    /// into the observed node. The watchtower must report the resulting fork proof.
+    #[test(tokio::test)]
+    async fn it_reports_forks() {
+        let (consensus_proxy, producer1, producer2, _nw1, nw2) = setup().await;
+        let mut watchtower = watchtower(consensus_proxy, &producer2, &nw2).await;
+
+        let block = producer1.next_block(vec![], false);
+        producer2.push(block).expect("Pushing blocks must succeed");
+
+        let block_number = producer2.blockchain.read().block_number() + 1;
+        let _block = producer2.next_block(vec![], false);
+        let fork = producer1.next_block(vec![0x48], false);
+        producer2.push(fork).expect("Pushing the fork must succeed");
+
+        let locator = EquivocationLocator::from(ForkLocator {
+            validator_address: validator_address(&producer2),
+            block_number,
+        });
+        wait_for_report(&mut watchtower, &locator).await;
+    }
+
+    /// Has the validator sign two different proposals for the same macro block and round. The
+    /// watchtower must keep both signed proposals as evidence.
+    #[test(tokio::test)]
+    async fn it_keeps_conflicting_proposals() {
+        let (consensus_proxy, producer1, producer2, nw1, nw2) = setup().await;
+        let mut watchtower = watchtower(consensus_proxy, &producer2, &nw2).await;
+
+        // Push blocks until before the macro block for both chains.
+        for _ in 0..Policy::blocks_per_batch() - 1 {
+            let block = producer1.next_block(vec![], false);
+            producer2.push(block).expect("Pushing blocks must succeed");
+        }
+
+        let macro_block = producer1.next_block(vec![], false);
+        let header1 = macro_block.unwrap_macro_ref().header.clone();
+        let mut header2 = header1.clone();
+        header2.timestamp += 1;
+
+        // The same proposal seen twice is not an equivocation.
+        publish_proposal(&nw1, header1.clone()).await;
+        publish_proposal(&nw1, header1.clone()).await;
+        publish_proposal(&nw1, header2.clone()).await;
+
+        let locator = EquivocationLocator::from(DoubleProposalLocator {
+            validator_address: validator_address(&producer2),
+            block_number: header1.block_number,
+            round: 0,
+        });
+        wait_for_report(&mut watchtower, &locator).await;
+
+        let evidence = watchtower
+            .get_conflicting_proposals(&locator)
+            .expect("watchtower should have kept the conflicting proposals");
+        assert_eq!(evidence.validator_address, validator_address(&producer2));
+        assert_eq!(evidence.proposal1.proposal, header1);
+        assert_eq!(evidence.proposal2.proposal, header2);
+
+        // The evidence contains the full signed proposals.
+        let proposer = producer2
+            .blockchain
+            .read()
+            .current_validators()
+            .unwrap()
+            .get_validator_by_slot_band(0)
+            .clone();
+        for proposal in [evidence.proposal1, evidence.proposal2] {
+            let data =
+                SignedProposal::hash(&proposal.proposal, proposal.round, proposal.valid_round)
+                    .serialize_to_vec();
+            assert!(proposer.signing_key.verify(&proposal.signature, &data));
+        }
+    }
+}
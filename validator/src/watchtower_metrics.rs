@@ -0,0 +1,67 @@
+use nimiq_block::EquivocationProof;
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeLabelValue},
+    metrics::{counter::Counter, family::Family},
+    registry::Registry,
+};
+
+#[derive(Default)]
+pub struct WatchtowerMetrics {
+    equivocations_reported: Family<EquivocationLabels, Counter>,
+    conflicting_proposals: Counter,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct EquivocationLabels {
+    ty: EquivocationType,
+    source: EquivocationSource,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum EquivocationType {
+    Fork,
+    DoubleProposal,
+    DoubleVote,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+enum EquivocationSource {
+    Detected,
+    Relayed,
+}
+
+impl WatchtowerMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "equivocations_reported",
+            "Count of equivocations reported by the watchtower",
+            self.equivocations_reported.clone(),
+        );
+
+        registry.register(
+            "conflicting_proposals",
+            "Count of conflicting Tendermint proposals observed by the watchtower",
+            self.conflicting_proposals.clone(),
+        );
+    }
+
+    pub(crate) fn note_equivocation(&self, proof: &EquivocationProof, detected: bool) {
+        let ty = match proof {
+            EquivocationProof::Fork(_) => EquivocationType::Fork,
+            EquivocationProof::DoubleProposal(_) => EquivocationType::DoubleProposal,
+            EquivocationProof::DoubleVote(_) => EquivocationType::DoubleVote,
+        };
+        let source = if detected {
+            EquivocationSource::Detected
+        } else {
+            EquivocationSource::Relayed
+        };
+        self.equivocations_reported
+            .get_or_create(&EquivocationLabels { ty, source })
+            .inc();
+    }
+
+    pub(crate) fn note_conflicting_proposal(&self) {
+        self.conflicting_proposals.inc();
+    }
+}
@@ -125,6 +125,7 @@ async fn it_verifies_inferior_chain_proposals() {
         0,
         NetworkId::UnitAlbatross,
         blockchain2.read().head().block_number() + 1,
+        Default::default(),
     );
 
     // Make sure the main chain proposal is acceptable.
@@ -24,12 +24,12 @@ pub use nimiq::{
 };
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_consensus::ConsensusEvent;
-use nimiq_hash::Blake2bHash;
+use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_network_interface::{
     network::{CloseReason, Network, NetworkEvent},
     Multiaddr,
 };
-use nimiq_primitives::policy::Policy;
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use tokio::sync::oneshot;
 use tsify::Tsify;
 use wasm_bindgen::{prelude::*, JsCast};
@@ -45,7 +45,13 @@ use crate::{
             PlainValidatorType,
         },
         block::{PlainBlock, PlainBlockType},
+        outbox::Outbox,
         peer_info::PlainPeerInfo,
+        storage::{self, StoredState},
+        transaction_history::{
+            PlainTransactionHistoryOptions, PlainTransactionHistoryOptionsType,
+            TransactionHistoryStream, TransactionHistoryStreamType,
+        },
     },
     client_configuration::{
         ClientConfiguration, PlainClientConfiguration, PlainClientConfigurationType,
@@ -112,6 +118,9 @@ pub struct Client {
     /// Map from transaction hash as hex string to oneshot sender.
     /// Used to await transaction events in `send_transaction`.
     transaction_oneshots: Rc<RefCell<HashMap<String, oneshot::Sender<PlainTransactionDetails>>>>,
+
+    /// The transactions sent by this client that are not confirmed or expired yet.
+    outbox: Rc<Outbox>,
 }
 
 #[wasm_bindgen]
@@ -170,6 +179,21 @@ impl Client {
             .expect("Client initialization failed");
         log::info!("Web client initialized");
 
+        // Resume from the persisted state, such that only the blocks produced in the meantime need to be synced.
+        if web_config.persistent_storage {
+            match storage::load(web_config.network_id).await {
+                Ok(Some(state)) => match state.restore(&client.blockchain()) {
+                    Ok(()) => log::info!(
+                        block_number = client.blockchain_head().block_number(),
+                        "Restored persisted state"
+                    ),
+                    Err(error) => log::warn!(%error, "Discarding invalid persisted state"),
+                },
+                Ok(None) => log::info!("No persisted state found"),
+                Err(error) => log::warn!(?error, "Failed to load persisted state"),
+            }
+        }
+
         // Start consensus.
         let consensus = client.take_consensus().unwrap();
         log::info!("Spawning consensus");
@@ -178,6 +202,12 @@ impl Client {
         let zkp_component = client.take_zkp_component().unwrap();
         spawn_local(zkp_component);
 
+        let outbox = Rc::new(Outbox::new(
+            web_config.network_id,
+            web_config.persistent_storage,
+        ));
+        outbox.restore().await;
+
         let client = Client {
             inner: client,
             network_id: from_network_id(web_config.network_id),
@@ -188,6 +218,7 @@ impl Client {
             peer_changed_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
             transaction_listeners: Rc::new(RefCell::new(HashMap::new())),
             transaction_oneshots: Rc::new(RefCell::new(HashMap::new())),
+            outbox,
         };
 
         client.setup_offline_online_event_handlers();
@@ -195,6 +226,11 @@ impl Client {
         client.setup_blockchain_events();
         client.setup_network_events();
         client.setup_transaction_events().await;
+        client.setup_outbox();
+
+        if web_config.persistent_storage {
+            client.setup_storage(web_config.network_id);
+        }
 
         Ok(client)
     }
@@ -371,6 +407,16 @@ impl Client {
         Ok(())
     }
 
+    /// Removes the synced state persisted in IndexedDB, such that the next client starts syncing from scratch.
+    ///
+    /// If persistent storage is enabled for this client, its state is persisted again on the next macro block.
+    #[wasm_bindgen(js_name = clearStorage)]
+    pub async fn clear_storage(&self) -> Result<(), JsError> {
+        storage::clear()
+            .await
+            .map_err(|error| JsError::new(&format!("Failed to clear storage: {:?}", error)))
+    }
+
     /// Returns the block hash of the current blockchain head.
     #[wasm_bindgen(js_name = getHeadHash)]
     pub async fn get_head_hash(&self) -> String {
@@ -501,7 +547,12 @@ impl Client {
 
     /// Sends a transaction to the network and returns {@link PlainTransactionDetails}.
     ///
-    /// Throws in case of network errors.
+    /// The transaction is added to the outbox, which tracks it until it is confirmed by a macro block
+    /// or expires. Status changes are reported to the transaction listeners of its sender and recipient.
+    /// Transactions that are not included yet are rebroadcast whenever consensus is re-established.
+    ///
+    /// Throws in case of network errors. The transaction stays in the outbox in that case and is
+    /// rebroadcast once consensus is re-established.
     #[wasm_bindgen(js_name = sendTransaction)]
     pub async fn send_transaction(
         &self,
@@ -577,8 +628,18 @@ impl Client {
             .borrow_mut()
             .insert(hash.clone(), sender);
 
+        // Track the transaction in the outbox before sending it, such that it is not lost if sending fails
+        let native_hash: Blake2bHash = tx.native_ref().hash();
+        self.outbox.add(tx.native());
+
         // Actually send the transaction
         consensus.send_transaction(tx.native()).await?;
+        if let Some(entry) = self
+            .outbox
+            .update(&native_hash, TransactionState::Pending, None)
+        {
+            Client::fire_transaction_event(&self.transaction_listeners, &entry.to_details());
+        }
 
         let timeout = gloo_timers::future::TimeoutFuture::new(10_000);
 
@@ -742,6 +803,37 @@ impl Client {
         Ok(serde_wasm_bindgen::to_value(&plain_tx_details)?.into())
     }
 
+    /// Streams the transaction history of an address from newest to oldest.
+    ///
+    /// Returns an async iterator that pages backwards through the transaction receipts of the address
+    /// and yields each page as soon as its transactions have been verified. Use it with
+    /// `for await (const transactions of client.transactionHistoryStream(address)) { ... }`.
+    ///
+    /// The optional `since` and `until` block heights restrict the streamed transactions, `pageSize`
+    /// sets the number of transactions requested per page (default 100, maximum 500).
+    #[wasm_bindgen(js_name = transactionHistoryStream)]
+    pub fn transaction_history_stream(
+        &self,
+        address: &AddressAnyType,
+        options: Option<PlainTransactionHistoryOptionsType>,
+    ) -> Result<TransactionHistoryStreamType, JsError> {
+        let options = match options {
+            Some(options) => serde_wasm_bindgen::from_value(options.into())?,
+            None => PlainTransactionHistoryOptions {
+                since: None,
+                until: None,
+                page_size: None,
+            },
+        };
+
+        TransactionHistoryStream::new(
+            self.inner.consensus_proxy(),
+            Address::from_any(address)?.take_native(),
+            options,
+        )?
+        .into_async_iterator()
+    }
+
     fn setup_offline_online_event_handlers(&self) {
         let network = self.inner.network();
 
@@ -786,6 +878,32 @@ impl Client {
         handler.forget();
     }
 
+    /// Persists the synced state whenever a macro block is finalized.
+    fn setup_storage(&self, network_id: NetworkId) {
+        let blockchain = self.inner.blockchain();
+        let zkp_component = self.inner.zkp_component();
+
+        let mut blockchain_events = blockchain.read().notifier_as_stream();
+
+        spawn_local(async move {
+            while let Some(event) = blockchain_events.next().await {
+                if !matches!(
+                    event,
+                    BlockchainEvent::EpochFinalized(_) | BlockchainEvent::Finalized(_)
+                ) {
+                    continue;
+                }
+
+                let Some(state) = StoredState::collect(&blockchain, &zkp_component) else {
+                    continue;
+                };
+                if let Err(error) = storage::store(network_id, &state).await {
+                    log::warn!(?error, "Failed to persist state");
+                }
+            }
+        });
+    }
+
     fn setup_consensus_events(&self) {
         let consensus = self.inner.consensus_proxy();
         let network = self.inner.network();
@@ -999,6 +1117,7 @@ impl Client {
 
         let transaction_listeners = Rc::clone(&self.transaction_listeners);
         let transaction_oneshots = Rc::clone(&self.transaction_oneshots);
+        let outbox = Rc::clone(&self.outbox);
 
         spawn_local(async move {
             let mut address_notifications = consensus.subscribe_address_notifications().await;
@@ -1036,10 +1155,9 @@ impl Client {
                         log::error!("Failed to prove transactions from receipts: {}", e);
                     })
                 {
-                    let this = JsValue::null();
-
                     for hist_tx in hist_txs {
                         let block_number = hist_tx.block_number;
+                        let tx_hash: Blake2bHash = hist_tx.tx_hash().into();
                         let details = PlainTransactionDetails::try_from_historic_transaction(
                             hist_tx,
                             block_number,
@@ -1053,38 +1171,136 @@ impl Client {
                             let _ = sender.send(details.clone());
                         }
 
-                        fn from_user(addr: &str) -> nimiq_keys::Address {
-                            nimiq_keys::Address::from_user_friendly_address(addr).unwrap()
+                        // The listeners are notified below, so the outbox update does not need to be reported.
+                        outbox.update(&tx_hash, details.state.clone(), details.block_height);
+
+                        Client::fire_transaction_event(&transaction_listeners, &details);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Keeps the transactions in the outbox up to date: transactions that are not included yet are
+    /// rebroadcast when consensus is re-established, included transactions are marked as confirmed
+    /// once they are finalized by a macro block, and transactions whose validity window has passed
+    /// are marked as expired.
+    fn setup_outbox(&self) {
+        let consensus = self.inner.consensus_proxy();
+        let transaction_listeners = Rc::clone(&self.transaction_listeners);
+        let outbox = Rc::clone(&self.outbox);
+
+        let mut consensus_events = consensus.subscribe_events();
+
+        spawn_local(async move {
+            while let Some(event) = consensus_events.next().await {
+                if !matches!(event, Ok(ConsensusEvent::Established)) {
+                    continue;
+                }
+
+                let current_block_number = consensus.blockchain.read().head().block_number();
+                for entry in outbox.unincluded() {
+                    let hash: Blake2bHash = entry.transaction.hash();
+
+                    // The transaction might have been included while we were offline.
+                    if let Ok(hist_txs) = consensus
+                        .prove_transactions_from_receipts(vec![(hash.clone(), None)], 1)
+                        .await
+                    {
+                        if let Some(details) = hist_txs.into_iter().next().and_then(|hist_tx| {
+                            PlainTransactionDetails::try_from_historic_transaction(
+                                hist_tx,
+                                current_block_number,
+                            )
+                        }) {
+                            if outbox
+                                .update(&hash, details.state.clone(), details.block_height)
+                                .is_some()
+                            {
+                                Client::fire_transaction_event(&transaction_listeners, &details);
+                            }
+                            continue;
                         }
+                    }
 
-                        let sender = from_user(&details.transaction.sender);
-                        let recipient = from_user(&details.transaction.recipient);
-                        let staker_address = if let PlainTransactionRecipientData::AddStake(data) =
-                            &details.transaction.data
-                        {
-                            Some(from_user(&data.staker))
-                        } else {
-                            None
-                        };
-
-                        if let Ok(js_value) = serde_wasm_bindgen::to_value(&details) {
-                            for (listener, addresses) in transaction_listeners.borrow().values() {
-                                if addresses.contains(&sender)
-                                    || addresses.contains(&recipient)
-                                    || if let Some(ref address) = staker_address {
-                                        addresses.contains(address)
-                                    } else {
-                                        false
-                                    }
-                                {
-                                    let _ = listener.call1(&this, &js_value);
-                                }
+                    match consensus.send_transaction(entry.transaction.clone()).await {
+                        Ok(()) => {
+                            log::debug!(%hash, "Rebroadcast transaction from outbox");
+                            if let Some(entry) =
+                                outbox.update(&hash, TransactionState::Pending, None)
+                            {
+                                Client::fire_transaction_event(
+                                    &transaction_listeners,
+                                    &entry.to_details(),
+                                );
                             }
                         }
+                        Err(error) => {
+                            log::warn!(%hash, %error, "Failed to rebroadcast transaction from outbox")
+                        }
                     }
                 }
             }
         });
+
+        let blockchain = self.inner.blockchain();
+        let transaction_listeners = Rc::clone(&self.transaction_listeners);
+        let outbox = Rc::clone(&self.outbox);
+
+        let mut blockchain_events = blockchain.read().notifier_as_stream();
+
+        spawn_local(async move {
+            while blockchain_events.next().await.is_some() {
+                let (block_number, macro_block_number) = {
+                    let blockchain = blockchain.read();
+                    (
+                        blockchain.block_number(),
+                        blockchain.macro_head().block_number(),
+                    )
+                };
+
+                for entry in outbox.update_at(block_number, macro_block_number) {
+                    Client::fire_transaction_event(&transaction_listeners, &entry.to_details());
+                }
+            }
+        });
+    }
+
+    /// Calls the transaction listeners subscribed to the sender, recipient or staker of the transaction.
+    fn fire_transaction_event(
+        listeners: &Rc<RefCell<HashMap<usize, (Function, HashSet<nimiq_keys::Address>)>>>,
+        details: &PlainTransactionDetails,
+    ) {
+        fn from_user(addr: &str) -> nimiq_keys::Address {
+            nimiq_keys::Address::from_user_friendly_address(addr).unwrap()
+        }
+
+        let sender = from_user(&details.transaction.sender);
+        let recipient = from_user(&details.transaction.recipient);
+        let staker_address =
+            if let PlainTransactionRecipientData::AddStake(data) = &details.transaction.data {
+                Some(from_user(&data.staker))
+            } else {
+                None
+            };
+
+        let Ok(js_value) = serde_wasm_bindgen::to_value(details) else {
+            return;
+        };
+
+        let this = JsValue::null();
+        for (listener, addresses) in listeners.borrow().values() {
+            if addresses.contains(&sender)
+                || addresses.contains(&recipient)
+                || if let Some(ref address) = staker_address {
+                    addresses.contains(address)
+                } else {
+                    false
+                }
+            {
+                let _ = listener.call1(&this, &js_value);
+            }
+        }
     }
 
     fn next_listener_id(&self) -> usize {
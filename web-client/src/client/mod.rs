@@ -1,4 +1,7 @@
 pub mod account;
 pub mod block;
 pub mod lib;
+pub mod outbox;
 pub mod peer_info;
+pub mod storage;
+pub mod transaction_history;
@@ -0,0 +1,190 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::{
+    client::storage,
+    transaction::{PlainTransactionDetails, Transaction, TransactionState},
+};
+
+/// A transaction sent by this client, together with its last known state.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub transaction: nimiq_transaction::Transaction,
+    pub state: TransactionState,
+    /// The height of the block that included the transaction, if it has been included.
+    pub block_height: Option<u32>,
+}
+
+impl OutboxEntry {
+    pub fn to_details(&self) -> PlainTransactionDetails {
+        PlainTransactionDetails::new(
+            &Transaction::from(self.transaction.clone()),
+            self.state.clone(),
+            None,
+            self.block_height,
+            None,
+            None,
+        )
+    }
+
+    /// Returns whether the transaction can no longer be included at the given block height.
+    fn is_expired_at(&self, block_number: u32) -> bool {
+        block_number
+            >= self.transaction.validity_start_height + Policy::transaction_validity_window_blocks()
+    }
+}
+
+/// The outbox tracks the transactions sent by this client until they are confirmed by a macro block
+/// or expire. Transactions that are not included yet are rebroadcast whenever consensus is
+/// re-established. If persistent storage is enabled, the outbox survives reloads.
+pub struct Outbox {
+    network_id: NetworkId,
+    persistent: bool,
+    entries: RefCell<HashMap<Blake2bHash, OutboxEntry>>,
+}
+
+impl Outbox {
+    pub fn new(network_id: NetworkId, persistent: bool) -> Self {
+        Self {
+            network_id,
+            persistent,
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Restores the persisted transactions of this client's network.
+    pub async fn restore(&self) {
+        if !self.persistent {
+            return;
+        }
+
+        match storage::load_outbox().await {
+            Ok(entries) => {
+                let mut own_entries = self.entries.borrow_mut();
+                for entry in entries {
+                    if entry.transaction.network_id == self.network_id {
+                        own_entries.insert(entry.transaction.hash(), entry);
+                    }
+                }
+                log::info!(num_transactions = own_entries.len(), "Restored outbox");
+            }
+            Err(error) => log::warn!(?error, "Failed to load outbox"),
+        }
+    }
+
+    /// Adds a transaction that is about to be sent to the outbox.
+    pub fn add(&self, transaction: nimiq_transaction::Transaction) {
+        let entry = OutboxEntry {
+            transaction,
+            state: TransactionState::New,
+            block_height: None,
+        };
+        self.persist(&entry);
+        self.entries
+            .borrow_mut()
+            .insert(entry.transaction.hash(), entry);
+    }
+
+    /// Returns the transactions that have not been included yet and need to be (re)broadcast.
+    pub fn unincluded(&self) -> Vec<OutboxEntry> {
+        self.entries
+            .borrow()
+            .values()
+            .filter(|entry| {
+                matches!(
+                    entry.state,
+                    TransactionState::New | TransactionState::Pending
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the state of a transaction in the outbox. Transactions that reach a final state are
+    /// removed from the outbox.
+    /// Returns the updated entry if the transaction is part of the outbox and its state changed.
+    pub fn update(
+        &self,
+        hash: &Blake2bHash,
+        state: TransactionState,
+        block_height: Option<u32>,
+    ) -> Option<OutboxEntry> {
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries.get_mut(hash)?;
+        if entry.state == state {
+            return None;
+        }
+
+        entry.state = state;
+        entry.block_height = block_height.or(entry.block_height);
+        let entry = entry.clone();
+
+        match entry.state {
+            TransactionState::Confirmed
+            | TransactionState::Expired
+            | TransactionState::Invalidated => {
+                entries.remove(hash);
+                self.unpersist(hash);
+            }
+            _ => self.persist(&entry),
+        }
+
+        Some(entry)
+    }
+
+    /// Marks all transactions that are confirmed or expired at the given block heights as such.
+    /// Returns the updated entries.
+    pub fn update_at(&self, block_number: u32, macro_block_number: u32) -> Vec<OutboxEntry> {
+        let updates: Vec<_> = self
+            .entries
+            .borrow()
+            .iter()
+            .filter_map(|(hash, entry)| match entry.state {
+                TransactionState::Included
+                    if entry.block_height.unwrap_or(u32::MAX) <= macro_block_number =>
+                {
+                    Some((hash.clone(), TransactionState::Confirmed))
+                }
+                TransactionState::New | TransactionState::Pending
+                    if entry.is_expired_at(block_number) =>
+                {
+                    Some((hash.clone(), TransactionState::Expired))
+                }
+                _ => None,
+            })
+            .collect();
+
+        updates
+            .into_iter()
+            .filter_map(|(hash, state)| self.update(&hash, state, None))
+            .collect()
+    }
+
+    fn persist(&self, entry: &OutboxEntry) {
+        if !self.persistent {
+            return;
+        }
+        let entry = entry.clone();
+        spawn_local(async move {
+            if let Err(error) = storage::store_outbox_entry(&entry).await {
+                log::warn!(?error, "Failed to persist outbox entry");
+            }
+        });
+    }
+
+    fn unpersist(&self, hash: &Blake2bHash) {
+        if !self.persistent {
+            return;
+        }
+        let hash = hash.clone();
+        spawn_local(async move {
+            if let Err(error) = storage::remove_outbox_entry(&hash).await {
+                log::warn!(?error, "Failed to remove outbox entry");
+            }
+        });
+    }
+}
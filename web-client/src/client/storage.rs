@@ -0,0 +1,249 @@
+use js_sys::{global, Array, Promise, Reflect, Uint8Array};
+use nimiq_block::{Block, MacroBlock};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushError};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_light_blockchain::LightBlockchain;
+use nimiq_network_interface::network::Network;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_zkp_component::{types::ZKProof, zkp_component::ZKPComponentProxy};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbFactory, IdbRequest, IdbTransactionMode};
+
+use crate::client::outbox::OutboxEntry;
+
+/// Name of the IndexedDB database used by the web client.
+const DB_NAME: &str = "nimiq-web-client";
+/// Version of the IndexedDB database. Must be increased whenever the object stores change.
+const DB_VERSION: u32 = 2;
+/// Name of the object store holding the synced state, keyed by network ID.
+const STATE_STORE: &str = "state";
+/// Name of the object store holding the transaction outbox, keyed by transaction hash.
+const OUTBOX_STORE: &str = "outbox";
+
+/// The synced state of the light client that is persisted across reloads.
+///
+/// The state is never trusted on load: the election block and its ZKP are verified again when
+/// restoring the light blockchain, and the macro head must be a valid successor of the election block.
+#[derive(Serialize, Deserialize)]
+pub struct StoredState {
+    /// The latest verified zero-knowledge proof.
+    pub zkp: ZKProof,
+    /// The election block proven by the ZKP, including its body.
+    pub election_block: MacroBlock,
+    /// The most recent macro block after the election block, including its body.
+    pub macro_head: Option<MacroBlock>,
+}
+
+impl StoredState {
+    /// Collects the current state of the light blockchain and the ZKP component.
+    /// Returns None if there is nothing worth persisting, i.e. the client has not synced a ZKP yet.
+    pub fn collect<N: Network>(
+        blockchain: &BlockchainProxy,
+        zkp_component: &ZKPComponentProxy<N>,
+    ) -> Option<Self> {
+        let zkp_state = zkp_component.get_zkp_state();
+        if zkp_state.latest_proof.is_none() || zkp_state.latest_block.body.is_none() {
+            return None;
+        }
+
+        let macro_head = match blockchain {
+            BlockchainProxy::Light(ref light_blockchain) => light_blockchain.read().macro_head(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("The web client always uses a light blockchain"),
+        };
+        let macro_head = (macro_head.block_number() > zkp_state.latest_block.block_number()
+            && macro_head.body.is_some())
+        .then_some(macro_head);
+
+        Some(Self {
+            election_block: zkp_state.latest_block.clone(),
+            zkp: zkp_state.into(),
+            macro_head,
+        })
+    }
+
+    /// Restores this state into the given light blockchain. The ZKP is fully verified before the
+    /// election block is adopted. Returns an error if the stored state is invalid.
+    pub fn restore(self, blockchain: &BlockchainProxy) -> Result<(), PushError> {
+        let light_blockchain = match blockchain {
+            BlockchainProxy::Light(ref light_blockchain) => light_blockchain,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("The web client always uses a light blockchain"),
+        };
+
+        // Only an election block can be adopted with a ZKP.
+        if !self.election_block.is_election() {
+            return Err(PushError::InvalidZKP);
+        }
+
+        // The proof must belong to the stored election block.
+        if self.zkp.block_number != self.election_block.block_number() {
+            return Err(PushError::InvalidZKP);
+        }
+        let proof = self.zkp.proof.ok_or(PushError::InvalidZKP)?;
+
+        LightBlockchain::push_zkp(
+            light_blockchain.upgradable_read(),
+            Block::Macro(self.election_block),
+            proof,
+            false,
+        )?;
+
+        if let Some(macro_head) = self.macro_head {
+            // The macro head is only an optimization, so failing to push it is not fatal.
+            if let Err(error) = LightBlockchain::push_macro(
+                light_blockchain.upgradable_read(),
+                Block::Macro(macro_head),
+            ) {
+                log::warn!(%error, "Failed to restore the stored macro head");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads the persisted state for the given network, if any.
+pub async fn load(network_id: NetworkId) -> Result<Option<StoredState>, JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str(STATE_STORE)?
+        .object_store(STATE_STORE)?;
+
+    let value = wait_for(&store.get(&state_key(network_id))?).await?;
+    if value.is_undefined() {
+        return Ok(None);
+    }
+
+    let bytes = Uint8Array::new(&value).to_vec();
+    match StoredState::deserialize_from_vec(&bytes) {
+        Ok(state) => Ok(Some(state)),
+        Err(error) => {
+            log::warn!(%error, "Discarding unreadable stored state");
+            Ok(None)
+        }
+    }
+}
+
+/// Persists the given state for the given network, replacing any previously stored state.
+pub async fn store(network_id: NetworkId, state: &StoredState) -> Result<(), JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(STATE_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(STATE_STORE)?;
+
+    let value = Uint8Array::from(state.serialize_to_vec().as_slice());
+    wait_for(&store.put_with_key(&value, &state_key(network_id))?).await?;
+    Ok(())
+}
+
+/// Removes the persisted state of all networks.
+pub async fn clear() -> Result<(), JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(STATE_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(STATE_STORE)?;
+
+    wait_for(&store.clear()?).await?;
+    Ok(())
+}
+
+/// Loads all transactions of the outbox.
+pub async fn load_outbox() -> Result<Vec<OutboxEntry>, JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str(OUTBOX_STORE)?
+        .object_store(OUTBOX_STORE)?;
+
+    let values: Array = wait_for(&store.get_all()?).await?.unchecked_into();
+
+    let mut entries = vec![];
+    for value in values.iter() {
+        let bytes = Uint8Array::new(&value).to_vec();
+        match OutboxEntry::deserialize_from_vec(&bytes) {
+            Ok(entry) => entries.push(entry),
+            Err(error) => log::warn!(%error, "Discarding unreadable outbox entry"),
+        }
+    }
+    Ok(entries)
+}
+
+/// Persists the given outbox entry, replacing any previously stored entry of the same transaction.
+pub async fn store_outbox_entry(entry: &OutboxEntry) -> Result<(), JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(OUTBOX_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(OUTBOX_STORE)?;
+
+    let value = Uint8Array::from(entry.serialize_to_vec().as_slice());
+    let key: JsValue = entry.transaction.hash::<Blake2bHash>().to_hex().into();
+    wait_for(&store.put_with_key(&value, &key)?).await?;
+    Ok(())
+}
+
+/// Removes the outbox entry of the transaction with the given hash.
+pub async fn remove_outbox_entry(hash: &Blake2bHash) -> Result<(), JsValue> {
+    let db = open_database().await?;
+    let store = db
+        .transaction_with_str_and_mode(OUTBOX_STORE, IdbTransactionMode::Readwrite)?
+        .object_store(OUTBOX_STORE)?;
+
+    wait_for(&store.delete(&hash.to_hex().into())?).await?;
+    Ok(())
+}
+
+fn state_key(network_id: NetworkId) -> JsValue {
+    network_id.to_string().into()
+}
+
+/// Opens the database, creating the object stores on first use.
+async fn open_database() -> Result<IdbDatabase, JsValue> {
+    // `indexedDB` is available both in windows and in workers.
+    let factory: IdbFactory = Reflect::get(&global(), &"indexedDB".into())?.dyn_into()?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    let upgrade_request = request.clone();
+    let on_upgrade_needed = Closure::once_into_js(move || {
+        if let Ok(db) = upgrade_request.result() {
+            // Create the object stores that are missing in the previous version of the database.
+            let db = db.unchecked_into::<IdbDatabase>();
+            for store in [STATE_STORE, OUTBOX_STORE] {
+                if !db.object_store_names().contains(store) {
+                    let _ = db.create_object_store(store);
+                }
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.unchecked_ref()));
+
+    Ok(wait_for(&request).await?.unchecked_into())
+}
+
+/// Waits for the given request to complete and returns its result.
+async fn wait_for(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+
+        let error_request = request.clone();
+        let on_error = Closure::once_into_js(move || {
+            let error = error_request
+                .error()
+                .ok()
+                .flatten()
+                .map_or(JsValue::UNDEFINED, JsValue::from);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+
+    JsFuture::from(promise).await
+}
@@ -0,0 +1,186 @@
+use std::{collections::HashSet, rc::Rc};
+
+use js_sys::{Function, Object, Promise, Reflect, Symbol};
+use nimiq::client::ConsensusProxy;
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_consensus::messages::TransactionReceiptsCursor;
+use nimiq_hash::Blake2bHash;
+use tokio::sync::Mutex;
+use tsify::Tsify;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::{client::lib::MAX_TRANSACTIONS_BY_ADDRESS, transaction::PlainTransactionDetails};
+
+/// The default number of transactions requested per page.
+const DEFAULT_PAGE_SIZE: u16 = 100;
+
+/// Options for streaming the transaction history of an address.
+#[derive(serde::Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainTransactionHistoryOptions {
+    /// Only include transactions at or after this block height.
+    #[tsify(optional)]
+    pub since: Option<u32>,
+    /// Only include transactions at or before this block height.
+    #[tsify(optional)]
+    pub until: Option<u32>,
+    /// The number of transactions requested from the network per page.
+    #[tsify(optional)]
+    pub page_size: Option<u16>,
+}
+
+/// Pages backwards through the transaction history of an address. Each page of receipts is
+/// verified with transaction inclusion proofs before it is yielded.
+pub struct TransactionHistoryStream {
+    consensus: ConsensusProxy,
+    address: nimiq_keys::Address,
+    since: u32,
+    until: u32,
+    page_size: u16,
+    /// The position of the oldest transaction of the last page, the next page starts after it.
+    start_at: Option<TransactionReceiptsCursor>,
+    /// Hashes of the transactions that have already been yielded.
+    yielded: HashSet<Blake2bHash>,
+    done: bool,
+}
+
+impl TransactionHistoryStream {
+    pub fn new(
+        consensus: ConsensusProxy,
+        address: nimiq_keys::Address,
+        options: PlainTransactionHistoryOptions,
+    ) -> Result<Self, JsError> {
+        let page_size = options.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+        if page_size == 0 || page_size > MAX_TRANSACTIONS_BY_ADDRESS {
+            return Err(JsError::new(&format!(
+                "The page size must be between 1 and {}",
+                MAX_TRANSACTIONS_BY_ADDRESS
+            )));
+        }
+
+        Ok(Self {
+            consensus,
+            address,
+            since: options.since.unwrap_or(0),
+            until: options.until.unwrap_or(u32::MAX),
+            page_size,
+            start_at: None,
+            yielded: HashSet::new(),
+            done: false,
+        })
+    }
+
+    /// Fetches and verifies the next page of transactions, newest first.
+    /// Returns None if the history has been fully streamed.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<PlainTransactionDetails>>, JsError> {
+        while !self.done {
+            let page = self
+                .consensus
+                .request_transaction_receipts_page(
+                    self.address.clone(),
+                    1,
+                    Some(self.page_size),
+                    self.start_at.clone(),
+                    Some(self.until),
+                )
+                .await?
+                .map_err(|error| JsError::new(&error.to_string()))?;
+
+            // The receipts are ordered from newest to oldest, so we are done once we are past the
+            // oldest requested block or if there is no next page.
+            let past_since = page
+                .receipts
+                .iter()
+                .any(|(_, block_number)| *block_number < self.since);
+            self.done = page.next.is_none() || past_since;
+            self.start_at = page.next;
+
+            let receipts: Vec<_> = page
+                .receipts
+                .into_iter()
+                .filter(|(hash, block_number)| {
+                    *block_number >= self.since && !self.yielded.contains(hash)
+                })
+                .map(|(hash, block_number)| (hash, Some(block_number)))
+                .collect();
+            if receipts.is_empty() {
+                continue;
+            }
+
+            let transactions = self
+                .consensus
+                .prove_transactions_from_receipts(receipts, 1)
+                .await?;
+
+            let current_height = self.consensus.blockchain.read().head().block_number();
+            let mut page = vec![];
+            for hist_tx in transactions {
+                self.yielded.insert(hist_tx.tx_hash().into());
+                page.push(
+                    PlainTransactionDetails::try_from_historic_transaction(hist_tx, current_height)
+                        .expect("no non-reward inherent"),
+                );
+            }
+            page.sort_by(|a, b| b.block_height.cmp(&a.block_height));
+
+            return Ok(Some(page));
+        }
+
+        Ok(None)
+    }
+
+    /// Wraps the stream into a Javascript object implementing the async iterator protocol.
+    pub fn into_async_iterator(self) -> Result<TransactionHistoryStreamType, JsError> {
+        let stream = Rc::new(Mutex::new(self));
+
+        let next = Closure::<dyn FnMut() -> Promise>::new(move || {
+            let stream = Rc::clone(&stream);
+            future_to_promise(async move {
+                let page = stream
+                    .lock()
+                    .await
+                    .next_page()
+                    .await
+                    .map_err(JsValue::from)?;
+
+                let result = Object::new();
+                match page {
+                    Some(page) => {
+                        Reflect::set(&result, &"done".into(), &false.into())?;
+                        Reflect::set(
+                            &result,
+                            &"value".into(),
+                            &serde_wasm_bindgen::to_value(&page)?,
+                        )?;
+                    }
+                    None => {
+                        Reflect::set(&result, &"done".into(), &true.into())?;
+                    }
+                }
+                Ok(result.into())
+            })
+        });
+
+        let iterator = Object::new();
+        Reflect::set(&iterator, &"next".into(), &next.into_js_value())
+            .map_err(|_| JsError::new("Failed to create the async iterator"))?;
+        Reflect::set(
+            &iterator,
+            &Symbol::async_iterator(),
+            &Function::new_no_args("return this"),
+        )
+        .map_err(|_| JsError::new("Failed to create the async iterator"))?;
+
+        Ok(iterator.unchecked_into())
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "PlainTransactionHistoryOptions")]
+    pub type PlainTransactionHistoryOptionsType;
+
+    #[wasm_bindgen(typescript_type = "AsyncIterableIterator<PlainTransactionDetails[]>")]
+    pub type TransactionHistoryStreamType;
+}
@@ -2,12 +2,18 @@ use std::str::FromStr;
 
 use nimiq_hash::Blake2bHash;
 use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::Serialize;
+use nimiq_transaction::account::htlc_contract::{
+    AnyHash, AnyHash32, AnyHash64, OutgoingHTLCTransactionProof, PreImage,
+};
 use nimiq_transaction_builder::{Recipient, Sender};
 use wasm_bindgen::prelude::*;
 
 use crate::{
     address::Address,
-    primitives::{bls_key_pair::BLSKeyPair, public_key::PublicKey},
+    primitives::{
+        bls_key_pair::BLSKeyPair, public_key::PublicKey, signature_proof::SignatureProof,
+    },
     transaction::Transaction,
     utils::to_network_id,
 };
@@ -81,19 +87,212 @@ impl TransactionBuilder {
         Ok(Transaction::from(tx))
     }
 
-    // pub fn new_create_vesting()
+    /// Creates a new vesting contract owned by `owner` and transfers `value` amount of luna (NIM's smallest unit)
+    /// from the sender into it. The funds are released in `num_steps` equal payouts, the first one at
+    /// `start_time + time_step` and then every `time_step` milliseconds.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the numbers given for value and fee do not fit within a u64, `num_steps` is zero
+    /// or the networkId is unknown.
+    #[wasm_bindgen(js_name = newCreateVesting)]
+    pub fn new_create_vesting(
+        sender: &Address,
+        owner: &Address,
+        start_time: u64,
+        time_step: u64,
+        num_steps: u32,
+        value: u64,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        if num_steps == 0 {
+            return Err(JsError::new("The number of steps must be positive"));
+        }
 
-    // pub fn new_redeem_vesting()
+        let value = Coin::try_from(value)?;
 
-    // pub fn new_create_htlc()
+        let mut recipient = Recipient::new_vesting_builder(owner.native_ref().clone());
+        recipient.with_steps(value, start_time, time_step, num_steps);
 
-    // pub fn new_redeem_htlc()
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate()?)
+            .with_value(value)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
 
-    // pub fn new_refund_htlc()
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
 
-    // pub fn new_redeem_htlc_early()
+    /// Redeems `value` amount of luna (NIM's smallest unit) from the vesting contract and transfers them
+    /// to the recipient.
+    ///
+    /// The returned transaction is not yet signed. You can sign it with the owner's key pair, e.g. with
+    /// `tx.sign(keyPair)`.
+    ///
+    /// Throws when the numbers given for value and fee do not fit within a u64 or the networkId is unknown.
+    #[wasm_bindgen(js_name = newRedeemVesting)]
+    pub fn new_redeem_vesting(
+        contract_address: &Address,
+        recipient: &Address,
+        value: u64,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_vesting(contract_address.native_ref().clone()))
+            .with_recipient(Recipient::new_basic(recipient.native_ref().clone()))
+            .with_value(Coin::try_from(value)?)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
 
-    // pub fn sign_htlc_early()
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
+    /// Creates a new HTLC (hashed time-locked contract) and transfers `value` amount of luna (NIM's smallest unit)
+    /// from the sender into it. The `htlc_recipient` can redeem the funds by presenting the pre-image of
+    /// `hash_root` before `timeout`, afterwards the `htlc_sender` can reclaim them.
+    ///
+    /// The hash root is given as a hex string, its `hash_algorithm` must be one of
+    /// `'blake2b' | 'sha256' | 'sha512'`.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the numbers given for value and fee do not fit within a u64, the hash root is invalid
+    /// or the networkId is unknown.
+    #[wasm_bindgen(js_name = newCreateHtlc)]
+    pub fn new_create_htlc(
+        sender: &Address,
+        htlc_sender: &Address,
+        htlc_recipient: &Address,
+        hash_algorithm: &str,
+        hash_root: &str,
+        hash_count: u8,
+        timeout: u64,
+        value: u64,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut recipient = Recipient::new_htlc_builder();
+        recipient
+            .with_sender(htlc_sender.native_ref().clone())
+            .with_recipient(htlc_recipient.native_ref().clone())
+            .with_hash(parse_hash_root(hash_algorithm, hash_root)?, hash_count)
+            .with_timeout(timeout);
+
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate()?)
+            .with_value(Coin::try_from(value)?)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
+    /// Redeems `value` amount of luna (NIM's smallest unit) from the HTLC and transfers them to the recipient.
+    /// This transaction is used for all ways of resolving a HTLC: regular transfer, timeout resolve and
+    /// early resolve.
+    ///
+    /// The returned transaction is not yet signed. Sign its `serializeContent()` with the required key(s)
+    /// and set its proof with `htlcRegularTransferProof`, `htlcTimeoutResolveProof` or
+    /// `htlcEarlyResolveProof`, e.g. `tx.proof = TransactionBuilder.htlcTimeoutResolveProof(signatureProof)`.
+    ///
+    /// Throws when the numbers given for value and fee do not fit within a u64 or the networkId is unknown.
+    #[wasm_bindgen(js_name = newRedeemHtlc)]
+    pub fn new_redeem_htlc(
+        contract_address: &Address,
+        recipient: &Address,
+        value: u64,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_htlc(contract_address.native_ref().clone()))
+            .with_recipient(Recipient::new_basic(recipient.native_ref().clone()))
+            .with_value(Coin::try_from(value)?)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
+    /// Builds the proof for redeeming a HTLC with the regular transfer method, i.e. by the `htlc_recipient`
+    /// presenting the `pre_image` of the hash root before the timeout. `hash_count` is the number of times
+    /// the pre-image needs to be hashed to yield the hash root.
+    ///
+    /// The signature proof must be made by the `htlc_recipient` over the transaction's `serializeContent()`.
+    /// The returned bytes can be assigned to the transaction's `proof` field.
+    ///
+    /// Throws when the hash root or pre-image are invalid.
+    #[wasm_bindgen(js_name = htlcRegularTransferProof)]
+    pub fn htlc_regular_transfer_proof(
+        signature_proof: &SignatureProof,
+        hash_algorithm: &str,
+        hash_root: &str,
+        hash_count: u8,
+        pre_image: &str,
+    ) -> Result<Vec<u8>, JsError> {
+        let proof = OutgoingHTLCTransactionProof::RegularTransfer {
+            hash_depth: hash_count,
+            hash_root: parse_hash_root(hash_algorithm, hash_root)?,
+            pre_image: PreImage::from_str(pre_image)?,
+            signature_proof: signature_proof.native_ref().clone(),
+        };
+        Ok(proof.serialize_to_vec())
+    }
+
+    /// Builds the proof for redeeming a HTLC with the timeout resolve method, i.e. by the `htlc_sender`
+    /// after the timeout has passed.
+    ///
+    /// The signature proof must be made by the `htlc_sender` over the transaction's `serializeContent()`.
+    /// The returned bytes can be assigned to the transaction's `proof` field.
+    #[wasm_bindgen(js_name = htlcTimeoutResolveProof)]
+    pub fn htlc_timeout_resolve_proof(signature_proof: &SignatureProof) -> Vec<u8> {
+        let proof = OutgoingHTLCTransactionProof::TimeoutResolve {
+            signature_proof_sender: signature_proof.native_ref().clone(),
+        };
+        proof.serialize_to_vec()
+    }
+
+    /// Builds the proof for redeeming a HTLC with the early resolve method, i.e. with the consent of both
+    /// the `htlc_sender` and the `htlc_recipient` at any time.
+    ///
+    /// Both signature proofs must be made over the transaction's `serializeContent()`. They can be
+    /// single-sig or multi-sig proofs, depending on the type of the `htlc_sender` and `htlc_recipient`.
+    /// The returned bytes can be assigned to the transaction's `proof` field.
+    #[wasm_bindgen(js_name = htlcEarlyResolveProof)]
+    pub fn htlc_early_resolve_proof(
+        htlc_sender_signature_proof: &SignatureProof,
+        htlc_recipient_signature_proof: &SignatureProof,
+    ) -> Vec<u8> {
+        let proof = OutgoingHTLCTransactionProof::EarlyResolve {
+            signature_proof_recipient: htlc_recipient_signature_proof.native_ref().clone(),
+            signature_proof_sender: htlc_sender_signature_proof.native_ref().clone(),
+        };
+        proof.serialize_to_vec()
+    }
 
     /// Creates a new staker in the staking contract and transfers `value` amount of luna (NIM's smallest unit)
     /// from the sender account to this new staker.
@@ -466,3 +665,16 @@ impl TransactionBuilder {
         Ok(Transaction::from(tx))
     }
 }
+
+/// Parses a hex-encoded HTLC hash root for the given hash algorithm.
+fn parse_hash_root(hash_algorithm: &str, hash_root: &str) -> Result<AnyHash, JsError> {
+    match hash_algorithm {
+        "blake2b" => Ok(AnyHash::Blake2b(AnyHash32::from_str(hash_root)?)),
+        "sha256" => Ok(AnyHash::Sha256(AnyHash32::from_str(hash_root)?)),
+        "sha512" => Ok(AnyHash::Sha512(AnyHash64::from_str(hash_root)?)),
+        _ => Err(JsError::new(&format!(
+            "Unknown hash algorithm: {}",
+            hash_algorithm
+        ))),
+    }
+}
@@ -146,7 +146,8 @@ impl Transaction {
     /// of the signature proof required for the transaction.
     ///
     /// ### Limitations
-    /// - HTLC redemption is not supported and will throw.
+    /// - HTLC redemption is not supported and will throw. Use `TransactionBuilder.htlcRegularTransferProof`,
+    ///   `htlcTimeoutResolveProof` or `htlcEarlyResolveProof` to build the proof instead.
     /// - For transaction to the staking contract, both signatures are made with the same keypair,
     ///   so it is not possible to interact with a staker that is different from the sender address
     ///   or using a different cold or signing key for validator transactions.
@@ -162,28 +163,10 @@ impl Transaction {
                 builder.sign_with_key_pair(key_pair.native_ref());
                 builder.generate().unwrap().proof
             }
-            TransactionProofBuilder::Htlc(mut _builder) => {
-                // TODO: Create a separate HTLC signing method that takes the type of proof as an argument
+            TransactionProofBuilder::Htlc(_) => {
                 return Err(JsError::new(
-                    "HTLC redemption transactions are not supported",
+                    "HTLC redemption transactions must be signed with the HTLC proof helpers of the TransactionBuilder",
                 ));
-
-                // // Redeem
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // builder.regular_transfer(hash_algorithm, pre_image, hash_count, hash_root, sig);
-
-                // // Refund
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // builder.timeout_resolve(sig);
-
-                // // Early resolve
-                // builder.early_resolve(htlc_sender_signature, htlc_recipient_signature);
-
-                // // Sign early
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // return Ok(sig);
-
-                // builder.generate().unwrap()
             }
             TransactionProofBuilder::OutStaking(mut builder) => {
                 builder.sign_with_key_pair(key_pair.native_ref());
@@ -1059,7 +1042,7 @@ impl PlainTransaction {
 
 /// Describes the state of a transaction as known by the client.
 #[cfg(feature = "client")]
-#[derive(Clone, serde::Serialize, serde::Deserialize, Tsify)]
+#[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, Tsify)]
 #[serde(rename_all = "lowercase")]
 pub enum TransactionState {
     /// The transaction only exists locally and has not been broadcast or accepted by any peers.
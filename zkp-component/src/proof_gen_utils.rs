@@ -65,6 +65,9 @@ pub async fn launch_generate_new_proof(
         .args(env::args().skip(1))
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
+        // Make sure the prover process doesn't outlive the node when this future is dropped. During
+        // shutdown, the running proof is given until the stage timeout to finish before that.
+        .kill_on_drop(true)
         .spawn()?;
 
     child
@@ -208,6 +208,18 @@ impl<N: Network> ZKPComponent<N> {
         }
     }
 
+    /// Resolves once the prover finished the proof it is generating, if any. This is used to let a
+    /// running proof finish during shutdown before the prover process is killed.
+    #[cfg(feature = "zkp-prover")]
+    pub fn running_proof_finished(&self) -> impl Future<Output = ()> + Send + 'static {
+        let proof_finished = self.zk_prover.as_ref().map(ZKProver::proof_finished);
+        async move {
+            if let Some(proof_finished) = proof_finished {
+                proof_finished.await;
+            }
+        }
+    }
+
     /// Returns if the prover is activated.
     pub fn is_zkp_prover_activated(&self) -> bool {
         #[cfg(feature = "zkp-prover")]
@@ -8,7 +8,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use futures::{future::BoxFuture, stream::BoxStream, FutureExt, Stream, StreamExt};
+use futures::{future::BoxFuture, stream::BoxStream, Future, FutureExt, Stream, StreamExt};
 use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, Direction};
@@ -17,7 +17,10 @@ use nimiq_network_interface::network::Network;
 use nimiq_primitives::policy::Policy;
 use nimiq_utils::spawn::spawn;
 use parking_lot::{lock_api::RwLockUpgradableReadGuard, RwLock, RwLockWriteGuard};
-use tokio::sync::oneshot::{channel, Sender};
+use tokio::sync::{
+    oneshot::{channel, Sender},
+    watch,
+};
 
 use crate::{proof_gen_utils::*, types::*};
 
@@ -29,6 +32,7 @@ use crate::{proof_gen_utils::*, types::*};
 /// - The election blocks stream
 /// - The genesis state
 /// - The current proof generation future if a proof is being generated
+/// - Whether a proof is being generated, to let it finish during shutdown
 /// - The path of the proving keys directory
 /// - The path of the prover binary
 ///
@@ -42,6 +46,7 @@ pub struct ZKProver<N: Network> {
     genesis_header_hash: [u8; 32],
     proof_future:
         Option<BoxFuture<'static, Result<(ZKPState, MacroBlock), ZKProofGenerationError>>>,
+    proving: watch::Sender<bool>,
     prover_keys_path: PathBuf,
     prover_path: Option<PathBuf>,
 }
@@ -112,6 +117,7 @@ impl<N: Network> ZKProver<N> {
             pending_election_blocks,
             election_stream: Box::pin(blockchain_election_rx),
             proof_future: None,
+            proving: watch::Sender::new(false),
             prover_keys_path,
             prover_path,
         }
@@ -124,6 +130,14 @@ impl<N: Network> ZKProver<N> {
         }
     }
 
+    /// Resolves once no proof is being generated anymore, or once the prover was dropped.
+    pub(crate) fn proof_finished(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut proving = self.proving.subscribe();
+        async move {
+            let _ = proving.wait_for(|proving| !proving).await;
+        }
+    }
+
     /// The broadcasting of the generated zk proof.
     fn broadcast_zk_proof(network: &Arc<N>, zk_proof: ZKProof) {
         let network = Arc::clone(network);
@@ -169,6 +183,7 @@ impl<N: Network> ZKProver<N> {
                 .boxed(),
             );
             self.sender = Some(sender);
+            self.proving.send_replace(true);
         } else {
             log::debug!(
                 block_height = zkp_state.latest_block.block_number(),
@@ -203,6 +218,7 @@ impl<N: Network> Stream for ZKProver<N> {
             if let Poll::Ready(proof) = proof_future.poll_unpin(cx) {
                 self.proof_future = None;
                 self.sender = None;
+                self.proving.send_replace(false);
                 match proof {
                     Ok((new_zkp_state, block)) => {
                         assert!(